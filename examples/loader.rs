@@ -0,0 +1,29 @@
+use std::fs::File;
+use std::io::BufReader;
+
+use pdf_parser::loader::Loader;
+use pdf_parser::object::Object;
+
+fn main() {
+    // open the file from argument and read only what is needed
+    let args: Vec<String> = std::env::args().collect();
+    let path = std::path::Path::new(&args[1]);
+    let file = File::open(path).unwrap();
+
+    let mut loader = Loader::new(BufReader::new(file)).expect("Failed to read PDF file");
+    let header = loader.header();
+    println!("PDF version: {}.{}", header.major, header.minor);
+    println!("PDF object count: {:?}", loader.references().count());
+    let trailer = loader.trailer().expect("Failed to parse PDF trailer");
+    println!("PDF trailer: {:?}", trailer.dictionary);
+    println!("PDF startxref: {:?}", trailer.startxref);
+
+    let root = trailer
+        .dictionary
+        .get("Root")
+        .and_then(Object::as_reference);
+    if let Some(root) = root {
+        let catalog = loader.load(root).expect("Failed to load PDF catalog");
+        println!("PDF catalog: {:?}", catalog.object());
+    }
+}
@@ -0,0 +1,15 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use pdf_parser::filter;
+use pdf_parser::object::Object;
+
+// Decodes a stream object, e.g. `<< /Filter /FlateDecode >> stream ... endstream`,
+// so that every filter of `filter::decode` and its parameters are reached.
+fuzz_target!(|data: &[u8]| {
+    if let Ok((_, object)) = Object::parse(data) {
+        if let Some((dictionary, data)) = object.as_stream() {
+            let _ = filter::decode(dictionary, data);
+        }
+    }
+});
@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use pdf_parser::object::PDF;
+use pdf_parser::options::ParseOptions;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = PDF::parse(data);
+    let _ = PDF::parse_with_warnings(data, &ParseOptions::lenient());
+});
@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use pdf_parser::object::Trailer;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = Trailer::parse(data);
+});
@@ -0,0 +1,9 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use pdf_parser::object::CrossReferenceTable;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = CrossReferenceTable::parse(data);
+    let _ = CrossReferenceTable::parse_section(data);
+});
@@ -0,0 +1,550 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::error::ParseError;
+use crate::object::{DictionaryObject, IndirectReference, Object, PDF};
+use crate::utils::decode_name;
+
+/// Maximum depth of form field `/Kids`, outline items and `/Next` chains followed.
+const MAX_DEPTH: usize = 64;
+
+/// An action dictionary, typed by its `/S` entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    /// Go to a destination in the current document.
+    GoTo,
+    /// Go to a destination in another document.
+    GoToR {
+        file: Option<String>,
+        new_window: Option<bool>,
+    },
+    /// Go to a destination in an embedded document.
+    GoToE {
+        file: Option<String>,
+        /// The `/N` name of the target embedded file, if given.
+        target: Option<String>,
+        new_window: Option<bool>,
+    },
+    /// Launch an application or open a file.
+    Launch {
+        file: Option<String>,
+        /// The command line parameters of the Windows-specific `/Win` dictionary.
+        parameters: Option<String>,
+        new_window: Option<bool>,
+    },
+    URI {
+        uri: String,
+        is_map: bool,
+    },
+    SubmitForm {
+        url: Option<String>,
+        /// The fields included or excluded, by name.
+        fields: Vec<String>,
+        flags: i32,
+    },
+    ImportData {
+        file: Option<String>,
+    },
+    JavaScript {
+        source: String,
+    },
+    /// Play a media rendition, optionally running a script.
+    Rendition {
+        source: Option<String>,
+    },
+    Named {
+        name: String,
+    },
+    /// Any other action type, with its `/S` name.
+    Other {
+        subtype: String,
+    },
+}
+
+impl Action {
+    /// Reads an action dictionary, `None` if `object` is not a dictionary.
+    pub fn from_object(pdf: &PDF, object: &Object) -> Option<Self> {
+        let object = pdf.resolve(object);
+        let dictionary = object.as_dictionary()?;
+        let get = |key: &str| match pdf.resolve(entry(dictionary, key)?) {
+            Object::Null => None,
+            value => Some(value),
+        };
+        let file = || get("F").and_then(|file| file_name(pdf, file));
+        let new_window = || get("NewWindow").and_then(Object::as_bool);
+        let subtype = get("S")
+            .and_then(Object::as_name)
+            .map(decode_name)
+            .unwrap_or_default();
+
+        Some(match &*subtype {
+            "GoTo" => Action::GoTo,
+            "GoToR" => Action::GoToR {
+                file: file(),
+                new_window: new_window(),
+            },
+            "GoToE" => Action::GoToE {
+                file: file(),
+                target: get("T")
+                    .and_then(|target| pdf.get(target, "N"))
+                    .and_then(Object::text_string),
+                new_window: new_window(),
+            },
+            "Launch" => {
+                let windows = get("Win");
+                Action::Launch {
+                    file: file().or_else(|| {
+                        windows
+                            .and_then(|windows| pdf.get(windows, "F"))
+                            .and_then(|file| file_name(pdf, file))
+                    }),
+                    parameters: windows
+                        .and_then(|windows| pdf.get(windows, "P"))
+                        .and_then(Object::text_string),
+                    new_window: new_window(),
+                }
+            }
+            "URI" => Action::URI {
+                uri: get("URI")
+                    .and_then(Object::string_bytes)
+                    .map(|uri| String::from_utf8_lossy(&uri).into_owned())
+                    .unwrap_or_default(),
+                is_map: get("IsMap").and_then(Object::as_bool).unwrap_or(false),
+            },
+            "SubmitForm" => Action::SubmitForm {
+                url: file(),
+                fields: get("Fields")
+                    .and_then(Object::as_array)
+                    .unwrap_or_default()
+                    .iter()
+                    .filter_map(|field| match pdf.resolve(field) {
+                        // Fields are given by name, or directly as a field dictionary.
+                        field @ Object::Dictionary(..) => pdf.get(field, "T")?.text_string(),
+                        name => name.text_string(),
+                    })
+                    .collect(),
+                flags: get("Flags").and_then(Object::as_i32).unwrap_or(0),
+            },
+            "ImportData" => Action::ImportData { file: file() },
+            "JavaScript" => Action::JavaScript {
+                source: get("JS")
+                    .and_then(|js| javascript_source(pdf, js))
+                    .unwrap_or_default(),
+            },
+            "Rendition" => Action::Rendition {
+                source: get("JS").and_then(|js| javascript_source(pdf, js)),
+            },
+            "Named" => Action::Named {
+                name: get("N")
+                    .and_then(Object::as_name)
+                    .map(decode_name)
+                    .unwrap_or_default()
+                    .into_owned(),
+            },
+            subtype => Action::Other {
+                subtype: subtype.to_string(),
+            },
+        })
+    }
+
+    /// The `/S` name of the action.
+    pub fn subtype(&self) -> &str {
+        match self {
+            Action::GoTo => "GoTo",
+            Action::GoToR { .. } => "GoToR",
+            Action::GoToE { .. } => "GoToE",
+            Action::Launch { .. } => "Launch",
+            Action::URI { .. } => "URI",
+            Action::SubmitForm { .. } => "SubmitForm",
+            Action::ImportData { .. } => "ImportData",
+            Action::JavaScript { .. } => "JavaScript",
+            Action::Rendition { .. } => "Rendition",
+            Action::Named { .. } => "Named",
+            Action::Other { subtype } => subtype,
+        }
+    }
+
+    /// The script the action runs, if any.
+    pub fn javascript(&self) -> Option<&str> {
+        match self {
+            Action::JavaScript { source } => Some(source),
+            Action::Rendition { source } => source.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// Looks up `key` in an action dictionary, also matching a key written with
+/// `#xx` escapes such as `/J#53`.
+fn entry<'o, 'a>(dictionary: &'o DictionaryObject<'a>, key: &str) -> Option<&'o Object<'a>> {
+    dictionary.get(key).or_else(|| {
+        dictionary
+            .iter()
+            .find(|(name, _)| name.decode() == key)
+            .map(|(_, value)| value)
+    })
+}
+
+/// Reads the name of a file specification, either a string or a dictionary
+/// with `/UF`, `/F` or platform-specific entries.
+pub fn file_name(pdf: &PDF, object: &Object) -> Option<String> {
+    let object = pdf.resolve(object);
+    if object.as_dictionary().is_none() {
+        return object.text_string();
+    }
+    ["UF", "F", "Unix", "DOS", "Mac"]
+        .iter()
+        .find_map(|key| pdf.get(object, key)?.text_string())
+}
+
+/// Reads a `/JS` entry, either a text string or a stream.
+/// Streams that cannot be decoded are read as raw text.
+pub fn javascript_source(pdf: &PDF, js: &Object) -> Option<String> {
+    match pdf.resolve(js) {
+        Object::Dictionary(dictionary, data) => {
+            let data = crate::filter::decode(dictionary, data).unwrap_or(data.to_vec());
+            Some(crate::encoding::decode_text_string(&data))
+        }
+        string => string.text_string(),
+    }
+}
+
+/// Where an action is attached.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TriggerSource {
+    /// The document catalog.
+    Document,
+    /// A document-level script of the `/Names` `/JavaScript` tree, with its name.
+    NameTree(String),
+    /// A page, by index.
+    Page(usize),
+    /// An annotation, by page index and index in the page `/Annots`.
+    Annotation { page: usize, index: usize },
+    /// A form field or widget of the `/AcroForm` field tree.
+    Field,
+    /// An item of the document outline.
+    OutlineItem,
+}
+
+/// An event of an object that runs an action.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trigger {
+    pub source: TriggerSource,
+    /// The object holding the action, if it is an indirect object.
+    pub owner: Option<IndirectReference>,
+    /// The entry holding the action: `OpenAction`, `A`, an `/AA` event such as `O`
+    /// or `K`, or `JavaScript` for the scripts of the name tree.
+    pub event: String,
+    /// How the action is reached from the trailer, e.g. `/Root/Pages[0]/Annots[1]/AA/U`.
+    pub path: String,
+    /// The index of the first action run, in `ActionGraph::actions`.
+    pub action: usize,
+}
+
+/// An action of the graph, with the actions it chains to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActionNode {
+    pub reference: Option<IndirectReference>,
+    pub action: Action,
+    /// The indices of the `/Next` actions, run after this one in order.
+    pub next: Vec<usize>,
+    /// Whether `/Next` is an array rather than a single action.
+    next_is_array: bool,
+}
+
+/// The actions of a document and the events running them.
+/// Indirect actions appear once however many times they are referenced.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ActionGraph {
+    pub actions: Vec<ActionNode>,
+    pub triggers: Vec<Trigger>,
+    /// `/Next` edges `(from, to)` that close a loop, i.e. lead back to an action
+    /// that is already running.
+    pub cycles: Vec<(usize, usize)>,
+    /// Why sources were skipped, e.g. a broken page tree hides the page and
+    /// annotation actions, while the other sources are still read.
+    pub errors: Vec<ParseError>,
+}
+
+impl ActionGraph {
+    /// Returns the actions run by an action and its `/Next` chain, in order.
+    /// Each action is listed once, so a chain with a cycle ends where it loops.
+    pub fn chain(&self, action: usize) -> Vec<usize> {
+        let mut result = Vec::new();
+        self.walk(action, String::new(), &mut |index, _| result.push(index));
+        result
+    }
+
+    /// Visits the actions of a chain in order with their path relative to `path`.
+    pub(crate) fn walk(&self, action: usize, path: String, visit: &mut impl FnMut(usize, &str)) {
+        let mut visited = HashSet::new();
+        let mut pending = vec![(action, path)];
+        while let Some((index, path)) = pending.pop() {
+            if !visited.insert(index) {
+                continue;
+            }
+            visit(index, &path);
+            let node = &self.actions[index];
+            // Pushed in reverse, so that the first `/Next` action is run first.
+            for (position, &next) in node.next.iter().enumerate().rev() {
+                let path = match node.next_is_array {
+                    true => format!("{}/Next[{}]", path, position),
+                    false => format!("{}/Next", path),
+                };
+                pending.push((next, path));
+            }
+        }
+    }
+}
+
+/// Builds the action graph while walking the places actions can be attached to.
+struct GraphBuilder<'p, 'a> {
+    pdf: &'p PDF<'a>,
+    graph: ActionGraph,
+    indices: HashMap<IndirectReference, usize>,
+    /// The actions being read, to tell loops apart from actions shared by two chains.
+    running: HashSet<usize>,
+}
+
+impl<'p, 'a> GraphBuilder<'p, 'a> {
+    /// Adds an action and the actions chained to it, returning its index.
+    fn action(&mut self, object: &'p Object<'a>, depth: usize) -> Option<usize> {
+        let reference = object.as_reference();
+        if let Some(index) = reference.and_then(|reference| self.indices.get(&reference)) {
+            return Some(*index);
+        }
+        let action = Action::from_object(self.pdf, object)?;
+        let index = self.graph.actions.len();
+        self.graph.actions.push(ActionNode {
+            reference,
+            action,
+            next: Vec::new(),
+            next_is_array: false,
+        });
+        if let Some(reference) = reference {
+            self.indices.insert(reference, index);
+        }
+        if depth > MAX_DEPTH {
+            return Some(index);
+        }
+
+        self.running.insert(index);
+        let (next, next_is_array) = match self
+            .pdf
+            .resolve(object)
+            .as_dictionary()
+            .and_then(|dictionary| entry(dictionary, "Next"))
+        {
+            Some(next) => match self.pdf.resolve(next) {
+                Object::Array(actions) => (actions.iter().collect(), true),
+                _ => (vec![next], false),
+            },
+            None => (Vec::new(), false),
+        };
+        let mut indices = Vec::new();
+        for next in next {
+            if let Some(next) = self.action(next, depth + 1) {
+                if self.running.contains(&next) {
+                    self.graph.cycles.push((index, next));
+                }
+                indices.push(next);
+            }
+        }
+        self.running.remove(&index);
+
+        let node = &mut self.graph.actions[index];
+        node.next = indices;
+        node.next_is_array = next_is_array;
+        Some(index)
+    }
+
+    fn trigger(
+        &mut self,
+        source: TriggerSource,
+        owner: Option<IndirectReference>,
+        event: &str,
+        path: String,
+        action: &'p Object<'a>,
+    ) {
+        if let Some(action) = self.action(action, 0) {
+            self.graph.triggers.push(Trigger {
+                source,
+                owner,
+                event: event.to_string(),
+                path,
+                action,
+            });
+        }
+    }
+
+    /// Reads the `/A` action and the `/AA` trigger events of an object.
+    fn actions(&mut self, source: TriggerSource, object: &'p Object<'a>, path: &str) {
+        let owner = object.as_reference();
+        let dictionary = match self.pdf.resolve(object).as_dictionary() {
+            Some(dictionary) => dictionary,
+            None => return,
+        };
+        if let Some(action) = dictionary.get("A") {
+            let path = format!("{}/A", path);
+            self.trigger(source.clone(), owner, "A", path, action);
+        }
+        self.additional_actions(source, owner, dictionary, path);
+    }
+
+    fn additional_actions(
+        &mut self,
+        source: TriggerSource,
+        owner: Option<IndirectReference>,
+        dictionary: &'p DictionaryObject<'a>,
+        path: &str,
+    ) {
+        let events = match dictionary
+            .get("AA")
+            .and_then(|events| self.pdf.resolve(events).as_dictionary())
+        {
+            Some(events) => events,
+            None => return,
+        };
+        let mut events: Vec<_> = events.iter().collect();
+        events.sort_by_key(|(event, _)| event.0);
+        for (event, action) in events {
+            let path = format!("{}/AA/{}", path, event.0);
+            self.trigger(source.clone(), owner, event.0, path, action);
+        }
+    }
+
+    fn fields(
+        &mut self,
+        fields: &'p [Object<'a>],
+        path: &str,
+        depth: usize,
+        visited: &mut HashSet<IndirectReference>,
+    ) {
+        if depth > MAX_DEPTH {
+            return;
+        }
+        for (index, field) in fields.iter().enumerate() {
+            if let Some(reference) = field.as_reference() {
+                if !visited.insert(reference) {
+                    continue;
+                }
+            }
+            let path = format!("{}[{}]", path, index);
+            self.actions(TriggerSource::Field, field, &path);
+            if let Some(kids) = self.pdf.get(field, "Kids").and_then(Object::as_array) {
+                self.fields(kids, &format!("{}/Kids", path), depth + 1, visited);
+            }
+        }
+    }
+
+    fn outline_items(&mut self, first: &'p Object<'a>, path: String) {
+        let mut visited = HashSet::new();
+        let mut pending = vec![(first, path, 0)];
+        while let Some((item, path, depth)) = pending.pop() {
+            if depth > MAX_DEPTH {
+                continue;
+            }
+            if let Some(reference) = item.as_reference() {
+                if !visited.insert(reference) {
+                    continue;
+                }
+            }
+            let dictionary = match self.pdf.resolve(item).as_dictionary() {
+                Some(dictionary) => dictionary,
+                None => continue,
+            };
+            self.actions(TriggerSource::OutlineItem, item, &path);
+            // Pushed in reverse, so that children are read before siblings.
+            if let Some(next) = dictionary.get("Next") {
+                pending.push((next, format!("{}/Next", path), depth));
+            }
+            if let Some(child) = dictionary.get("First") {
+                pending.push((child, format!("{}/First", path), depth + 1));
+            }
+        }
+    }
+}
+
+impl<'a> PDF<'a> {
+    /// Builds the graph of the actions of the document and of the events running
+    /// them: the catalog `/OpenAction` and `/AA`, the `/Names` `/JavaScript` tree,
+    /// page and annotation actions, form fields and outline items. A source that
+    /// cannot be read is skipped and recorded in `errors`.
+    pub fn action_graph(&self) -> Result<ActionGraph, ParseError> {
+        let root = self
+            .trailer
+            .dictionary
+            .get("Root")
+            .ok_or(ParseError::InvalidPDFTrailer)?;
+        let catalog = self.catalog()?;
+        let mut builder = GraphBuilder {
+            pdf: self,
+            graph: ActionGraph::default(),
+            indices: HashMap::new(),
+            running: HashSet::new(),
+        };
+
+        // `/OpenAction` may also be a destination array, which is not an action.
+        if let Some(action) = catalog.get("OpenAction") {
+            let path = "/Root/OpenAction".to_string();
+            let owner = root.as_reference();
+            builder.trigger(TriggerSource::Document, owner, "OpenAction", path, action);
+        }
+        if let Some(dictionary) = catalog.as_dictionary() {
+            let owner = root.as_reference();
+            builder.additional_actions(TriggerSource::Document, owner, dictionary, "/Root");
+        }
+
+        match self.name_tree("JavaScript") {
+            Ok(Some(tree)) => {
+                for (name, action) in tree.iter() {
+                    let name = crate::encoding::decode_text_string(&name);
+                    let path = format!("/Root/Names/JavaScript({})", name);
+                    let source = TriggerSource::NameTree(name);
+                    builder.trigger(source, None, "JavaScript", path, action);
+                }
+            }
+            Ok(None) => {}
+            Err(error) => builder.graph.errors.push(error),
+        }
+
+        let pages = self.pages().unwrap_or_else(|error| {
+            builder.graph.errors.push(error);
+            Vec::new()
+        });
+        for (index, page) in pages.iter().enumerate() {
+            let path = format!("/Root/Pages[{}]", index);
+            let (source, owner) = (TriggerSource::Page(index), Some(page.reference));
+            builder.additional_actions(source, owner, page.dictionary, &path);
+            let annotations = page
+                .dictionary
+                .get("Annots")
+                .and_then(|annotations| self.resolve(annotations).as_array())
+                .unwrap_or_default();
+            for (position, annotation) in annotations.iter().enumerate() {
+                let source = TriggerSource::Annotation {
+                    page: index,
+                    index: position,
+                };
+                let path = format!("{}/Annots[{}]", path, position);
+                builder.actions(source, annotation, &path);
+            }
+        }
+
+        if let Some(fields) = self
+            .get(catalog, "AcroForm")
+            .and_then(|form| self.get(form, "Fields"))
+            .and_then(Object::as_array)
+        {
+            builder.fields(fields, "/Root/AcroForm/Fields", 0, &mut HashSet::new());
+        }
+
+        if let Some(first) = self
+            .get(catalog, "Outlines")
+            .and_then(|outlines| outlines.get("First"))
+        {
+            builder.outline_items(first, "/Root/Outlines/First".to_string());
+        }
+
+        Ok(builder.graph)
+    }
+}
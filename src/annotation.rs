@@ -0,0 +1,299 @@
+use crate::action::{file_name, Action};
+use crate::document::Page;
+use crate::error::ParseError;
+use crate::interpreter::Rect;
+use crate::object::{IndirectReference, Object, PDF};
+
+/// Annotation flags (`/F`).
+pub const INVISIBLE: u32 = 1;
+pub const HIDDEN: u32 = 1 << 1;
+pub const PRINT: u32 = 1 << 2;
+pub const NO_ZOOM: u32 = 1 << 3;
+pub const NO_ROTATE: u32 = 1 << 4;
+pub const NO_VIEW: u32 = 1 << 5;
+pub const READ_ONLY: u32 = 1 << 6;
+pub const LOCKED: u32 = 1 << 7;
+pub const TOGGLE_NO_VIEW: u32 = 1 << 8;
+pub const LOCKED_CONTENTS: u32 = 1 << 9;
+
+/// The `/Subtype` of an annotation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AnnotationType {
+    Text,
+    Link,
+    FreeText,
+    Line,
+    Square,
+    Circle,
+    Polygon,
+    PolyLine,
+    Highlight,
+    Underline,
+    Squiggly,
+    StrikeOut,
+    Stamp,
+    Caret,
+    Ink,
+    Popup,
+    FileAttachment,
+    Sound,
+    Movie,
+    Widget,
+    Screen,
+    PrinterMark,
+    TrapNet,
+    Watermark,
+    /// `/3D`
+    ThreeD,
+    Redact,
+    Projection,
+    RichMedia,
+    /// Any other subtype, with its name.
+    Other(String),
+}
+
+impl AnnotationType {
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "Text" => AnnotationType::Text,
+            "Link" => AnnotationType::Link,
+            "FreeText" => AnnotationType::FreeText,
+            "Line" => AnnotationType::Line,
+            "Square" => AnnotationType::Square,
+            "Circle" => AnnotationType::Circle,
+            "Polygon" => AnnotationType::Polygon,
+            "PolyLine" => AnnotationType::PolyLine,
+            "Highlight" => AnnotationType::Highlight,
+            "Underline" => AnnotationType::Underline,
+            "Squiggly" => AnnotationType::Squiggly,
+            "StrikeOut" => AnnotationType::StrikeOut,
+            "Stamp" => AnnotationType::Stamp,
+            "Caret" => AnnotationType::Caret,
+            "Ink" => AnnotationType::Ink,
+            "Popup" => AnnotationType::Popup,
+            "FileAttachment" => AnnotationType::FileAttachment,
+            "Sound" => AnnotationType::Sound,
+            "Movie" => AnnotationType::Movie,
+            "Widget" => AnnotationType::Widget,
+            "Screen" => AnnotationType::Screen,
+            "PrinterMark" => AnnotationType::PrinterMark,
+            "TrapNet" => AnnotationType::TrapNet,
+            "Watermark" => AnnotationType::Watermark,
+            "3D" => AnnotationType::ThreeD,
+            "Redact" => AnnotationType::Redact,
+            "Projection" => AnnotationType::Projection,
+            "RichMedia" => AnnotationType::RichMedia,
+            other => AnnotationType::Other(other.to_string()),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            AnnotationType::Text => "Text",
+            AnnotationType::Link => "Link",
+            AnnotationType::FreeText => "FreeText",
+            AnnotationType::Line => "Line",
+            AnnotationType::Square => "Square",
+            AnnotationType::Circle => "Circle",
+            AnnotationType::Polygon => "Polygon",
+            AnnotationType::PolyLine => "PolyLine",
+            AnnotationType::Highlight => "Highlight",
+            AnnotationType::Underline => "Underline",
+            AnnotationType::Squiggly => "Squiggly",
+            AnnotationType::StrikeOut => "StrikeOut",
+            AnnotationType::Stamp => "Stamp",
+            AnnotationType::Caret => "Caret",
+            AnnotationType::Ink => "Ink",
+            AnnotationType::Popup => "Popup",
+            AnnotationType::FileAttachment => "FileAttachment",
+            AnnotationType::Sound => "Sound",
+            AnnotationType::Movie => "Movie",
+            AnnotationType::Widget => "Widget",
+            AnnotationType::Screen => "Screen",
+            AnnotationType::PrinterMark => "PrinterMark",
+            AnnotationType::TrapNet => "TrapNet",
+            AnnotationType::Watermark => "Watermark",
+            AnnotationType::ThreeD => "3D",
+            AnnotationType::Redact => "Redact",
+            AnnotationType::Projection => "Projection",
+            AnnotationType::RichMedia => "RichMedia",
+            AnnotationType::Other(name) => name,
+        }
+    }
+
+    /// Whether the annotation is a markup annotation, i.e. a comment that may have
+    /// an author, a popup and replies.
+    pub fn is_markup(&self) -> bool {
+        !matches!(
+            self,
+            AnnotationType::Link
+                | AnnotationType::Popup
+                | AnnotationType::Widget
+                | AnnotationType::Screen
+                | AnnotationType::PrinterMark
+                | AnnotationType::TrapNet
+                | AnnotationType::Watermark
+                | AnnotationType::ThreeD
+                | AnnotationType::RichMedia
+                | AnnotationType::Other(_)
+        )
+    }
+}
+
+/// An appearance stream of the `/AP` dictionary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppearanceStream {
+    /// `N` (normal), `R` (rollover) or `D` (down).
+    pub kind: String,
+    /// The appearance state selecting the stream, for annotations with several.
+    pub state: Option<String>,
+    pub reference: Option<IndirectReference>,
+}
+
+/// An annotation of a page.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Annotation {
+    /// The position in the page `/Annots`.
+    pub index: usize,
+    pub reference: Option<IndirectReference>,
+    pub subtype: AnnotationType,
+    pub rect: Option<Rect>,
+    pub flags: u32,
+    /// The `/Contents` text, or the alternate description of non-text annotations.
+    pub contents: Option<String>,
+    /// The `/NM` name, unique on the page.
+    pub name: Option<String>,
+    /// The `/M` modification date, as written.
+    pub modified: Option<String>,
+    /// The `/T` author of markup annotations.
+    pub author: Option<String>,
+    pub appearance_state: Option<String>,
+    pub appearances: Vec<AppearanceStream>,
+    /// The `/A` action of links and widgets.
+    pub action: Option<Action>,
+    /// The `/AA` actions, by trigger event, sorted by event name.
+    pub additional_actions: Vec<(String, Action)>,
+    /// The `/FS` file name of file attachments.
+    pub file: Option<String>,
+}
+
+impl Annotation {
+    /// Reads an annotation dictionary, `None` if `object` is not a dictionary.
+    pub fn from_object(pdf: &PDF, index: usize, object: &Object) -> Option<Self> {
+        let resolved = pdf.resolve(object);
+        resolved.as_dictionary()?;
+        let get = |key: &str| pdf.get(resolved, key);
+        let text = |key: &str| get(key).and_then(Object::text_string);
+
+        let subtype = get("Subtype")
+            .and_then(Object::as_name)
+            .map(AnnotationType::from_name)
+            .unwrap_or_else(|| AnnotationType::Other(String::new()));
+        let author = match subtype.is_markup() {
+            true => text("T"),
+            false => None,
+        };
+        let file = match subtype {
+            AnnotationType::FileAttachment => get("FS").and_then(|file| file_name(pdf, file)),
+            _ => None,
+        };
+
+        let mut additional_actions: Vec<(String, Action)> = get("AA")
+            .and_then(Object::as_dictionary)
+            .map(|actions| {
+                actions
+                    .iter()
+                    .filter_map(|(event, action)| {
+                        Some((event.0.to_string(), Action::from_object(pdf, action)?))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        additional_actions.sort_by(|a, b| a.0.cmp(&b.0));
+
+        Some(Annotation {
+            index,
+            reference: object.as_reference(),
+            subtype,
+            rect: get("Rect").and_then(|rect| Rect::from_object(pdf, rect)),
+            flags: get("F").and_then(Object::as_i32).unwrap_or(0) as u32,
+            contents: text("Contents"),
+            name: text("NM"),
+            modified: text("M"),
+            author,
+            appearance_state: get("AS").and_then(Object::as_name).map(str::to_string),
+            appearances: get("AP")
+                .map(|appearances| appearance_streams(pdf, appearances))
+                .unwrap_or_default(),
+            action: get("A").and_then(|action| Action::from_object(pdf, action)),
+            additional_actions,
+            file,
+        })
+    }
+
+    pub fn has_flag(&self, flag: u32) -> bool {
+        self.flags & flag != 0
+    }
+}
+
+/// Lists the streams of an `/AP` dictionary: `N`, `R` then `D`, with the states of
+/// each sorted by name.
+fn appearance_streams(pdf: &PDF, appearances: &Object) -> Vec<AppearanceStream> {
+    let mut streams = Vec::new();
+    for kind in ["N", "R", "D"] {
+        let entry = match pdf.resolve(appearances).get(kind) {
+            Some(entry) => entry,
+            None => continue,
+        };
+        match pdf.resolve(entry) {
+            // A dictionary without stream data maps appearance states to streams.
+            Object::Dictionary(states, &[]) => {
+                let mut states: Vec<_> = states.iter().collect();
+                states.sort_by_key(|(state, _)| state.0);
+                streams.extend(states.into_iter().map(|(state, stream)| AppearanceStream {
+                    kind: kind.to_string(),
+                    state: Some(state.0.to_string()),
+                    reference: stream.as_reference(),
+                }));
+            }
+            _ => streams.push(AppearanceStream {
+                kind: kind.to_string(),
+                state: None,
+                reference: entry.as_reference(),
+            }),
+        }
+    }
+    streams
+}
+
+impl<'p, 'a> Page<'p, 'a> {
+    /// Returns the annotations of the page in `/Annots` order, skipping entries
+    /// that are not dictionaries.
+    pub fn annotations(&self) -> Vec<Annotation> {
+        let annotations = self
+            .dictionary
+            .get("Annots")
+            .and_then(|annotations| self.pdf.resolve(annotations).as_array())
+            .unwrap_or_default();
+        annotations
+            .iter()
+            .enumerate()
+            .filter_map(|(index, annotation)| Annotation::from_object(self.pdf, index, annotation))
+            .collect()
+    }
+}
+
+impl PDF<'_> {
+    /// Returns the annotations of every page, with the index of the page.
+    pub fn annotations(&self) -> Result<Vec<(usize, Annotation)>, ParseError> {
+        let mut result = Vec::new();
+        for (index, page) in self.pages()?.iter().enumerate() {
+            result.extend(
+                page.annotations()
+                    .into_iter()
+                    .map(|annotation| (index, annotation)),
+            );
+        }
+        Ok(result)
+    }
+}
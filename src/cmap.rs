@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+
+use crate::content::Content;
+use crate::error::ParseError;
+use crate::object::Object;
+
+/// Largest number of bytes in a character code.
+const MAX_CODE_LENGTH: usize = 4;
+
+/// A range of codes of one byte length, e.g. `<8140> <9FFC>`.
+/// A code belongs to the range if each of its bytes is within the bounds of that byte.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CodespaceRange {
+    low: Vec<u8>,
+    high: Vec<u8>,
+}
+
+impl CodespaceRange {
+    fn contains(&self, code: &[u8]) -> bool {
+        code.len() == self.low.len()
+            && code
+                .iter()
+                .zip(self.low.iter().zip(&self.high))
+                .all(|(byte, (low, high))| low <= byte && byte <= high)
+    }
+}
+
+/// A `bfrange` mapping consecutive codes to consecutive UTF-16 strings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct UnicodeRange {
+    low: u32,
+    high: u32,
+    destination: Vec<u16>,
+}
+
+/// A `cidrange` mapping consecutive codes to consecutive CIDs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct CidRange {
+    low: u32,
+    high: u32,
+    cid: u32,
+}
+
+/// A CMap, either mapping character codes to CIDs (a font `/Encoding`)
+/// or to Unicode (a `/ToUnicode` stream).
+#[derive(Debug, Clone, Default)]
+pub struct CMap {
+    codespace: Vec<CodespaceRange>,
+    unicode: HashMap<u32, String>,
+    unicode_ranges: Vec<UnicodeRange>,
+    cids: HashMap<u32, u32>,
+    cid_ranges: Vec<CidRange>,
+    identity: bool,
+}
+
+impl CMap {
+    /// The `Identity-H` and `Identity-V` CMaps, mapping two-byte codes to the same CIDs.
+    pub fn identity() -> Self {
+        Self {
+            codespace: vec![CodespaceRange {
+                low: vec![0x00, 0x00],
+                high: vec![0xFF, 0xFF],
+            }],
+            identity: true,
+            ..Default::default()
+        }
+    }
+
+    /// Parses a CMap program.
+    /// CMaps are PostScript, which tokenizes like a content stream, so the sections are
+    /// read from the operations ending them, e.g. `endbfchar` with its operands.
+    pub fn parse(data: &[u8]) -> Result<Self, ParseError> {
+        let content = Content::parse(data)?;
+        let mut cmap = CMap::default();
+
+        for operation in content.operations {
+            let operands = &operation.operands;
+            match operation.operator {
+                "endcodespacerange" => {
+                    for pair in operands.chunks_exact(2) {
+                        let low = string(&pair[0])?;
+                        let high = string(&pair[1])?;
+                        if low.len() != high.len() || low.is_empty() || low.len() > MAX_CODE_LENGTH
+                        {
+                            return Err(ParseError::InvalidPDFCMap);
+                        }
+                        cmap.codespace.push(CodespaceRange { low, high });
+                    }
+                }
+                "endbfchar" => {
+                    for pair in operands.chunks_exact(2) {
+                        let code = code(&pair[0])?;
+                        let text = match &pair[1] {
+                            Object::Name(name) => crate::glyph_list::to_unicode(name.0)
+                                .ok_or(ParseError::InvalidPDFCMap)?,
+                            destination => utf16(&string(destination)?),
+                        };
+                        cmap.unicode.insert(code, text);
+                    }
+                }
+                "endbfrange" => {
+                    for triple in operands.chunks_exact(3) {
+                        let low = code(&triple[0])?;
+                        let high = code(&triple[1])?;
+                        if high < low {
+                            return Err(ParseError::InvalidPDFCMap);
+                        }
+                        match &triple[2] {
+                            // Each code of the range has its own destination.
+                            Object::Array(destinations) => {
+                                for (code, destination) in (low..=high).zip(destinations) {
+                                    cmap.unicode.insert(code, utf16(&string(destination)?));
+                                }
+                            }
+                            destination => {
+                                let destination = string(destination)?
+                                    .chunks(2)
+                                    .map(|pair| {
+                                        u16::from_be_bytes([pair[0], *pair.get(1).unwrap_or(&0)])
+                                    })
+                                    .collect();
+                                cmap.unicode_ranges.push(UnicodeRange {
+                                    low,
+                                    high,
+                                    destination,
+                                });
+                            }
+                        }
+                    }
+                }
+                "endcidchar" => {
+                    for pair in operands.chunks_exact(2) {
+                        let cid = pair[1].as_i32().ok_or(ParseError::InvalidPDFCMap)?;
+                        cmap.cids.insert(code(&pair[0])?, cid.max(0) as u32);
+                    }
+                }
+                "endcidrange" => {
+                    for triple in operands.chunks_exact(3) {
+                        let cid = triple[2].as_i32().ok_or(ParseError::InvalidPDFCMap)?;
+                        cmap.cid_ranges.push(CidRange {
+                            low: code(&triple[0])?,
+                            high: code(&triple[1])?,
+                            cid: cid.max(0) as u32,
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(cmap)
+    }
+
+    /// Splits a string into character codes according to the codespace ranges.
+    /// Bytes matching no range are read as one-byte codes.
+    pub fn codes(&self, text: &[u8]) -> Vec<u32> {
+        let mut codes = Vec::new();
+        let mut rest = text;
+        while !rest.is_empty() {
+            let length = (1..=MAX_CODE_LENGTH.min(rest.len()))
+                .find(|&length| {
+                    self.codespace
+                        .iter()
+                        .any(|range| range.contains(&rest[..length]))
+                })
+                .unwrap_or(1);
+            codes.push(to_code(&rest[..length]));
+            rest = &rest[length..];
+        }
+        codes
+    }
+
+    /// Whether `code` can be a one-byte code, which is the case word spacing applies to.
+    pub fn is_single_byte(&self, code: u32) -> bool {
+        code <= 0xFF
+            && (self.codespace.is_empty()
+                || self
+                    .codespace
+                    .iter()
+                    .any(|range| range.contains(&[code as u8])))
+    }
+
+    /// Returns the CID selected by a character code, 0 (`.notdef`) if there is none.
+    pub fn cid(&self, code: u32) -> u32 {
+        if self.identity {
+            return code;
+        }
+        if let Some(cid) = self.cids.get(&code) {
+            return *cid;
+        }
+        self.cid_ranges
+            .iter()
+            .rev()
+            .find(|range| range.low <= code && code <= range.high)
+            .and_then(|range| range.cid.checked_add(code - range.low))
+            .unwrap_or(0)
+    }
+
+    /// Returns the Unicode text of a character code.
+    pub fn to_unicode(&self, code: u32) -> Option<String> {
+        if let Some(text) = self.unicode.get(&code) {
+            return Some(text.clone());
+        }
+        let range = self
+            .unicode_ranges
+            .iter()
+            .rev()
+            .find(|range| range.low <= code && code <= range.high)?;
+        // The offset is added to the last UTF-16 unit of the destination.
+        let mut destination = range.destination.clone();
+        let last = destination.last_mut()?;
+        *last = last.wrapping_add((code - range.low) as u16);
+        Some(String::from_utf16_lossy(&destination))
+    }
+}
+
+fn string(object: &Object) -> Result<Vec<u8>, ParseError> {
+    object.string_bytes().ok_or(ParseError::InvalidPDFCMap)
+}
+
+fn code(object: &Object) -> Result<u32, ParseError> {
+    let bytes = string(object)?;
+    if bytes.is_empty() || bytes.len() > MAX_CODE_LENGTH {
+        return Err(ParseError::InvalidPDFCMap);
+    }
+    Ok(to_code(&bytes))
+}
+
+fn to_code(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0, |code, &byte| code << 8 | byte as u32)
+}
+
+fn utf16(bytes: &[u8]) -> String {
+    let units: Vec<u16> = bytes
+        .chunks(2)
+        .map(|pair| u16::from_be_bytes([pair[0], *pair.get(1).unwrap_or(&0)]))
+        .collect();
+    String::from_utf16_lossy(&units)
+}
@@ -0,0 +1,152 @@
+use nom::branch::alt;
+use nom::bytes::complete::{tag, take_while1};
+use nom::multi::many0;
+
+use crate::error::{ParseError, ParseResult};
+use crate::object::{DictionaryObject, Object};
+use crate::utils::{
+    take_while1_whitespace, take_while_separator, take_while_whitespace, DELIMITER_CHARS,
+    WHITE_SPACE_CHARS,
+};
+
+/// A content stream operation, e.g. `/F1 12 Tf`.
+/// Operands come first in the stream, the operator follows them.
+#[derive(Debug, Clone)]
+pub struct Operation<'a> {
+    pub operator: &'a str,
+    pub operands: Vec<Object<'a>>,
+}
+
+/// A parsed content stream, such as the `/Contents` of a page.
+#[derive(Debug, Clone, Default)]
+pub struct Content<'a> {
+    pub operations: Vec<Operation<'a>>,
+}
+
+impl<'a> Content<'a> {
+    pub fn parse(input: &'a [u8]) -> Result<Self, ParseError> {
+        let (input, operations) =
+            many0(Operation::parse)(input).map_err(|_| ParseError::InvalidPDFContentStream)?;
+        let (input, _) = skip_filler(input).map_err(|_| ParseError::InvalidPDFContentStream)?;
+        if !input.is_empty() {
+            return Err(ParseError::InvalidPDFContentStream);
+        }
+
+        Ok(Self { operations })
+    }
+}
+
+impl<'a> IntoIterator for Content<'a> {
+    type Item = Operation<'a>;
+    type IntoIter = std::vec::IntoIter<Operation<'a>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.operations.into_iter()
+    }
+}
+
+impl<'a> Operation<'a> {
+    /// Parses the operands and the operator of one operation.
+    /// An inline image (`BI ... ID ... EI`) is returned as a single `BI` operation
+    /// whose only operand is a dictionary holding the image parameters and data.
+    pub fn parse(input: &'a [u8]) -> ParseResult<'a, Operation<'a>> {
+        let mut operands = Vec::new();
+        let (mut input, _) = skip_filler(input)?;
+
+        while let Ok((remaining, operand)) = Operation::parse_operand(input) {
+            operands.push(operand);
+            (input, _) = skip_filler(remaining)?;
+        }
+
+        let (input, operator) = Operation::parse_operator(input)?;
+        if operator == "BI" {
+            if !operands.is_empty() {
+                return Err(ParseError::InvalidPDFContentStream.into());
+            }
+            let (input, image) = Operation::parse_inline_image(input)?;
+            return Ok((
+                input,
+                Operation {
+                    operator,
+                    operands: vec![image],
+                },
+            ));
+        }
+
+        Ok((input, Operation { operator, operands }))
+    }
+
+    // Content streams never contain indirect objects or references,
+    // so only direct objects are accepted as operands.
+    pub fn parse_operand(input: &'a [u8]) -> ParseResult<'a, Object<'a>> {
+        alt((
+            Object::parse_dictionary,
+            Object::parse_array,
+            Object::parse_name,
+            Object::parse_literal_string,
+            Object::parse_hexadecimal_string,
+            Object::parse_numeric,
+            Object::parse_bool,
+            Object::parse_null,
+        ))(input)
+    }
+
+    pub fn parse_operator(input: &'a [u8]) -> ParseResult<'a, &'a str> {
+        let (input, (operator, _)) = nom::sequence::tuple((
+            take_while1(|c| !WHITE_SPACE_CHARS.contains(&c) && !DELIMITER_CHARS.contains(&c)),
+            take_while_separator,
+        ))(input)?;
+        let operator = std::str::from_utf8(operator).map_err(ParseError::UTF8Error)?;
+
+        Ok((input, operator))
+    }
+
+    /// Parses the part of an inline image following `BI`.
+    pub fn parse_inline_image(input: &'a [u8]) -> ParseResult<'a, Object<'a>> {
+        let mut dictionary = DictionaryObject::new();
+        let (mut input, _) = skip_filler(input)?;
+
+        while !input.starts_with(b"ID") {
+            let (remaining, key) = Object::parse_name(input)?;
+            let (remaining, _) = skip_filler(remaining)?;
+            let (remaining, value) = Operation::parse_operand(remaining)?;
+            let (remaining, _) = skip_filler(remaining)?;
+            if let Object::Name(key) = key {
+                dictionary.insert(key, value);
+            }
+            input = remaining;
+        }
+
+        // "ID" is followed by a single white-space character, then the image data.
+        let (input, _) = tag("ID")(input)?;
+        let input = match input.first() {
+            Some(c) if WHITE_SPACE_CHARS.contains(c) => &input[1..],
+            _ => return Err(ParseError::InvalidPDFContentStream.into()),
+        };
+
+        // The data ends at the first "EI" surrounded by white space.
+        let end = (0..input.len())
+            .find(|&i| {
+                input[i..].starts_with(b"EI")
+                    && (i == 0 || WHITE_SPACE_CHARS.contains(&input[i - 1]))
+                    && input
+                        .get(i + 2)
+                        .is_none_or(|c| WHITE_SPACE_CHARS.contains(c))
+            })
+            .ok_or(ParseError::InvalidPDFContentStream)?;
+        let data = &input[..end.saturating_sub(1)];
+        let (input, _) = take_while_separator(&input[end + 2..])?;
+
+        Ok((input, Object::Dictionary(dictionary, data)))
+    }
+}
+
+// Skips white space and comments between tokens.
+fn skip_filler(input: &[u8]) -> ParseResult<'_, ()> {
+    let (input, _) = take_while_whitespace(input)?;
+    let (input, _) = many0(alt((
+        take_while1_whitespace,
+        nom::combinator::recognize(Object::parse_comment),
+    )))(input)?;
+    Ok((input, ()))
+}
@@ -0,0 +1,256 @@
+use std::collections::HashMap;
+
+use crate::error::ParseError;
+use crate::object::{IndirectReference, Object, PDF};
+
+/// Maximum number of named destinations, or of `/D` entries, followed to reach
+/// an explicit destination.
+const MAX_NAMED_DEPTH: usize = 16;
+
+/// How a destination shows its page. Coordinates left `None` (`null`) keep their
+/// current value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum View {
+    XYZ {
+        left: Option<f32>,
+        top: Option<f32>,
+        zoom: Option<f32>,
+    },
+    Fit,
+    FitH {
+        top: Option<f32>,
+    },
+    FitV {
+        left: Option<f32>,
+    },
+    FitR {
+        left: f32,
+        bottom: f32,
+        right: f32,
+        top: f32,
+    },
+    /// As `Fit`, for the bounding box of the page contents.
+    FitB,
+    FitBH {
+        top: Option<f32>,
+    },
+    FitBV {
+        left: Option<f32>,
+    },
+}
+
+impl View {
+    /// Reads the view of an explicit destination, the elements after the page.
+    pub fn from_array(pdf: &PDF, array: &[Object]) -> Option<Self> {
+        let (name, parameters) = array.split_first()?;
+        let number = |index: usize| {
+            parameters
+                .get(index)
+                .and_then(|parameter| pdf.resolve(parameter).as_f32())
+        };
+        Some(match pdf.resolve(name).as_name()? {
+            "XYZ" => View::XYZ {
+                left: number(0),
+                top: number(1),
+                zoom: number(2).filter(|zoom| *zoom != 0.0),
+            },
+            "Fit" => View::Fit,
+            "FitH" => View::FitH { top: number(0) },
+            "FitV" => View::FitV { left: number(0) },
+            "FitR" => View::FitR {
+                left: number(0)?,
+                bottom: number(1)?,
+                right: number(2)?,
+                top: number(3)?,
+            },
+            "FitB" => View::FitB,
+            "FitBH" => View::FitBH { top: number(0) },
+            "FitBV" => View::FitBV { left: number(0) },
+            _ => return None,
+        })
+    }
+
+    /// The name of the view, e.g. `FitH`.
+    pub fn name(&self) -> &str {
+        match self {
+            View::XYZ { .. } => "XYZ",
+            View::Fit => "Fit",
+            View::FitH { .. } => "FitH",
+            View::FitV { .. } => "FitV",
+            View::FitR { .. } => "FitR",
+            View::FitB => "FitB",
+            View::FitBH { .. } => "FitBH",
+            View::FitBV { .. } => "FitBV",
+        }
+    }
+}
+
+/// The page of an explicit destination.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageTarget {
+    Reference(IndirectReference),
+    /// A page number, as used by destinations in other documents.
+    Index(i32),
+}
+
+/// A destination, as found in outline items, link annotations and `/GoTo`
+/// actions.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Destination {
+    /// `[page /View parameters...]`
+    Explicit { page: PageTarget, view: View },
+    /// A name of the catalog `/Dests` dictionary.
+    Name(String),
+    /// A string of the `/Names` `/Dests` tree.
+    String(Vec<u8>),
+}
+
+impl Destination {
+    /// Reads a destination. Dictionaries with a `/D` entry, the values of named
+    /// destinations, are read as their `/D`.
+    pub fn from_object(pdf: &PDF, object: &Object) -> Option<Self> {
+        let mut object = pdf.resolve(object);
+        // A `/D` may lead back to its own dictionary.
+        let mut depth = 0;
+        while let Object::Dictionary(dictionary, _) = object {
+            if depth == MAX_NAMED_DEPTH {
+                return None;
+            }
+            object = pdf.resolve(dictionary.get("D")?);
+            depth += 1;
+        }
+        match object {
+            Object::Array(array) => {
+                let (page, view) = array.split_first()?;
+                let page = match page {
+                    Object::IndirectReference { id, generation } => {
+                        PageTarget::Reference(IndirectReference {
+                            id: *id,
+                            generation: *generation,
+                        })
+                    }
+                    page => PageTarget::Index(page.as_i32()?),
+                };
+                Some(Destination::Explicit {
+                    page,
+                    view: View::from_array(pdf, view)?,
+                })
+            }
+            Object::Name(name) => Some(Destination::Name(name.0.to_string())),
+            string => string.string_bytes().map(Destination::String),
+        }
+    }
+}
+
+/// A destination resolved to a page of the document.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResolvedDestination {
+    /// The index of the page.
+    pub page: usize,
+    pub view: View,
+}
+
+/// Resolves destinations to pages, with the page tree read once.
+#[derive(Debug, Clone)]
+pub struct DestinationResolver<'p, 'a> {
+    pdf: &'p PDF<'a>,
+    pages: HashMap<IndirectReference, usize>,
+}
+
+impl<'p, 'a> DestinationResolver<'p, 'a> {
+    pub fn new(pdf: &'p PDF<'a>) -> Result<Self, ParseError> {
+        let pages = pdf
+            .pages()?
+            .iter()
+            .enumerate()
+            .map(|(index, page)| (page.reference, index))
+            .collect();
+        Ok(Self { pdf, pages })
+    }
+
+    /// Resolves a destination, following named destinations. Pages given by number
+    /// are accepted if the document has them. `None` if the destination or its
+    /// page does not exist.
+    pub fn resolve(&self, destination: &Destination) -> Option<ResolvedDestination> {
+        self.resolve_named(destination, 0)
+    }
+
+    /// Reads and resolves a destination object.
+    pub fn resolve_object(&self, object: &Object) -> Option<ResolvedDestination> {
+        self.resolve(&Destination::from_object(self.pdf, object)?)
+    }
+
+    /// Looks up a named destination without resolving it.
+    pub fn named(&self, destination: &Destination) -> Option<Destination> {
+        let pdf = self.pdf;
+        let target = match destination {
+            Destination::Explicit { .. } => return Some(destination.clone()),
+            Destination::Name(name) => {
+                let dests = pdf.get(pdf.catalog().ok()?, "Dests")?;
+                pdf.get(dests, name)?
+            }
+            Destination::String(key) => pdf.name_tree("Dests").ok()??.get(key)?,
+        };
+        Destination::from_object(pdf, target)
+    }
+
+    fn resolve_named(
+        &self,
+        destination: &Destination,
+        depth: usize,
+    ) -> Option<ResolvedDestination> {
+        if depth > MAX_NAMED_DEPTH {
+            return None;
+        }
+        match destination {
+            Destination::Explicit { page, view } => {
+                let page = match page {
+                    PageTarget::Reference(reference) => *self.pages.get(reference)?,
+                    PageTarget::Index(index) => usize::try_from(*index)
+                        .ok()
+                        .filter(|index| *index < self.pages.len())?,
+                };
+                Some(ResolvedDestination { page, view: *view })
+            }
+            named => self.resolve_named(&self.named(named)?, depth + 1),
+        }
+    }
+}
+
+impl<'a> PDF<'a> {
+    /// Reads and resolves a destination object, see `DestinationResolver`.
+    pub fn resolve_destination(
+        &self,
+        object: &Object,
+    ) -> Result<Option<ResolvedDestination>, ParseError> {
+        Ok(DestinationResolver::new(self)?.resolve_object(object))
+    }
+
+    /// Lists the named destinations of the catalog `/Dests` dictionary, sorted by
+    /// name, then those of the `/Names` `/Dests` tree, in tree order.
+    pub fn named_destinations(&self) -> Result<Vec<(Destination, Destination)>, ParseError> {
+        let mut destinations = Vec::new();
+        if let Some(dests) = self
+            .get(self.catalog()?, "Dests")
+            .and_then(Object::as_dictionary)
+        {
+            let mut names: Vec<_> = dests.iter().collect();
+            names.sort_by_key(|(name, _)| name.0);
+            destinations.extend(names.into_iter().filter_map(|(name, target)| {
+                Some((
+                    Destination::Name(name.0.to_string()),
+                    Destination::from_object(self, target)?,
+                ))
+            }));
+        }
+        if let Some(tree) = self.name_tree("Dests")? {
+            destinations.extend(tree.iter().filter_map(|(key, target)| {
+                Some((
+                    Destination::String(key),
+                    Destination::from_object(self, target)?,
+                ))
+            }));
+        }
+        Ok(destinations)
+    }
+}
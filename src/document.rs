@@ -0,0 +1,210 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::error::ParseError;
+use crate::object::{DictionaryObject, IndirectReference, Object, PDF};
+
+/// Maximum depth of `/Parent` chains followed when looking up inherited page attributes.
+const MAX_INHERITANCE_DEPTH: usize = 64;
+
+/// A page of the document, i.e. a leaf of the page tree.
+#[derive(Debug, Clone, Copy)]
+pub struct Page<'p, 'a> {
+    pub pdf: &'p PDF<'a>,
+    pub reference: IndirectReference,
+    pub dictionary: &'p DictionaryObject<'a>,
+}
+
+/// Returns the object of `object` if it defines `reference`.
+fn definition<'o, 'a>(
+    object: &'o Object<'a>,
+    reference: IndirectReference,
+) -> Option<&'o Object<'a>> {
+    match object {
+        Object::IndirectObject {
+            id,
+            generation,
+            dictionary,
+        } if *id == reference.id && *generation == reference.generation => Some(dictionary),
+        _ => None,
+    }
+}
+
+impl<'a> PDF<'a> {
+    /// Returns the object defined as `id generation obj`.
+    /// If an object is defined more than once, the last definition wins.
+    pub fn get_object(&self, reference: IndirectReference) -> Option<&Object<'a>> {
+        let (length, index) = self.index.0.get_or_init(|| {
+            let mut index = HashMap::new();
+            for (position, object) in self.body.iter().enumerate() {
+                if let Object::IndirectObject { id, generation, .. } = object {
+                    index.insert(
+                        IndirectReference {
+                            id: *id,
+                            generation: *generation,
+                        },
+                        position,
+                    );
+                }
+            }
+            (self.body.len(), index)
+        });
+        // `body` is public: if objects were added or removed since the index was
+        // built, it is searched instead.
+        if *length == self.body.len() {
+            match index.get(&reference) {
+                Some(&position) => {
+                    if let Some(object) = definition(&self.body[position], reference) {
+                        return Some(object);
+                    }
+                }
+                None => return None,
+            }
+        }
+        self.body
+            .iter()
+            .rev()
+            .find_map(|object| definition(object, reference))
+    }
+
+    /// Follows `object` if it is an indirect reference.
+    /// References to missing objects resolve to `Object::Null`, as the spec requires.
+    pub fn resolve<'o>(&'o self, object: &'o Object<'a>) -> &'o Object<'a> {
+        const NULL: &Object = &Object::Null;
+        let mut object = object;
+        let mut visited = HashSet::new();
+        while let Some(reference) = object.as_reference() {
+            if !visited.insert(reference) {
+                return NULL;
+            }
+            object = self.get_object(reference).unwrap_or(NULL);
+        }
+        object
+    }
+
+    /// Looks up `key` in a dictionary and resolves the value.
+    pub fn get<'o>(&'o self, object: &'o Object<'a>, key: &str) -> Option<&'o Object<'a>> {
+        let value = self.resolve(self.resolve(object).get(key)?);
+        match value {
+            Object::Null => None,
+            _ => Some(value),
+        }
+    }
+
+    /// Returns the dictionary and raw data of a stream, without the end-of-line
+    /// marker before `endstream` when `/Length` leaves it out.
+    pub fn stream_data<'o>(
+        &'o self,
+        object: &'o Object<'a>,
+    ) -> Option<(&'o DictionaryObject<'a>, &'a [u8])> {
+        let (dictionary, data) = self.resolve(object).as_stream()?;
+        let data = match dictionary
+            .get("Length")
+            .and_then(|length| self.resolve(length).as_i32())
+        {
+            Some(length) if (0..data.len() as i32).contains(&length) => &data[..length as usize],
+            _ => data,
+        };
+        Some((dictionary, data))
+    }
+
+    /// Returns the document catalog, i.e. the trailer's `/Root`.
+    pub fn catalog(&self) -> Result<&Object<'a>, ParseError> {
+        self.get(&self.trailer.dictionary, "Root")
+            .filter(|catalog| catalog.as_dictionary().is_some())
+            .ok_or(ParseError::InvalidPDFTrailer)
+    }
+
+    /// Returns the pages of the document in order.
+    pub fn pages(&self) -> Result<Vec<Page<'_, 'a>>, ParseError> {
+        let catalog = self.catalog()?;
+        let root = catalog.get("Pages").ok_or(ParseError::InvalidPDFPageTree)?;
+
+        let mut pages = Vec::new();
+        let mut visited = HashSet::new();
+        self.collect_pages(root, &mut pages, &mut visited)?;
+        Ok(pages)
+    }
+
+    fn collect_pages<'p>(
+        &'p self,
+        node: &'p Object<'a>,
+        pages: &mut Vec<Page<'p, 'a>>,
+        visited: &mut HashSet<IndirectReference>,
+    ) -> Result<(), ParseError> {
+        let reference = node.as_reference().ok_or(ParseError::InvalidPDFPageTree)?;
+        if !visited.insert(reference) {
+            return Err(ParseError::InvalidPDFPageTree);
+        }
+        let dictionary = self
+            .resolve(node)
+            .as_dictionary()
+            .ok_or(ParseError::InvalidPDFPageTree)?;
+
+        match dictionary.get("Type").and_then(Object::as_name) {
+            Some("Pages") => {
+                let kids = dictionary
+                    .get("Kids")
+                    .map(|kids| self.resolve(kids))
+                    .and_then(Object::as_array)
+                    .ok_or(ParseError::InvalidPDFPageTree)?;
+                for kid in kids {
+                    self.collect_pages(kid, pages, visited)?;
+                }
+            }
+            Some("Page") => pages.push(Page {
+                pdf: self,
+                reference,
+                dictionary,
+            }),
+            _ => return Err(ParseError::InvalidPDFPageTree),
+        }
+        Ok(())
+    }
+}
+
+impl<'p, 'a> Page<'p, 'a> {
+    /// Looks up `key` in the page, then in its ancestors for inheritable attributes
+    /// such as `/Resources`, `/MediaBox` and `/Rotate`.
+    pub fn get_inherited(&self, key: &str) -> Option<&'p Object<'a>> {
+        let mut dictionary = self.dictionary;
+        for _ in 0..MAX_INHERITANCE_DEPTH {
+            if let Some(value) = dictionary.get(key) {
+                return Some(self.pdf.resolve(value));
+            }
+            dictionary = self
+                .pdf
+                .resolve(dictionary.get("Parent")?)
+                .as_dictionary()?;
+        }
+        None
+    }
+
+    pub fn resources(&self) -> Option<&'p DictionaryObject<'a>> {
+        self.get_inherited("Resources")?.as_dictionary()
+    }
+
+    /// Returns the decoded content of the page.
+    /// If `/Contents` is an array, the streams are concatenated as if they were one.
+    pub fn contents(&self) -> Result<Vec<u8>, ParseError> {
+        let contents = match self.dictionary.get("Contents") {
+            Some(contents) => self.pdf.resolve(contents),
+            None => return Ok(Vec::new()),
+        };
+        let streams = match contents {
+            Object::Array(array) => array.iter().map(|s| self.pdf.resolve(s)).collect(),
+            stream => vec![stream],
+        };
+
+        let mut result = Vec::new();
+        for stream in streams {
+            let (dictionary, data) = stream
+                .as_stream()
+                .ok_or(ParseError::InvalidPDFContentStream)?;
+            result.extend(crate::filter::decode(dictionary, data)?);
+            // The division between streams may occur only at token boundaries,
+            // so keep the last token of one stream apart from the first of the next.
+            result.push(b'\n');
+        }
+        Ok(result)
+    }
+}
@@ -0,0 +1,162 @@
+use md5::{Digest, Md5};
+
+use crate::annotation::AnnotationType;
+use crate::error::ParseError;
+use crate::object::{IndirectReference, Object, PDF};
+use crate::utils::decode_name;
+
+/// The `/EF` entries holding the file stream, in order of preference.
+const FILE_KEYS: [&str; 5] = ["F", "UF", "DOS", "Mac", "Unix"];
+
+/// Where an embedded file is attached.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttachmentSource {
+    /// The `/Names` `/EmbeddedFiles` tree, with the key of the file.
+    NameTree(String),
+    /// A FileAttachment annotation, by page index and index in the page `/Annots`.
+    Annotation { page: usize, index: usize },
+}
+
+/// A file embedded in the document through a file specification with `/EF`.
+#[derive(Debug, Clone)]
+pub struct EmbeddedFile<'p, 'a> {
+    pub pdf: &'p PDF<'a>,
+    pub source: AttachmentSource,
+    /// The file specification, if it is an indirect object.
+    pub file_spec: Option<IndirectReference>,
+    /// The embedded file stream, if it is an indirect object.
+    pub reference: Option<IndirectReference>,
+    /// The `/F` file name.
+    pub file: Option<String>,
+    /// The `/UF` file name, in Unicode.
+    pub unicode_file: Option<String>,
+    /// The `/Desc` description.
+    pub description: Option<String>,
+    /// The `/Subtype` of the stream, a MIME type such as `application/pdf`.
+    pub mime_type: Option<String>,
+    /// The `/Size` of `/Params`, the size of the decoded file in bytes.
+    pub size: Option<i32>,
+    /// The `/CheckSum` of `/Params`, the MD5 digest of the decoded file.
+    pub checksum: Option<Vec<u8>>,
+    pub creation_date: Option<String>,
+    pub modification_date: Option<String>,
+    stream: &'p Object<'a>,
+}
+
+impl<'p, 'a> EmbeddedFile<'p, 'a> {
+    /// Reads a file specification, `None` if it has no embedded file stream.
+    pub fn from_file_spec(
+        pdf: &'p PDF<'a>,
+        source: AttachmentSource,
+        file_spec: &'p Object<'a>,
+    ) -> Option<Self> {
+        let embedded = pdf.get(file_spec, "EF")?;
+        let stream = FILE_KEYS
+            .iter()
+            .filter_map(|key| embedded.get(key))
+            .find(|stream| pdf.resolve(stream).as_stream().is_some())?;
+        let get = |key: &str| pdf.get(stream, key);
+        let params = get("Params");
+        let param = |key: &str| params.and_then(|params| pdf.get(params, key));
+        let text = |object: Option<&Object>| object.and_then(Object::text_string);
+
+        Some(EmbeddedFile {
+            pdf,
+            source,
+            file_spec: file_spec.as_reference(),
+            reference: stream.as_reference(),
+            file: text(pdf.get(file_spec, "F")),
+            unicode_file: text(pdf.get(file_spec, "UF")),
+            description: text(pdf.get(file_spec, "Desc")),
+            mime_type: get("Subtype")
+                .and_then(Object::as_name)
+                .map(|name| decode_name(name).into_owned()),
+            size: param("Size").and_then(Object::as_i32),
+            checksum: param("CheckSum").and_then(Object::string_bytes),
+            creation_date: text(param("CreationDate")),
+            modification_date: text(param("ModDate")),
+            stream,
+        })
+    }
+
+    /// The name of the file, `/UF` if given, otherwise `/F`.
+    pub fn name(&self) -> Option<&str> {
+        self.unicode_file.as_deref().or(self.file.as_deref())
+    }
+
+    /// Returns the decoded content of the file.
+    pub fn content(&self) -> Result<Vec<u8>, ParseError> {
+        let (dictionary, data) = self
+            .pdf
+            .stream_data(self.stream)
+            .ok_or(ParseError::InvalidPDFStreamData)?;
+        crate::filter::decode(dictionary, data)
+    }
+
+    /// Compares the MD5 digest of the content with the declared `/CheckSum`,
+    /// `None` if there is none.
+    pub fn checksum_matches(&self) -> Result<Option<bool>, ParseError> {
+        let checksum = match &self.checksum {
+            Some(checksum) => checksum,
+            None => return Ok(None),
+        };
+        let digest = Md5::digest(self.content()?);
+        Ok(Some(digest.as_slice() == checksum.as_slice()))
+    }
+
+    /// Compares the size of the content with the declared `/Size`, `None` if
+    /// there is none.
+    pub fn size_matches(&self) -> Result<Option<bool>, ParseError> {
+        let size = match self.size {
+            Some(size) => size,
+            None => return Ok(None),
+        };
+        Ok(Some(self.content()?.len() as i64 == size as i64))
+    }
+}
+
+impl<'a> PDF<'a> {
+    /// Lists the embedded files of the `/Names` `/EmbeddedFiles` tree, then those
+    /// of FileAttachment annotations page by page. A file specification reached
+    /// both ways is listed once.
+    pub fn embedded_files(&self) -> Result<Vec<EmbeddedFile<'_, 'a>>, ParseError> {
+        let mut files = Vec::new();
+        if let Some(tree) = self.name_tree("EmbeddedFiles")? {
+            for (key, file_spec) in tree.iter() {
+                let name = crate::encoding::decode_text_string(&key);
+                let source = AttachmentSource::NameTree(name);
+                files.extend(EmbeddedFile::from_file_spec(self, source, file_spec));
+            }
+        }
+
+        for (index, page) in self.pages()?.iter().enumerate() {
+            let annotations = page
+                .dictionary
+                .get("Annots")
+                .and_then(|annotations| self.resolve(annotations).as_array())
+                .unwrap_or_default();
+            for (position, annotation) in annotations.iter().enumerate() {
+                let subtype = self.get(annotation, "Subtype").and_then(Object::as_name);
+                if subtype.map(AnnotationType::from_name) != Some(AnnotationType::FileAttachment) {
+                    continue;
+                }
+                let file_spec = match self.resolve(annotation).get("FS") {
+                    Some(file_spec) => file_spec,
+                    None => continue,
+                };
+                let listed = file_spec.as_reference().is_some_and(|reference| {
+                    files.iter().any(|file| file.file_spec == Some(reference))
+                });
+                if listed {
+                    continue;
+                }
+                let source = AttachmentSource::Annotation {
+                    page: index,
+                    index: position,
+                };
+                files.extend(EmbeddedFile::from_file_spec(self, source, file_spec));
+            }
+        }
+        Ok(files)
+    }
+}
@@ -11,8 +11,9 @@ pub type ParseResult<'a, T> = IResult<&'a [u8], T, ParseError>;
 // Currently, most of the errors are just "ParseError::NomError(ErrorKind)".
 #[derive(Debug, Clone, PartialEq, thiserror::Error)]
 pub enum ParseError {
-    // #[error("IO error: {0:?}")]
-    // IOError(#[from] std::io::Error),
+    /// The kind of an I/O error, as `std::io::Error` is not `Clone`.
+    #[error("IO error: {0:?}")]
+    IOError(std::io::ErrorKind),
     #[error("UTF-8 error")]
     UTF8Error(#[from] Utf8Error),
     #[error("ParseInt error: {0:?}")]
@@ -45,10 +46,58 @@ pub enum ParseError {
     InvalidPDFObjectStreamData,
     #[error("Invalid PDF object stream data length")]
     InvalidPDFObjectStreamDataLength,
+    #[error("Invalid PDF object reference: {0} {1} R")]
+    InvalidPDFObjectReference(u32, u32),
+    #[error("Invalid PDF page tree")]
+    InvalidPDFPageTree,
+    #[error("Invalid PDF content stream")]
+    InvalidPDFContentStream,
+    #[error("Invalid PDF stream data")]
+    InvalidPDFStreamData,
+    #[error("Invalid PDF CMap")]
+    InvalidPDFCMap,
+    #[error("Invalid PDF image")]
+    InvalidPDFImage,
+    #[error("Invalid PDF form")]
+    InvalidPDFForm,
+    #[error("Invalid PDF form field: {0}")]
+    InvalidPDFFormField(String),
+    #[error("Invalid PDF outline: {0}")]
+    InvalidPDFOutline(String),
+    #[error("Invalid PDF name tree: {0}")]
+    InvalidPDFNameTree(String),
+    #[error("Invalid PDF number tree: {0}")]
+    InvalidPDFNumberTree(String),
+    /// A limit of `ParseOptions` was reached.
+    #[error("PDF limit exceeded: {0}")]
+    PDFLimitExceeded(String),
+    #[error("Unsupported PDF stream filter: {0}")]
+    UnsupportedPDFFilter(String),
+    #[error("Unsupported PDF colour space: {0}")]
+    UnsupportedPDFColorSpace(String),
     #[error("Nom Parse error: {0:?}")]
     NomError(ErrorKind),
 }
 
+/// A deviation from the specification accepted in lenient mode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseWarning {
+    /// The offset in the file where the deviation was found.
+    pub offset: usize,
+    pub kind: ParseWarningKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseWarningKind {
+    /// A cross-reference entry offset not written with 10 digits, or a
+    /// generation not written with 5.
+    XrefEntryWidth,
+    /// An indirect object of the body without `endobj`.
+    MissingEndobj { id: u32, generation: u32 },
+    /// A trailer without `%%EOF`.
+    MissingEof,
+}
+
 impl From<ParseError> for nom::Err<ParseError> {
     fn from(e: ParseError) -> Self {
         nom::Err::Error(e)
@@ -61,6 +110,12 @@ impl From<ParseError> for nom::Err<ParseError> {
 //     }
 // }
 
+impl From<std::io::Error> for ParseError {
+    fn from(e: std::io::Error) -> Self {
+        ParseError::IOError(e.kind())
+    }
+}
+
 impl From<ErrorKind> for ParseError {
     fn from(e: ErrorKind) -> Self {
         ParseError::NomError(e)
@@ -0,0 +1,618 @@
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+
+use crate::annotation::{HIDDEN, NO_VIEW};
+use crate::content::Content;
+use crate::encoding::WIN_ANSI_ENCODING;
+use crate::error::ParseError;
+use crate::form::{Field, FieldType, Form, EDIT, MULTILINE, MULTI_SELECT, PUSHBUTTON, RADIO};
+use crate::interpreter::{Matrix, Rect};
+use crate::object::{DictionaryObject, IndirectReference, NameObject, Object, PDF};
+use crate::writer::{encode_text_string, hex_string, reference_object, write_object, Writer};
+
+/// Font size used when `/DA` asks for automatic sizing with `0 Tf`.
+const MAX_AUTO_FONT_SIZE: f32 = 12.0;
+/// Padding between the widget border and its text.
+const TEXT_PADDING: f32 = 2.0;
+
+/// The appearance stream drawn for a widget, with the rectangle it maps to
+/// `/Rect`.
+#[derive(Debug, Clone, Copy)]
+struct Appearance {
+    reference: IndirectReference,
+    bbox: Rect,
+    matrix: Matrix,
+}
+
+/// Sets field values by fully qualified name and saves the document, optionally
+/// flattening the form into page content first.
+#[derive(Debug, Clone)]
+pub struct FormFiller<'p, 'a> {
+    form: Form,
+    writer: Writer<'p, 'a>,
+    acro_form: &'p Object<'a>,
+    /// A Helvetica font added for appearances whose `/DA` font is not in `/DR`.
+    fallback_font: Option<IndirectReference>,
+    /// Appearance streams generated for widgets.
+    appearances: HashMap<IndirectReference, Appearance>,
+    /// Appearance states set on widgets.
+    states: HashMap<IndirectReference, String>,
+}
+
+impl<'a> PDF<'a> {
+    /// Starts filling the interactive form of the document.
+    pub fn form_filler(&self) -> Result<FormFiller<'_, 'a>, ParseError> {
+        let form = self.form()?.ok_or(ParseError::InvalidPDFForm)?;
+        let acro_form = self
+            .get(self.catalog()?, "AcroForm")
+            .ok_or(ParseError::InvalidPDFForm)?;
+        Ok(FormFiller {
+            form,
+            writer: Writer::new(self),
+            acro_form,
+            fallback_font: None,
+            appearances: HashMap::new(),
+            states: HashMap::new(),
+        })
+    }
+}
+
+impl<'p, 'a> FormFiller<'p, 'a> {
+    /// The form as it was read, before any change.
+    pub fn form(&self) -> &Form {
+        &self.form
+    }
+
+    /// Sets the value of a text field and regenerates the appearance of its widgets.
+    pub fn set_text(&mut self, name: &str, value: &str) -> Result<(), ParseError> {
+        let field = self.terminal_field(name, FieldType::Text)?;
+        let reference = field_reference(&field)?;
+        let hex = hex_string(&encode_text_string(value));
+        self.writer
+            .set(reference, "V", &Object::HexadecimalString(hex.as_bytes()));
+
+        for widget in &field.widgets {
+            let (widget, rect) = match (widget.reference, widget.rect) {
+                (Some(widget), Some(rect)) => (widget, rect),
+                _ => continue,
+            };
+            let appearance = self.text_appearance(&field, value, &rect)?;
+            self.set_normal_appearance(widget, appearance);
+        }
+        Ok(())
+    }
+
+    /// Checks or unchecks a check box. The "on" state is the one its appearance
+    /// dictionary names besides `Off`, `Yes` if there is none.
+    pub fn set_checkbox(&mut self, name: &str, checked: bool) -> Result<(), ParseError> {
+        let field = self.terminal_field(name, FieldType::Button)?;
+        if field.has_flag(RADIO) || field.has_flag(PUSHBUTTON) {
+            return Err(ParseError::InvalidPDFFormField(name.to_string()));
+        }
+        let reference = field_reference(&field)?;
+
+        let mut value = "Off".to_string();
+        for widget in field.widgets.iter().filter_map(|widget| widget.reference) {
+            let state = match checked {
+                true => self
+                    .on_states(widget)
+                    .into_iter()
+                    .next()
+                    .unwrap_or_else(|| "Yes".to_string()),
+                false => "Off".to_string(),
+            };
+            value = state.clone();
+            self.set_state(widget, state);
+        }
+        self.writer
+            .set(reference, "V", &Object::Name(NameObject(&value)));
+        Ok(())
+    }
+
+    /// Selects the radio button whose "on" state is `state`, or none with `Off`.
+    pub fn set_radio(&mut self, name: &str, state: &str) -> Result<(), ParseError> {
+        let field = self.terminal_field(name, FieldType::Button)?;
+        if !field.has_flag(RADIO) {
+            return Err(ParseError::InvalidPDFFormField(name.to_string()));
+        }
+        let reference = field_reference(&field)?;
+
+        let widgets: Vec<IndirectReference> = field
+            .widgets
+            .iter()
+            .filter_map(|widget| widget.reference)
+            .collect();
+        let on_states: Vec<Vec<String>> = widgets
+            .iter()
+            .map(|widget| self.on_states(*widget))
+            .collect();
+        if state != "Off" && !on_states.iter().flatten().any(|on| on == state) {
+            return Err(ParseError::InvalidPDFFormField(format!(
+                "{}: {}",
+                name, state
+            )));
+        }
+        for (widget, on_states) in widgets.into_iter().zip(on_states) {
+            let widget_state = match on_states.iter().any(|on| on == state) {
+                true => state.to_string(),
+                false => "Off".to_string(),
+            };
+            self.set_state(widget, widget_state);
+        }
+        self.writer
+            .set(reference, "V", &Object::Name(NameObject(state)));
+        Ok(())
+    }
+
+    /// Selects options of a list or combo box by export value. Values that are not
+    /// options are accepted only by editable combo boxes.
+    pub fn set_choice(&mut self, name: &str, values: &[&str]) -> Result<(), ParseError> {
+        let field = self.terminal_field(name, FieldType::Choice)?;
+        if values.len() > 1 && !field.has_flag(MULTI_SELECT) {
+            return Err(ParseError::InvalidPDFFormField(name.to_string()));
+        }
+        if !field.options.is_empty() && !field.has_flag(EDIT) {
+            for value in values {
+                if !field.options.iter().any(|option| option.export == *value) {
+                    return Err(ParseError::InvalidPDFFormField(format!(
+                        "{}: {}",
+                        name, value
+                    )));
+                }
+            }
+        }
+        let reference = field_reference(&field)?;
+
+        let hex: Vec<String> = values
+            .iter()
+            .map(|value| hex_string(&encode_text_string(value)))
+            .collect();
+        let mut strings: Vec<Object> = hex
+            .iter()
+            .map(|hex| Object::HexadecimalString(hex.as_bytes()))
+            .collect();
+        let value = match strings.len() {
+            0 => Object::Null,
+            1 => strings.remove(0),
+            _ => Object::Array(strings),
+        };
+        self.writer.set(reference, "V", &value);
+        // The selected indices would no longer match the value.
+        self.writer.remove(reference, "I");
+        Ok(())
+    }
+
+    /// Draws the appearance of every visible widget into its page and removes the
+    /// widgets and the form from the document.
+    pub fn flatten(&mut self) -> Result<(), ParseError> {
+        let pdf = self.writer.pdf();
+        let widgets: Vec<(IndirectReference, Option<usize>, Option<Rect>)> = self
+            .form
+            .fields
+            .iter()
+            .flat_map(|field| &field.widgets)
+            .filter_map(|widget| Some((widget.reference?, widget.page, widget.rect)))
+            .collect();
+        let removed: HashSet<IndirectReference> =
+            widgets.iter().map(|(reference, _, _)| *reference).collect();
+
+        for (index, page) in pdf.pages()?.iter().enumerate() {
+            let mut draws = Vec::new();
+            for (widget, _, rect) in widgets.iter().filter(|(_, page, _)| *page == Some(index)) {
+                let flags = pdf
+                    .get(
+                        &Object::IndirectReference {
+                            id: widget.id,
+                            generation: widget.generation,
+                        },
+                        "F",
+                    )
+                    .and_then(Object::as_i32)
+                    .unwrap_or(0) as u32;
+                if flags & (HIDDEN | NO_VIEW) != 0 {
+                    continue;
+                }
+                if let (Some(appearance), Some(rect)) = (self.appearance(*widget), rect) {
+                    draws.push((appearance, *rect));
+                }
+            }
+            self.flatten_page(page, &draws, &removed);
+        }
+
+        let catalog = pdf
+            .trailer
+            .dictionary
+            .get("Root")
+            .and_then(Object::as_reference)
+            .ok_or(ParseError::InvalidPDFTrailer)?;
+        self.writer.remove(catalog, "AcroForm");
+        Ok(())
+    }
+
+    /// Appends the changes to `original`, the bytes the document was parsed from.
+    pub fn save_incremental(&self, original: &[u8]) -> Vec<u8> {
+        self.writer.incremental(original)
+    }
+
+    /// Writes the whole document with the changes applied.
+    pub fn save(&self) -> Vec<u8> {
+        self.writer.rewrite()
+    }
+
+    fn terminal_field(&self, name: &str, field_type: FieldType) -> Result<Field, ParseError> {
+        self.form
+            .terminal_fields()
+            .find(|field| field.name == name && field.field_type == Some(field_type))
+            .cloned()
+            .ok_or_else(|| ParseError::InvalidPDFFormField(name.to_string()))
+    }
+
+    /// The appearance states of a widget's `/AP /N` dictionary other than `Off`.
+    fn on_states(&self, widget: IndirectReference) -> Vec<String> {
+        let pdf = self.writer.pdf();
+        let widget = Object::IndirectReference {
+            id: widget.id,
+            generation: widget.generation,
+        };
+        let normal = pdf
+            .get(&widget, "AP")
+            .and_then(|appearances| pdf.get(appearances, "N"));
+        let mut states: Vec<String> = match normal {
+            // A stream is a single appearance, not a dictionary of states.
+            Some(Object::Dictionary(states, &[])) => states
+                .keys()
+                .map(|state| state.0)
+                .filter(|state| *state != "Off")
+                .map(str::to_string)
+                .collect(),
+            _ => Vec::new(),
+        };
+        states.sort();
+        states
+    }
+
+    fn set_state(&mut self, widget: IndirectReference, state: String) {
+        self.writer
+            .set(widget, "AS", &Object::Name(NameObject(&state)));
+        self.states.insert(widget, state);
+    }
+
+    fn set_normal_appearance(&mut self, widget: IndirectReference, appearance: Appearance) {
+        let mut normal = DictionaryObject::new();
+        normal.insert(
+            NameObject("N"),
+            Object::IndirectReference {
+                id: appearance.reference.id,
+                generation: appearance.reference.generation,
+            },
+        );
+        self.writer
+            .set(widget, "AP", &Object::Dictionary(normal, &[]));
+        self.appearances.insert(widget, appearance);
+    }
+
+    /// Generates a form XObject showing `value` with the field's `/DA`.
+    fn text_appearance(
+        &mut self,
+        field: &Field,
+        value: &str,
+        rect: &Rect,
+    ) -> Result<Appearance, ParseError> {
+        let (width, height) = (rect.width(), rect.height());
+        let default_appearance = field.default_appearance.clone().unwrap_or_default();
+        let style = TextStyle::parse(&default_appearance);
+        let multiline = field.has_flag(MULTILINE);
+        let size = match style.size {
+            size if size > 0.0 => size,
+            _ if multiline => MAX_AUTO_FONT_SIZE,
+            _ => ((height - 2.0 * TEXT_PADDING) / 1.2).clamp(1.0, MAX_AUTO_FONT_SIZE),
+        };
+        let font_name = style.font.clone().unwrap_or_else(|| "Helv".to_string());
+
+        let mut content = Vec::new();
+        write!(
+            content,
+            "/Tx BMC\nq\n{p} {p} {} {} re W n\nBT\n/{} {} Tf\n{}",
+            width - 2.0 * TEXT_PADDING,
+            height - 2.0 * TEXT_PADDING,
+            font_name,
+            size,
+            style.color,
+            p = TEXT_PADDING,
+        )
+        .unwrap();
+        if multiline {
+            writeln!(
+                content,
+                "{} TL\n{} {} Td",
+                size * 1.15,
+                TEXT_PADDING,
+                height - TEXT_PADDING - size
+            )
+            .unwrap();
+            for (i, line) in value.lines().enumerate() {
+                if i > 0 {
+                    content.extend_from_slice(b"T*\n");
+                }
+                writeln!(content, "<{}> Tj", hex_string(&win_ansi(line))).unwrap();
+            }
+        } else {
+            let baseline = (height - size) / 2.0 + 0.22 * size;
+            writeln!(
+                content,
+                "{} {} Td\n<{}> Tj",
+                TEXT_PADDING,
+                baseline,
+                hex_string(&win_ansi(value))
+            )
+            .unwrap();
+        }
+        content.extend_from_slice(b"ET\nQ\nEMC\n");
+
+        let font = self.font_resource(&font_name);
+        let mut fonts = DictionaryObject::new();
+        fonts.insert(NameObject(&font_name), font);
+        let mut resources = DictionaryObject::new();
+        resources.insert(NameObject("Font"), Object::Dictionary(fonts, &[]));
+
+        let bbox = Rect::new(0.0, 0.0, width, height);
+        let mut dictionary = DictionaryObject::new();
+        dictionary.insert(NameObject("Type"), Object::Name(NameObject("XObject")));
+        dictionary.insert(NameObject("Subtype"), Object::Name(NameObject("Form")));
+        dictionary.insert(
+            NameObject("BBox"),
+            Object::Array(vec![
+                Object::Integer(0),
+                Object::Integer(0),
+                Object::Real(width),
+                Object::Real(height),
+            ]),
+        );
+        dictionary.insert(NameObject("Resources"), Object::Dictionary(resources, &[]));
+        let reference = self.writer.add(&Object::Dictionary(dictionary, &content));
+        Ok(Appearance {
+            reference,
+            bbox,
+            matrix: Matrix::IDENTITY,
+        })
+    }
+
+    /// The font `name` of the form's `/DR`, or a Helvetica font added once.
+    fn font_resource(&mut self, name: &str) -> Object<'a> {
+        let pdf = self.writer.pdf();
+        let font = pdf
+            .get(self.acro_form, "DR")
+            .and_then(|resources| pdf.get(resources, "Font"))
+            .and_then(|fonts| fonts.get(name));
+        if let Some(font) = font {
+            return font.clone();
+        }
+        let reference = match self.fallback_font {
+            Some(reference) => reference,
+            None => {
+                let mut font = DictionaryObject::new();
+                font.insert(NameObject("Type"), Object::Name(NameObject("Font")));
+                font.insert(NameObject("Subtype"), Object::Name(NameObject("Type1")));
+                font.insert(
+                    NameObject("BaseFont"),
+                    Object::Name(NameObject("Helvetica")),
+                );
+                font.insert(
+                    NameObject("Encoding"),
+                    Object::Name(NameObject("WinAnsiEncoding")),
+                );
+                let reference = self.writer.add(&Object::Dictionary(font, &[]));
+                self.fallback_font = Some(reference);
+                reference
+            }
+        };
+        Object::IndirectReference {
+            id: reference.id,
+            generation: reference.generation,
+        }
+    }
+
+    /// The appearance to draw for a widget: the generated one, or the `/AP /N`
+    /// stream of the document, picked by appearance state if there are several.
+    fn appearance(&self, widget: IndirectReference) -> Option<Appearance> {
+        if let Some(appearance) = self.appearances.get(&widget) {
+            return Some(*appearance);
+        }
+        let pdf = self.writer.pdf();
+        let widget_object = Object::IndirectReference {
+            id: widget.id,
+            generation: widget.generation,
+        };
+        let normal = pdf.get(&widget_object, "AP")?.get("N")?;
+        let stream = match pdf.resolve(normal) {
+            Object::Dictionary(states, &[]) => {
+                let state = match self.states.get(&widget) {
+                    Some(state) => state.as_str(),
+                    None => pdf.get(&widget_object, "AS")?.as_name()?,
+                };
+                states.get(state)?
+            }
+            _ => normal,
+        };
+        let reference = stream.as_reference()?;
+        let bbox = Rect::from_object(pdf, pdf.get(stream, "BBox")?)?;
+        let matrix = pdf
+            .get(stream, "Matrix")
+            .and_then(|matrix| Matrix::from_object(pdf, matrix))
+            .unwrap_or(Matrix::IDENTITY);
+        Some(Appearance {
+            reference,
+            bbox,
+            matrix,
+        })
+    }
+
+    /// Draws the appearances at the end of the page content, after the original
+    /// content wrapped in `q`/`Q`, and drops the flattened widgets from `/Annots`.
+    fn flatten_page(
+        &mut self,
+        page: &crate::document::Page<'p, 'a>,
+        draws: &[(Appearance, Rect)],
+        removed: &HashSet<IndirectReference>,
+    ) {
+        let pdf = self.writer.pdf();
+        let annotations = page
+            .dictionary
+            .get("Annots")
+            .and_then(|annotations| pdf.resolve(annotations).as_array())
+            .unwrap_or_default();
+        let kept: Vec<Object> = annotations
+            .iter()
+            .filter(|annotation| {
+                annotation
+                    .as_reference()
+                    .is_none_or(|reference| !removed.contains(&reference))
+            })
+            .cloned()
+            .collect();
+        if kept.len() != annotations.len() {
+            self.writer
+                .set(page.reference, "Annots", &Object::Array(kept));
+        }
+        if draws.is_empty() {
+            return;
+        }
+
+        let resources = page.resources().cloned().unwrap_or_default();
+        let mut xobjects = resources
+            .get("XObject")
+            .and_then(|xobjects| pdf.resolve(xobjects).as_dictionary())
+            .cloned()
+            .unwrap_or_default();
+        let mut names = Vec::new();
+        let mut counter = 0;
+        for _ in draws {
+            let name = loop {
+                counter += 1;
+                let name = format!("Fm{}", counter);
+                if !xobjects.contains_key(name.as_str()) {
+                    break name;
+                }
+            };
+            names.push(name);
+        }
+
+        let mut content = b"Q\n".to_vec();
+        for ((appearance, rect), name) in draws.iter().zip(&names) {
+            let bbox = appearance.bbox.transform(&appearance.matrix);
+            if bbox.width() == 0.0 || bbox.height() == 0.0 {
+                continue;
+            }
+            let sx = rect.width() / bbox.width();
+            let sy = rect.height() / bbox.height();
+            writeln!(
+                content,
+                "q {} 0 0 {} {} {} cm /{} Do Q",
+                sx,
+                sy,
+                rect.x0 - bbox.x0 * sx,
+                rect.y0 - bbox.y0 * sy,
+                name
+            )
+            .unwrap();
+            xobjects.insert(
+                NameObject(name),
+                Object::IndirectReference {
+                    id: appearance.reference.id,
+                    generation: appearance.reference.generation,
+                },
+            );
+        }
+        let mut resources = resources;
+        resources.insert(NameObject("XObject"), Object::Dictionary(xobjects, &[]));
+        self.writer.set(
+            page.reference,
+            "Resources",
+            &Object::Dictionary(resources, &[]),
+        );
+
+        let save = self
+            .writer
+            .add(&Object::Dictionary(DictionaryObject::new(), b"q\n"));
+        let draw = self
+            .writer
+            .add(&Object::Dictionary(DictionaryObject::new(), &content));
+        let mut contents = vec![reference_object(save)];
+        match page.dictionary.get("Contents") {
+            Some(contents_object @ Object::IndirectReference { .. })
+                if pdf.resolve(contents_object).as_array().is_none() =>
+            {
+                contents.push(contents_object.clone())
+            }
+            Some(contents_object) => contents.extend(
+                pdf.resolve(contents_object)
+                    .as_array()
+                    .unwrap_or_default()
+                    .iter()
+                    .cloned(),
+            ),
+            None => {}
+        }
+        contents.push(reference_object(draw));
+        self.writer
+            .set(page.reference, "Contents", &Object::Array(contents));
+    }
+}
+
+fn field_reference(field: &Field) -> Result<IndirectReference, ParseError> {
+    field
+        .reference
+        .ok_or_else(|| ParseError::InvalidPDFFormField(field.name.clone()))
+}
+
+/// The font and colour of a `/DA` string, e.g. `/Helv 12 Tf 0 g`.
+#[derive(Debug, Clone, Default)]
+struct TextStyle {
+    font: Option<String>,
+    /// Zero asks for automatic sizing.
+    size: f32,
+    /// The operations other than `Tf`, serialized again.
+    color: String,
+}
+
+impl TextStyle {
+    fn parse(default_appearance: &str) -> Self {
+        let mut style = TextStyle::default();
+        let content = match Content::parse(default_appearance.as_bytes()) {
+            Ok(content) => content,
+            Err(_) => return style,
+        };
+        for operation in content.operations {
+            if operation.operator == "Tf" {
+                if let [font, size] = operation.operands.as_slice() {
+                    style.font = font.as_name().map(str::to_string);
+                    style.size = size.as_f32().unwrap_or(0.0);
+                }
+                continue;
+            }
+            let mut bytes = Vec::new();
+            for operand in &operation.operands {
+                write_object(operand, &mut bytes);
+                bytes.push(b' ');
+            }
+            bytes.extend_from_slice(operation.operator.as_bytes());
+            bytes.push(b'\n');
+            style.color.push_str(&String::from_utf8_lossy(&bytes));
+        }
+        style
+    }
+}
+
+/// Encodes text with `/WinAnsiEncoding`, the encoding of the fallback font.
+/// Characters it lacks become `?`.
+fn win_ansi(text: &str) -> Vec<u8> {
+    let mut codes: HashMap<String, u8> = HashMap::new();
+    for (code, name) in WIN_ANSI_ENCODING.iter().enumerate() {
+        if let Some(unicode) = name.and_then(crate::glyph_list::to_unicode) {
+            codes.entry(unicode).or_insert(code as u8);
+        }
+    }
+    text.chars()
+        .map(|c| *codes.get(c.to_string().as_str()).unwrap_or(&b'?'))
+        .collect()
+}
@@ -0,0 +1,470 @@
+use std::io::Read;
+
+use flate2::read::ZlibDecoder;
+
+use crate::error::ParseError;
+use crate::object::{DictionaryObject, IndirectReference, Object, PDF};
+use crate::options::ParseOptions;
+use crate::utils::WHITE_SPACE_CHARS;
+
+/// Returns the names of the filters applied to a stream, in decoding order.
+pub fn filters<'a>(dictionary: &DictionaryObject<'a>) -> Vec<&'a str> {
+    filter_list(dictionary.get("Filter"))
+}
+
+/// Reads a `/Filter` value, either a single name or an array of names.
+pub fn filter_list<'a>(filter: Option<&Object<'a>>) -> Vec<&'a str> {
+    match filter {
+        Some(Object::Name(name)) => vec![name.0],
+        Some(Object::Array(array)) => array.iter().filter_map(Object::as_name).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Filters whose output is an encoded image rather than samples,
+/// which are left to image codecs.
+pub const IMAGE_FILTERS: [&str; 4] = ["DCTDecode", "JPXDecode", "JBIG2Decode", "CCITTFaxDecode"];
+
+/// Returns the full name of a filter, expanding the abbreviations of inline images.
+pub fn full_name(filter: &str) -> &str {
+    match filter {
+        "AHx" => "ASCIIHexDecode",
+        "A85" => "ASCII85Decode",
+        "LZW" => "LZWDecode",
+        "Fl" => "FlateDecode",
+        "RL" => "RunLengthDecode",
+        "CCF" => "CCITTFaxDecode",
+        "DCT" => "DCTDecode",
+        filter => filter,
+    }
+}
+
+/// The `/DecodeParms` of the `LZWDecode` and `FlateDecode` filters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeParms {
+    pub predictor: i32,
+    pub colors: usize,
+    pub bits_per_component: usize,
+    pub columns: usize,
+    pub early_change: bool,
+}
+
+impl Default for DecodeParms {
+    fn default() -> Self {
+        Self {
+            predictor: 1,
+            colors: 1,
+            bits_per_component: 8,
+            columns: 1,
+            early_change: true,
+        }
+    }
+}
+
+impl DecodeParms {
+    /// Reads a parameter dictionary, using defaults for missing entries.
+    pub fn from_object(object: &Object) -> Self {
+        let default = DecodeParms::default();
+        let get = |key: &str, default: usize| {
+            object
+                .get(key)
+                .and_then(Object::as_i32)
+                .and_then(|value| usize::try_from(value).ok())
+                .unwrap_or(default)
+        };
+        Self {
+            predictor: object
+                .get("Predictor")
+                .and_then(Object::as_i32)
+                .unwrap_or(default.predictor),
+            colors: get("Colors", default.colors),
+            bits_per_component: get("BitsPerComponent", default.bits_per_component),
+            columns: get("Columns", default.columns),
+            early_change: get("EarlyChange", 1) != 0,
+        }
+    }
+}
+
+/// Returns the `/DecodeParms` of each filter of a stream, in decoding order.
+pub fn decode_parms(dictionary: &DictionaryObject) -> Vec<DecodeParms> {
+    let count = filters(dictionary).len();
+    let parms = dictionary
+        .get("DecodeParms")
+        .or_else(|| dictionary.get("DP"));
+    let mut result: Vec<DecodeParms> = match parms {
+        Some(Object::Array(array)) => array.iter().map(DecodeParms::from_object).collect(),
+        Some(parms) => vec![DecodeParms::from_object(parms)],
+        None => Vec::new(),
+    };
+    result.resize(result.len().max(count), DecodeParms::default());
+    result
+}
+
+/// Returns the names of the filters applied to a stream, in decoding order,
+/// expanding abbreviations.
+fn filter_names<'a>(dictionary: &DictionaryObject<'a>) -> Vec<&'a str> {
+    filters(dictionary).into_iter().map(full_name).collect()
+}
+
+/// Decodes the data of a stream by applying its `/Filter` pipeline, within the
+/// limits of the default `ParseOptions`.
+pub fn decode(dictionary: &DictionaryObject, data: &[u8]) -> Result<Vec<u8>, ParseError> {
+    decode_with(dictionary, data, &ParseOptions::default())
+}
+
+/// Decodes the data of a stream, failing once any filter outputs more than
+/// `options.decoded_limit` allows for the encoded data.
+pub fn decode_with(
+    dictionary: &DictionaryObject,
+    data: &[u8],
+    options: &ParseOptions,
+) -> Result<Vec<u8>, ParseError> {
+    let limit = options.decoded_limit(data.len());
+    let mut data = data.to_vec();
+    for (filter, parms) in filter_names(dictionary)
+        .into_iter()
+        .zip(decode_parms(dictionary))
+    {
+        data = decode_filter_limited(filter, &parms, &data, limit)?;
+    }
+    Ok(data)
+}
+
+pub fn decode_filter(filter: &str, data: &[u8]) -> Result<Vec<u8>, ParseError> {
+    decode_filter_with(filter, &DecodeParms::default(), data)
+}
+
+/// Applies one filter with its parameters, within the limits of the default
+/// `ParseOptions`.
+pub fn decode_filter_with(
+    filter: &str,
+    parms: &DecodeParms,
+    data: &[u8],
+) -> Result<Vec<u8>, ParseError> {
+    let limit = ParseOptions::default().decoded_limit(data.len());
+    decode_filter_limited(filter, parms, data, limit)
+}
+
+/// Applies one filter, failing once it outputs more than `limit` bytes.
+fn decode_filter_limited(
+    filter: &str,
+    parms: &DecodeParms,
+    data: &[u8],
+    limit: usize,
+) -> Result<Vec<u8>, ParseError> {
+    let result = match full_name(filter) {
+        "FlateDecode" => predict(parms, flate_decode_limited(data, limit)?),
+        "LZWDecode" => predict(parms, lzw_decode_limited(data, parms.early_change, limit)?),
+        "ASCIIHexDecode" => ascii_hex_decode(data),
+        "ASCII85Decode" => ascii85_decode(data),
+        "RunLengthDecode" => run_length_decode(data),
+        filter => Err(ParseError::UnsupportedPDFFilter(filter.to_string())),
+    }?;
+    match result.len() <= limit {
+        true => Ok(result),
+        false => Err(decoded_limit_exceeded(limit)),
+    }
+}
+
+fn decoded_limit_exceeded(limit: usize) -> ParseError {
+    ParseError::PDFLimitExceeded(format!("stream decoded to more than {} bytes", limit))
+}
+
+pub fn flate_decode(data: &[u8]) -> Result<Vec<u8>, ParseError> {
+    flate_decode_limited(data, ParseOptions::default().decoded_limit(data.len()))
+}
+
+/// Inflates at most `limit` bytes, so that a small stream cannot exhaust memory.
+fn flate_decode_limited(data: &[u8], limit: usize) -> Result<Vec<u8>, ParseError> {
+    let mut result = Vec::new();
+    ZlibDecoder::new(data)
+        .take(limit as u64 + 1)
+        .read_to_end(&mut result)
+        .map_err(|_| ParseError::InvalidPDFStreamData)?;
+    match result.len() <= limit {
+        true => Ok(result),
+        false => Err(decoded_limit_exceeded(limit)),
+    }
+}
+
+pub fn lzw_decode(data: &[u8], early_change: bool) -> Result<Vec<u8>, ParseError> {
+    let limit = ParseOptions::default().decoded_limit(data.len());
+    lzw_decode_limited(data, early_change, limit)
+}
+
+fn lzw_decode_limited(
+    data: &[u8],
+    early_change: bool,
+    limit: usize,
+) -> Result<Vec<u8>, ParseError> {
+    const CLEAR_TABLE: usize = 256;
+    const END_OF_DATA: usize = 257;
+    let mut table: Vec<Vec<u8>> = Vec::new();
+    let reset = |table: &mut Vec<Vec<u8>>| {
+        table.clear();
+        table.extend((0..=255).map(|byte| vec![byte]));
+        // Placeholders for the clear-table and end-of-data codes.
+        table.extend([Vec::new(), Vec::new()]);
+    };
+    reset(&mut table);
+
+    let mut result = Vec::new();
+    let mut previous: Option<Vec<u8>> = None;
+    let mut bits = 0u32;
+    let mut buffer = 0u32;
+    for &byte in data {
+        buffer = buffer << 8 | byte as u32;
+        bits += 8;
+        let offset = if early_change { 1 } else { 0 };
+        let width = match table.len() + offset {
+            0..=511 => 9,
+            512..=1023 => 10,
+            1024..=2047 => 11,
+            _ => 12,
+        };
+        if bits < width {
+            continue;
+        }
+        bits -= width;
+        let code = (buffer >> bits) as usize & ((1 << width) - 1);
+        buffer &= (1 << bits) - 1;
+
+        match code {
+            CLEAR_TABLE => {
+                reset(&mut table);
+                previous = None;
+                continue;
+            }
+            END_OF_DATA => break,
+            _ => {}
+        }
+        let entry = match (table.get(code), &previous) {
+            (Some(entry), _) if code < table.len() => entry.clone(),
+            // The code being defined by this very step: previous + previous[0].
+            (None, Some(previous)) if code == table.len() => {
+                let mut entry = previous.clone();
+                entry.push(previous[0]);
+                entry
+            }
+            _ => return Err(ParseError::InvalidPDFStreamData),
+        };
+        if result.len() + entry.len() > limit {
+            return Err(decoded_limit_exceeded(limit));
+        }
+        result.extend_from_slice(&entry);
+        if let Some(mut previous) = previous.take() {
+            if table.len() < 4096 {
+                previous.push(entry[0]);
+                table.push(previous);
+            }
+        }
+        previous = Some(entry);
+    }
+    Ok(result)
+}
+
+pub fn run_length_decode(data: &[u8]) -> Result<Vec<u8>, ParseError> {
+    let mut result = Vec::new();
+    let mut rest = data;
+    while let Some((&length, tail)) = rest.split_first() {
+        match length {
+            0..=127 => {
+                let count = length as usize + 1;
+                let run = tail.get(..count).ok_or(ParseError::InvalidPDFStreamData)?;
+                result.extend_from_slice(run);
+                rest = &tail[count..];
+            }
+            128 => break,
+            _ => {
+                let &byte = tail.first().ok_or(ParseError::InvalidPDFStreamData)?;
+                result.extend(std::iter::repeat_n(byte, 257 - length as usize));
+                rest = &tail[1..];
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// Reverses the TIFF or PNG predictor applied before `FlateDecode` or `LZWDecode`.
+fn predict(parms: &DecodeParms, data: Vec<u8>) -> Result<Vec<u8>, ParseError> {
+    if parms.predictor <= 1 {
+        return Ok(data);
+    }
+    let bits_per_pixel = parms
+        .colors
+        .checked_mul(parms.bits_per_component)
+        .ok_or(ParseError::InvalidPDFStreamData)?;
+    let bytes_per_pixel = bits_per_pixel.div_ceil(8).max(1);
+    let row_length = match parms.columns.checked_mul(bits_per_pixel) {
+        Some(bits) if bits > 0 => bits.div_ceil(8),
+        _ => return Err(ParseError::InvalidPDFStreamData),
+    };
+
+    if parms.predictor == 2 {
+        // TIFF predictor 2, only supported for 8-bit components.
+        if parms.bits_per_component != 8 {
+            return Err(ParseError::InvalidPDFStreamData);
+        }
+        let mut data = data;
+        for row in data.chunks_mut(row_length) {
+            for i in bytes_per_pixel..row.len() {
+                row[i] = row[i].wrapping_add(row[i - bytes_per_pixel]);
+            }
+        }
+        return Ok(data);
+    }
+
+    // PNG predictors: each row starts with its own filter type byte.
+    let mut result: Vec<u8> = Vec::with_capacity(data.len());
+    // Rows are never longer than the data, whatever `/Columns` says.
+    let mut previous = vec![0u8; row_length.min(data.len())];
+    for chunk in data.chunks(row_length.saturating_add(1)) {
+        let (&filter, row) = match chunk.split_first() {
+            Some(split) => split,
+            None => break,
+        };
+        let mut current = row.to_vec();
+        for i in 0..current.len() {
+            let left = if i >= bytes_per_pixel {
+                current[i - bytes_per_pixel]
+            } else {
+                0
+            };
+            let up = previous[i];
+            let upper_left = if i >= bytes_per_pixel {
+                previous[i - bytes_per_pixel]
+            } else {
+                0
+            };
+            let predicted = match filter {
+                0 => 0,
+                1 => left,
+                2 => up,
+                3 => ((left as u16 + up as u16) / 2) as u8,
+                4 => paeth(left, up, upper_left),
+                _ => return Err(ParseError::InvalidPDFStreamData),
+            };
+            current[i] = current[i].wrapping_add(predicted);
+        }
+        result.extend_from_slice(&current);
+        previous[..current.len()].copy_from_slice(&current);
+    }
+    Ok(result)
+}
+
+fn paeth(left: u8, up: u8, upper_left: u8) -> u8 {
+    let estimate = left as i16 + up as i16 - upper_left as i16;
+    let distance_left = (estimate - left as i16).abs();
+    let distance_up = (estimate - up as i16).abs();
+    let distance_upper_left = (estimate - upper_left as i16).abs();
+    if distance_left <= distance_up && distance_left <= distance_upper_left {
+        left
+    } else if distance_up <= distance_upper_left {
+        up
+    } else {
+        upper_left
+    }
+}
+
+pub fn ascii_hex_decode(data: &[u8]) -> Result<Vec<u8>, ParseError> {
+    let mut digits = Vec::new();
+    for &c in data {
+        match c {
+            b'>' => break,
+            c if WHITE_SPACE_CHARS.contains(&c) => {}
+            c if c.is_ascii_hexdigit() => digits.push(hex_value(c)),
+            _ => return Err(ParseError::InvalidPDFStreamData),
+        }
+    }
+    // An odd number of digits behaves as if the last one were followed by 0.
+    if digits.len() % 2 == 1 {
+        digits.push(0);
+    }
+    Ok(digits
+        .chunks(2)
+        .map(|pair| pair[0] << 4 | pair[1])
+        .collect())
+}
+
+pub fn ascii85_decode(data: &[u8]) -> Result<Vec<u8>, ParseError> {
+    let mut result = Vec::new();
+    let mut group = [0u8; 5];
+    let mut length = 0;
+    for &c in data {
+        match c {
+            b'~' => break,
+            b'z' if length == 0 => result.extend_from_slice(&[0; 4]),
+            b'!'..=b'u' => {
+                group[length] = c - b'!';
+                length += 1;
+                if length == 5 {
+                    result.extend_from_slice(&ascii85_group(&group));
+                    length = 0;
+                }
+            }
+            c if WHITE_SPACE_CHARS.contains(&c) => {}
+            _ => return Err(ParseError::InvalidPDFStreamData),
+        }
+    }
+    // A final partial group of n characters is padded with 'u' and yields n - 1 bytes.
+    if length == 1 {
+        return Err(ParseError::InvalidPDFStreamData);
+    }
+    if length > 1 {
+        group[length..].fill(b'u' - b'!');
+        result.extend_from_slice(&ascii85_group(&group)[..length - 1]);
+    }
+    Ok(result)
+}
+
+fn ascii85_group(group: &[u8; 5]) -> [u8; 4] {
+    let value = group
+        .iter()
+        .fold(0u64, |value, &digit| value * 85 + digit as u64);
+    (value as u32).to_be_bytes()
+}
+
+pub(crate) fn hex_value(c: u8) -> u8 {
+    match c {
+        b'0'..=b'9' => c - b'0',
+        b'a'..=b'f' => c - b'a' + 10,
+        b'A'..=b'F' => c - b'A' + 10,
+        _ => 0,
+    }
+}
+
+impl<'a> PDF<'a> {
+    /// Decodes the streams of the body, in body order. An object defined more
+    /// than once is decoded for each definition.
+    pub fn decode_streams(&self) -> Vec<(IndirectReference, Result<Vec<u8>, ParseError>)> {
+        self.body
+            .iter()
+            .filter_map(|object| self.decode_stream(object))
+            .collect()
+    }
+
+    /// Decodes an indirect object of the body if it is a stream.
+    pub(crate) fn decode_stream(
+        &self,
+        object: &Object<'a>,
+    ) -> Option<(IndirectReference, Result<Vec<u8>, ParseError>)> {
+        let (reference, object) = match object {
+            Object::IndirectObject {
+                id,
+                generation,
+                dictionary,
+            } => (
+                IndirectReference {
+                    id: *id,
+                    generation: *generation,
+                },
+                &**dictionary,
+            ),
+            _ => return None,
+        };
+        let (dictionary, data) = self
+            .stream_data(object)
+            .filter(|(_, data)| !data.is_empty())?;
+        Some((reference, decode(dictionary, data)))
+    }
+}
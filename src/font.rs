@@ -0,0 +1,355 @@
+use crate::cmap::CMap;
+use crate::interpreter::Matrix;
+use crate::object::{DictionaryObject, Object, PDF};
+
+/// Width of a glyph missing from `/Widths`, in glyph space units.
+const DEFAULT_WIDTH: f32 = 0.0;
+/// Approximate glyph width of fonts without `/Widths`, such as the standard 14 fonts
+/// whose metrics are not embedded in the document.
+const APPROXIMATE_WIDTH: f32 = 500.0;
+/// Default `/DW` of CIDFonts.
+const DEFAULT_CID_WIDTH: f32 = 1000.0;
+/// `/Ascent` and `/Descent` of fonts without a font descriptor, in glyph space units.
+const DEFAULT_ASCENT: f32 = 800.0;
+const DEFAULT_DESCENT: f32 = -200.0;
+/// The `Symbolic` bit of the font descriptor `/Flags`.
+const SYMBOLIC_FLAG: i32 = 1 << 2;
+
+/// A range of CIDs sharing widths, from a CIDFont `/W` array.
+#[derive(Debug, Clone, PartialEq)]
+struct CidWidths {
+    first: u32,
+    last: u32,
+    /// Either one width per CID or a single width for the whole range.
+    widths: Vec<f32>,
+}
+
+/// A font resource, holding what is needed to lay out the glyphs of a string.
+#[derive(Debug, Clone)]
+pub struct Font {
+    /// `/Subtype`, e.g. `Type1`, `TrueType`, `Type3` or `Type0`.
+    pub subtype: String,
+    /// `/BaseFont`, if any.
+    pub base_font: Option<String>,
+    /// Maps glyph space to text space, `[0.001 0 0 0.001 0 0]` for all fonts but Type 3.
+    pub font_matrix: Matrix,
+    /// Height above the baseline of the tallest glyphs, in glyph space units.
+    pub ascent: f32,
+    /// Depth below the baseline of the lowest glyphs, in glyph space units (negative).
+    pub descent: f32,
+    first_char: u32,
+    widths: Vec<f32>,
+    missing_width: f32,
+    /// The Unicode text of each code of a simple font, from `/Encoding` and `/Differences`.
+    encoding: Option<Vec<Option<String>>>,
+    /// The `/Encoding` CMap of a composite (Type 0) font.
+    cmap: Option<CMap>,
+    cid_widths: Vec<CidWidths>,
+    to_unicode: Option<CMap>,
+}
+
+impl Default for Font {
+    fn default() -> Self {
+        Self {
+            subtype: "Type1".to_string(),
+            base_font: None,
+            font_matrix: Matrix::new(0.001, 0.0, 0.0, 0.001, 0.0, 0.0),
+            ascent: DEFAULT_ASCENT,
+            descent: DEFAULT_DESCENT,
+            first_char: 0,
+            widths: Vec::new(),
+            missing_width: DEFAULT_WIDTH,
+            encoding: None,
+            cmap: None,
+            cid_widths: Vec::new(),
+            to_unicode: None,
+        }
+    }
+}
+
+impl Font {
+    /// Reads a font dictionary.
+    /// Missing or malformed entries fall back to their defaults rather than failing,
+    /// so that text can still be laid out approximately.
+    pub fn from_object(pdf: &PDF, object: &Object) -> Self {
+        let mut font = Font::default();
+        let dictionary = match pdf.resolve(object).as_dictionary() {
+            Some(dictionary) => dictionary,
+            None => return font,
+        };
+        let get = |key: &str| dictionary.get(key).map(|value| pdf.resolve(value));
+
+        if let Some(subtype) = get("Subtype").and_then(Object::as_name) {
+            font.subtype = subtype.to_string();
+        }
+        font.base_font = get("BaseFont")
+            .and_then(Object::as_name)
+            .map(str::to_string);
+        if let Some(matrix) = get("FontMatrix").and_then(|m| Matrix::from_object(pdf, m)) {
+            font.font_matrix = matrix;
+        }
+        font.to_unicode = get("ToUnicode").and_then(|stream| read_cmap(stream));
+
+        if font.subtype == "Type0" {
+            font.read_composite(pdf, dictionary);
+            return font;
+        }
+
+        font.first_char = get("FirstChar")
+            .and_then(Object::as_i32)
+            .unwrap_or(0)
+            .max(0) as u32;
+        match get("Widths").and_then(Object::as_array) {
+            Some(widths) => {
+                font.widths = widths
+                    .iter()
+                    .map(|width| pdf.resolve(width).as_f32().unwrap_or(DEFAULT_WIDTH))
+                    .collect()
+            }
+            None => font.missing_width = APPROXIMATE_WIDTH,
+        }
+        let descriptor = get("FontDescriptor");
+        if font.subtype == "Type3" {
+            // Type 3 glyph space is arbitrary, so the default metrics cannot apply.
+            font.read_bounding_box(pdf, dictionary.get("FontBBox"));
+        }
+        font.read_metrics(pdf, descriptor);
+        if let Some(width) = descriptor
+            .and_then(|descriptor| pdf.get(descriptor, "MissingWidth"))
+            .and_then(Object::as_f32)
+        {
+            font.missing_width = width;
+        }
+        let symbolic = descriptor
+            .and_then(|descriptor| pdf.get(descriptor, "Flags"))
+            .and_then(Object::as_i32)
+            .is_some_and(|flags| flags & SYMBOLIC_FLAG != 0);
+        font.encoding = read_encoding(pdf, get("Encoding"), symbolic);
+
+        font
+    }
+
+    fn read_composite(&mut self, pdf: &PDF, dictionary: &DictionaryObject) {
+        // Predefined CMaps other than Identity are not bundled; two-byte codes
+        // are the most likely guess for them.
+        self.cmap = match dictionary.get("Encoding").map(|value| pdf.resolve(value)) {
+            Some(stream @ Object::Dictionary(..)) => read_cmap(stream),
+            _ => None,
+        }
+        .or_else(|| Some(CMap::identity()));
+
+        let descendant = match dictionary
+            .get("DescendantFonts")
+            .map(|value| pdf.resolve(value))
+            .and_then(Object::as_array)
+            .and_then(<[Object]>::first)
+        {
+            Some(descendant) => pdf.resolve(descendant),
+            None => return,
+        };
+        self.missing_width = pdf
+            .get(descendant, "DW")
+            .and_then(Object::as_f32)
+            .unwrap_or(DEFAULT_CID_WIDTH);
+        if let Some(array) = pdf.get(descendant, "W").and_then(Object::as_array) {
+            self.cid_widths = read_cid_widths(pdf, array);
+        }
+        self.read_metrics(pdf, pdf.get(descendant, "FontDescriptor"));
+    }
+
+    /// Reads `/Ascent` and `/Descent` from the font descriptor.
+    /// Some producers write zeros there, which are ignored as well.
+    fn read_metrics(&mut self, pdf: &PDF, descriptor: Option<&Object>) {
+        let descriptor = match descriptor {
+            Some(descriptor) => descriptor,
+            None => return,
+        };
+        let metric = |key| pdf.get(descriptor, key).and_then(Object::as_f32);
+        if let Some(ascent) = metric("Ascent").filter(|&ascent| ascent != 0.0) {
+            self.ascent = ascent;
+        }
+        if let Some(descent) = metric("Descent").filter(|&descent| descent != 0.0) {
+            self.descent = descent;
+        }
+    }
+
+    /// Reads the ascent and descent from a `/FontBBox` array.
+    fn read_bounding_box(&mut self, pdf: &PDF, bbox: Option<&Object>) {
+        let numbers: Vec<f32> = match bbox.map(|bbox| pdf.resolve(bbox)) {
+            Some(Object::Array(array)) => array
+                .iter()
+                .filter_map(|number| pdf.resolve(number).as_f32())
+                .collect(),
+            _ => return,
+        };
+        if let [_, y0, _, y1] = numbers[..] {
+            if y0 != y1 {
+                self.descent = y0.min(y1);
+                self.ascent = y0.max(y1);
+            }
+        }
+    }
+
+    /// Splits a string into character codes.
+    /// Simple fonts use one byte per code, composite fonts follow their CMap.
+    pub fn codes(&self, text: &[u8]) -> Vec<u32> {
+        match &self.cmap {
+            Some(cmap) => cmap.codes(text),
+            None => text.iter().map(|&byte| byte as u32).collect(),
+        }
+    }
+
+    /// Returns the width of a glyph in glyph space units.
+    pub fn width(&self, code: u32) -> f32 {
+        if let Some(cmap) = &self.cmap {
+            let cid = cmap.cid(code);
+            return self
+                .cid_widths
+                .iter()
+                .find(|range| range.first <= cid && cid <= range.last)
+                .map_or(self.missing_width, |range| {
+                    let index = (cid - range.first) as usize;
+                    match range.widths.as_slice() {
+                        [width] => *width,
+                        widths => widths.get(index).copied().unwrap_or(self.missing_width),
+                    }
+                });
+        }
+        code.checked_sub(self.first_char)
+            .and_then(|index| self.widths.get(index as usize))
+            .copied()
+            .unwrap_or(self.missing_width)
+    }
+
+    /// Returns the Unicode text of a character code.
+    /// `/ToUnicode` takes precedence over the encoding. Simple fonts without a known
+    /// encoding read codes as Latin-1; codes that cannot be mapped become U+FFFD.
+    pub fn to_unicode(&self, code: u32) -> String {
+        if let Some(text) = self
+            .to_unicode
+            .as_ref()
+            .and_then(|cmap| cmap.to_unicode(code))
+        {
+            return text;
+        }
+        if self.cmap.is_none() {
+            match (&self.encoding, u8::try_from(code)) {
+                (Some(encoding), Ok(byte)) => {
+                    if let Some(text) = &encoding[byte as usize] {
+                        return text.clone();
+                    }
+                }
+                (None, Ok(byte)) => return char::from(byte).to_string(),
+                _ => {}
+            }
+        }
+        char::REPLACEMENT_CHARACTER.to_string()
+    }
+
+    /// Whether word spacing applies to `code`, i.e. it is the single-byte code 32.
+    pub fn is_space(&self, code: u32) -> bool {
+        code == 32
+            && self
+                .cmap
+                .as_ref()
+                .is_none_or(|cmap| cmap.is_single_byte(code))
+    }
+}
+
+fn read_cmap(stream: &Object) -> Option<CMap> {
+    let (dictionary, data) = stream.as_stream()?;
+    let data = crate::filter::decode(dictionary, data).ok()?;
+    CMap::parse(&data).ok()
+}
+
+/// Builds the Unicode table of a simple font from its `/Encoding`.
+/// Without one, non-symbolic fonts use `/StandardEncoding`, and symbolic fonts
+/// have no known encoding.
+fn read_encoding(
+    pdf: &PDF,
+    encoding: Option<&Object>,
+    symbolic: bool,
+) -> Option<Vec<Option<String>>> {
+    let default = if symbolic {
+        None
+    } else {
+        Some("StandardEncoding")
+    };
+    let (base, differences) = match encoding {
+        Some(Object::Name(name)) => (Some(name.0), None),
+        Some(encoding @ Object::Dictionary(..)) => (
+            pdf.get(encoding, "BaseEncoding")
+                .and_then(Object::as_name)
+                .or(default),
+            pdf.get(encoding, "Differences").and_then(Object::as_array),
+        ),
+        _ => (default, None),
+    };
+
+    let mut names: Vec<Option<&str>> = match base.and_then(crate::encoding::encoding) {
+        Some(table) => table.to_vec(),
+        None if differences.is_some() => vec![None; 256],
+        None => return None,
+    };
+    // `/Differences` is a list of codes, each followed by the names of consecutive codes.
+    // The names after a negative code are skipped.
+    let mut code = Some(0usize);
+    for difference in differences.unwrap_or_default() {
+        match pdf.resolve(difference) {
+            Object::Integer(start) => code = usize::try_from(*start).ok(),
+            Object::Name(name) => {
+                if let Some(entry) = code.and_then(|code| names.get_mut(code)) {
+                    *entry = Some(name.0);
+                }
+                code = code.and_then(|code| code.checked_add(1));
+            }
+            _ => {}
+        }
+    }
+
+    Some(
+        names
+            .into_iter()
+            .map(|name| name.and_then(crate::glyph_list::to_unicode))
+            .collect(),
+    )
+}
+
+/// Reads a `/W` array, made of `c [w1 w2 ...]` and `c_first c_last w` entries.
+fn read_cid_widths(pdf: &PDF, array: &[Object]) -> Vec<CidWidths> {
+    let mut result = Vec::new();
+    let mut rest = array;
+    while let Some(first) = rest.first().and_then(|first| pdf.resolve(first).as_i32()) {
+        let first = first.max(0) as u32;
+        match rest.get(1).map(|second| pdf.resolve(second)) {
+            Some(Object::Array(widths)) => {
+                let widths: Vec<f32> = widths
+                    .iter()
+                    .map(|width| pdf.resolve(width).as_f32().unwrap_or(DEFAULT_CID_WIDTH))
+                    .collect();
+                if !widths.is_empty() {
+                    result.push(CidWidths {
+                        first,
+                        last: first.saturating_add(widths.len() as u32 - 1),
+                        widths,
+                    });
+                }
+                rest = &rest[2..];
+            }
+            Some(last) => {
+                let last = last.as_i32().map(|last| last.max(0) as u32);
+                let width = rest.get(2).and_then(|width| pdf.resolve(width).as_f32());
+                if let (Some(last), Some(width)) = (last, width) {
+                    result.push(CidWidths {
+                        first,
+                        last,
+                        widths: vec![width],
+                    });
+                }
+                rest = rest.get(3..).unwrap_or_default();
+            }
+            None => break,
+        }
+    }
+    result
+}
@@ -0,0 +1,380 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::error::ParseError;
+use crate::interpreter::Rect;
+use crate::object::{IndirectReference, Object, PDF};
+
+/// Maximum depth of the field tree followed from `/Fields`.
+const MAX_FIELD_DEPTH: usize = 64;
+
+/// Field flags (`/Ff`) common to all field types.
+pub const READ_ONLY: u32 = 1;
+pub const REQUIRED: u32 = 1 << 1;
+pub const NO_EXPORT: u32 = 1 << 2;
+/// Field flags of text fields.
+pub const MULTILINE: u32 = 1 << 12;
+pub const PASSWORD: u32 = 1 << 13;
+/// Field flags of button fields.
+pub const NO_TOGGLE_TO_OFF: u32 = 1 << 14;
+pub const RADIO: u32 = 1 << 15;
+pub const PUSHBUTTON: u32 = 1 << 16;
+/// Field flags of choice fields.
+pub const COMBO: u32 = 1 << 17;
+pub const EDIT: u32 = 1 << 18;
+pub const MULTI_SELECT: u32 = 1 << 21;
+
+/// The `/FT` of a field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldType {
+    /// `/Btn`: push buttons, check boxes and radio buttons.
+    Button,
+    /// `/Tx`
+    Text,
+    /// `/Ch`: list and combo boxes.
+    Choice,
+    /// `/Sig`
+    Signature,
+}
+
+impl FieldType {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "Btn" => Some(FieldType::Button),
+            "Tx" => Some(FieldType::Text),
+            "Ch" => Some(FieldType::Choice),
+            "Sig" => Some(FieldType::Signature),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            FieldType::Button => "Btn",
+            FieldType::Text => "Tx",
+            FieldType::Choice => "Ch",
+            FieldType::Signature => "Sig",
+        }
+    }
+}
+
+/// The `/V` or `/DV` of a field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldValue {
+    /// The text of a text or choice field.
+    Text(String),
+    /// The state of a button, e.g. `Off` or the export name of a check box.
+    Name(String),
+    /// The selected options of a multiple-selection choice field.
+    List(Vec<String>),
+}
+
+impl FieldValue {
+    pub fn from_object(pdf: &PDF, object: &Object) -> Option<Self> {
+        match pdf.resolve(object) {
+            Object::Name(name) => Some(FieldValue::Name(name.0.to_string())),
+            Object::Array(values) => Some(FieldValue::List(
+                values
+                    .iter()
+                    .filter_map(|value| pdf.resolve(value).text_string())
+                    .collect(),
+            )),
+            // Rich text values may be given as a stream.
+            Object::Dictionary(dictionary, data) => {
+                let data = crate::filter::decode(dictionary, data).ok()?;
+                Some(FieldValue::Text(crate::encoding::decode_text_string(&data)))
+            }
+            string => string.text_string().map(FieldValue::Text),
+        }
+    }
+}
+
+/// An option of a choice field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChoiceOption {
+    /// The value the option sets the field to.
+    pub export: String,
+    /// The text shown, the same as `export` unless given separately.
+    pub label: String,
+}
+
+/// A widget annotation, i.e. where a field is shown.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Widget {
+    pub reference: Option<IndirectReference>,
+    pub rect: Option<Rect>,
+    /// The index of the page the widget is on, if known.
+    pub page: Option<usize>,
+    /// The `/AS` appearance state of check boxes and radio buttons.
+    pub appearance_state: Option<String>,
+}
+
+/// A node of the field tree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    /// The fully qualified name, i.e. the `/T` of the field and of its ancestors
+    /// joined with periods.
+    pub name: String,
+    /// The `/T` of the field itself.
+    pub partial_name: Option<String>,
+    pub reference: Option<IndirectReference>,
+    /// The field type and the entries below are inherited from ancestors.
+    pub field_type: Option<FieldType>,
+    pub value: Option<FieldValue>,
+    pub default_value: Option<FieldValue>,
+    pub flags: u32,
+    /// The `/DA` default appearance of variable text.
+    pub default_appearance: Option<String>,
+    pub options: Vec<ChoiceOption>,
+    pub widgets: Vec<Widget>,
+    /// The index of the parent field in `Form::fields`.
+    pub parent: Option<usize>,
+    /// The indices of the child fields in `Form::fields`; widgets are not fields.
+    pub children: Vec<usize>,
+}
+
+impl Field {
+    /// Whether the field has no child fields, i.e. holds a value of its own.
+    pub fn is_terminal(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    pub fn has_flag(&self, flag: u32) -> bool {
+        self.flags & flag != 0
+    }
+}
+
+/// The interactive form of a document, from the catalog `/AcroForm`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Form {
+    /// The fields in tree order, parents before their children.
+    pub fields: Vec<Field>,
+    pub need_appearances: bool,
+    /// The document-wide `/DA`, used by fields without one.
+    pub default_appearance: Option<String>,
+}
+
+impl Form {
+    /// Looks up a field by fully qualified name.
+    pub fn field(&self, name: &str) -> Option<&Field> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+
+    /// Returns the fields holding values, i.e. the leaves of the field tree.
+    pub fn terminal_fields(&self) -> impl Iterator<Item = &Field> {
+        self.fields.iter().filter(|field| field.is_terminal())
+    }
+}
+
+/// The inheritable entries of a field.
+#[derive(Debug, Clone, Default)]
+struct Inherited {
+    name: String,
+    field_type: Option<FieldType>,
+    value: Option<FieldValue>,
+    default_value: Option<FieldValue>,
+    flags: u32,
+    default_appearance: Option<String>,
+    options: Vec<ChoiceOption>,
+}
+
+struct FieldReader<'p, 'a> {
+    pdf: &'p PDF<'a>,
+    /// Page index of each annotation listed in a page `/Annots`.
+    annotation_pages: HashMap<IndirectReference, usize>,
+    page_indices: HashMap<IndirectReference, usize>,
+    visited: HashSet<IndirectReference>,
+    fields: Vec<Field>,
+}
+
+impl<'p, 'a> FieldReader<'p, 'a> {
+    fn field(
+        &mut self,
+        object: &'p Object<'a>,
+        parent: Option<usize>,
+        inherited: &Inherited,
+        depth: usize,
+    ) {
+        if depth > MAX_FIELD_DEPTH {
+            return;
+        }
+        let reference = object.as_reference();
+        if let Some(reference) = reference {
+            if !self.visited.insert(reference) {
+                return;
+            }
+        }
+        let pdf = self.pdf;
+        if pdf.resolve(object).as_dictionary().is_none() {
+            return;
+        }
+        let get = |key: &str| pdf.get(object, key);
+
+        let partial_name = get("T").and_then(Object::text_string);
+        let mut current = inherited.clone();
+        current.name = match (&partial_name, inherited.name.is_empty()) {
+            (Some(partial), true) => partial.clone(),
+            (Some(partial), false) => format!("{}.{}", inherited.name, partial),
+            (None, _) => inherited.name.clone(),
+        };
+        if let Some(field_type) = get("FT").and_then(Object::as_name) {
+            current.field_type = FieldType::from_name(field_type);
+        }
+        if let Some(value) = get("V") {
+            current.value = FieldValue::from_object(pdf, value);
+        }
+        if let Some(value) = get("DV") {
+            current.default_value = FieldValue::from_object(pdf, value);
+        }
+        if let Some(flags) = get("Ff").and_then(Object::as_i32) {
+            current.flags = flags as u32;
+        }
+        if let Some(appearance) = get("DA").and_then(Object::text_string) {
+            current.default_appearance = Some(appearance);
+        }
+        if let Some(options) = get("Opt").and_then(Object::as_array) {
+            current.options = options
+                .iter()
+                .filter_map(|option| read_option(pdf, option))
+                .collect();
+        }
+
+        let index = self.fields.len();
+        self.fields.push(Field {
+            name: current.name.clone(),
+            partial_name,
+            reference,
+            field_type: current.field_type,
+            value: current.value.clone(),
+            default_value: current.default_value.clone(),
+            flags: current.flags,
+            default_appearance: current.default_appearance.clone(),
+            options: current.options.clone(),
+            widgets: Vec::new(),
+            parent,
+            children: Vec::new(),
+        });
+        if let Some(parent) = parent {
+            self.fields[parent].children.push(index);
+        }
+
+        let kids = get("Kids").and_then(Object::as_array).unwrap_or_default();
+        // A field without kids is merged with its only widget.
+        if kids.is_empty() && pdf.get(object, "Subtype").and_then(Object::as_name) == Some("Widget")
+        {
+            let widget = self.widget(object);
+            self.fields[index].widgets.push(widget);
+        }
+        for kid in kids {
+            if is_field(pdf, kid) {
+                self.field(kid, Some(index), &current, depth + 1);
+            } else {
+                let widget = self.widget(kid);
+                self.fields[index].widgets.push(widget);
+            }
+        }
+    }
+
+    fn widget(&self, object: &Object) -> Widget {
+        let pdf = self.pdf;
+        let reference = object.as_reference();
+        let page = reference
+            .and_then(|reference| self.annotation_pages.get(&reference))
+            .or_else(|| {
+                let page = pdf.resolve(object).get("P")?.as_reference()?;
+                self.page_indices.get(&page)
+            })
+            .copied();
+        Widget {
+            reference,
+            rect: pdf
+                .get(object, "Rect")
+                .and_then(|rect| Rect::from_object(pdf, rect)),
+            page,
+            appearance_state: pdf
+                .get(object, "AS")
+                .and_then(Object::as_name)
+                .map(str::to_string),
+        }
+    }
+}
+
+/// Whether a kid of a field is a field itself rather than a widget.
+/// Widgets have no name and no kids of their own.
+fn is_field(pdf: &PDF, kid: &Object) -> bool {
+    ["T", "FT", "Kids"]
+        .iter()
+        .any(|key| pdf.get(kid, key).is_some())
+}
+
+/// Reads an `/Opt` entry, either the export value or an `[export label]` pair.
+fn read_option(pdf: &PDF, option: &Object) -> Option<ChoiceOption> {
+    match pdf.resolve(option) {
+        Object::Array(pair) => {
+            let export = pdf.resolve(pair.first()?).text_string()?;
+            let label = pair
+                .get(1)
+                .and_then(|label| pdf.resolve(label).text_string())
+                .unwrap_or_else(|| export.clone());
+            Some(ChoiceOption { export, label })
+        }
+        string => {
+            let export = string.text_string()?;
+            Some(ChoiceOption {
+                label: export.clone(),
+                export,
+            })
+        }
+    }
+}
+
+impl<'a> PDF<'a> {
+    /// Reads the interactive form of the document, `None` if there is no `/AcroForm`.
+    pub fn form(&self) -> Result<Option<Form>, ParseError> {
+        let catalog = self.catalog()?;
+        let acro_form = match self.get(catalog, "AcroForm") {
+            Some(acro_form) => acro_form,
+            None => return Ok(None),
+        };
+
+        let mut reader = FieldReader {
+            pdf: self,
+            annotation_pages: HashMap::new(),
+            page_indices: HashMap::new(),
+            visited: HashSet::new(),
+            fields: Vec::new(),
+        };
+        for (index, page) in self.pages()?.iter().enumerate() {
+            reader.page_indices.insert(page.reference, index);
+            let annotations = page
+                .dictionary
+                .get("Annots")
+                .and_then(|annotations| self.resolve(annotations).as_array())
+                .unwrap_or_default();
+            for reference in annotations.iter().filter_map(Object::as_reference) {
+                reader.annotation_pages.entry(reference).or_insert(index);
+            }
+        }
+
+        let default_appearance = self.get(acro_form, "DA").and_then(Object::text_string);
+        let inherited = Inherited {
+            default_appearance: default_appearance.clone(),
+            ..Default::default()
+        };
+        let fields = self
+            .get(acro_form, "Fields")
+            .and_then(Object::as_array)
+            .unwrap_or_default();
+        for field in fields {
+            reader.field(field, None, &inherited, 0);
+        }
+
+        Ok(Some(Form {
+            fields: reader.fields,
+            need_appearances: self
+                .get(acro_form, "NeedAppearances")
+                .and_then(Object::as_bool)
+                .unwrap_or(false),
+            default_appearance,
+        }))
+    }
+}
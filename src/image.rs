@@ -0,0 +1,560 @@
+use std::collections::HashSet;
+
+use crate::document::Page;
+use crate::error::ParseError;
+use crate::filter::{DecodeParms, IMAGE_FILTERS};
+use crate::interpreter::{GraphicsState, ImageDraw, Matrix, Visitor};
+use crate::object::{DictionaryObject, IndirectReference, Object, PDF};
+
+/// Maximum nesting of named and base colour spaces.
+const MAX_COLOR_SPACE_DEPTH: usize = 8;
+/// Maximum number of components of a colour, the limit on DeviceN colorants.
+const MAX_COMPONENTS: usize = 32;
+
+/// A colour space, as far as it matters to convert image samples.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColorSpace {
+    DeviceGray,
+    DeviceRGB,
+    DeviceCMYK,
+    CalGray,
+    CalRGB,
+    Lab,
+    /// An ICC profile with its number of components.
+    ICCBased(usize),
+    /// A palette of `hival + 1` colours of the base space, `lookup` holding their components.
+    Indexed {
+        base: Box<ColorSpace>,
+        hival: u8,
+        lookup: Vec<u8>,
+    },
+    /// A single colorant, with its name.
+    Separation(String),
+    /// Several colorants, with their number.
+    DeviceN(usize),
+    Pattern,
+    Other(String),
+}
+
+impl ColorSpace {
+    /// Reads a colour space name or array, looking up names in the `/ColorSpace`
+    /// resources. Inline image abbreviations are accepted as well.
+    pub fn from_object(
+        pdf: &PDF,
+        object: &Object,
+        resources: Option<&DictionaryObject>,
+    ) -> Option<Self> {
+        Self::read(pdf, object, resources, 0)
+    }
+
+    fn read(
+        pdf: &PDF,
+        object: &Object,
+        resources: Option<&DictionaryObject>,
+        depth: usize,
+    ) -> Option<Self> {
+        if depth > MAX_COLOR_SPACE_DEPTH {
+            return None;
+        }
+        let object = pdf.resolve(object);
+        let (family, parameters) = match object {
+            Object::Name(name) => (name.0, &[][..]),
+            Object::Array(array) => (array.first()?.as_name()?, &array[1..]),
+            _ => return None,
+        };
+        let parameter = |index: usize| parameters.get(index).map(|value| pdf.resolve(value));
+
+        Some(match family {
+            "DeviceGray" | "G" => ColorSpace::DeviceGray,
+            "DeviceRGB" | "RGB" => ColorSpace::DeviceRGB,
+            "DeviceCMYK" | "CMYK" => ColorSpace::DeviceCMYK,
+            "CalGray" => ColorSpace::CalGray,
+            "CalRGB" => ColorSpace::CalRGB,
+            "Lab" => ColorSpace::Lab,
+            "Pattern" => ColorSpace::Pattern,
+            "ICCBased" => {
+                let profile = parameter(0)?;
+                let components = pdf
+                    .get(profile, "N")
+                    .and_then(Object::as_i32)
+                    .and_then(|n| usize::try_from(n).ok())
+                    .or_else(|| {
+                        let alternate = pdf.get(profile, "Alternate")?;
+                        Some(Self::read(pdf, alternate, resources, depth + 1)?.components())
+                    })
+                    .filter(|n| (1..=MAX_COMPONENTS).contains(n))?;
+                ColorSpace::ICCBased(components)
+            }
+            "Indexed" | "I" => {
+                let base = Self::read(pdf, parameter(0)?, resources, depth + 1)?;
+                let hival = parameter(1)?.as_i32()?.clamp(0, 255) as u8;
+                let lookup = match parameter(2)? {
+                    Object::Dictionary(dictionary, data) => {
+                        crate::filter::decode(dictionary, data).ok()?
+                    }
+                    string => string.string_bytes()?,
+                };
+                ColorSpace::Indexed {
+                    base: Box::new(base),
+                    hival,
+                    lookup,
+                }
+            }
+            "Separation" => ColorSpace::Separation(parameter(0)?.as_name()?.to_string()),
+            "DeviceN" => {
+                let colorants = parameter(0)?.as_array()?.len();
+                if !(1..=MAX_COMPONENTS).contains(&colorants) {
+                    return None;
+                }
+                ColorSpace::DeviceN(colorants)
+            }
+            name => match resources
+                .and_then(|resources| resources.get("ColorSpace"))
+                .and_then(|spaces| pdf.get(spaces, name))
+            {
+                Some(space) => Self::read(pdf, space, resources, depth + 1)?,
+                None => ColorSpace::Other(name.to_string()),
+            },
+        })
+    }
+
+    /// The colour space family, e.g. `DeviceRGB` or `Indexed`.
+    pub fn family(&self) -> &str {
+        match self {
+            ColorSpace::DeviceGray => "DeviceGray",
+            ColorSpace::DeviceRGB => "DeviceRGB",
+            ColorSpace::DeviceCMYK => "DeviceCMYK",
+            ColorSpace::CalGray => "CalGray",
+            ColorSpace::CalRGB => "CalRGB",
+            ColorSpace::Lab => "Lab",
+            ColorSpace::ICCBased(_) => "ICCBased",
+            ColorSpace::Indexed { .. } => "Indexed",
+            ColorSpace::Separation(_) => "Separation",
+            ColorSpace::DeviceN(_) => "DeviceN",
+            ColorSpace::Pattern => "Pattern",
+            ColorSpace::Other(name) => name,
+        }
+    }
+
+    /// The number of components of a colour.
+    pub fn components(&self) -> usize {
+        match self {
+            ColorSpace::DeviceGray
+            | ColorSpace::CalGray
+            | ColorSpace::Indexed { .. }
+            | ColorSpace::Separation(_)
+            | ColorSpace::Pattern
+            | ColorSpace::Other(_) => 1,
+            ColorSpace::DeviceRGB | ColorSpace::CalRGB | ColorSpace::Lab => 3,
+            ColorSpace::DeviceCMYK => 4,
+            ColorSpace::ICCBased(n) | ColorSpace::DeviceN(n) => *n,
+        }
+    }
+
+    /// Whether colours convert to gray rather than RGB.
+    fn is_gray(&self) -> bool {
+        match self {
+            ColorSpace::DeviceGray | ColorSpace::CalGray | ColorSpace::Separation(_) => true,
+            ColorSpace::ICCBased(n) => *n == 1,
+            ColorSpace::Indexed { base, .. } => base.is_gray(),
+            _ => false,
+        }
+    }
+
+    /// Converts a colour to 8-bit gray or RGB, approximating calibrated spaces
+    /// by their device counterparts.
+    fn convert(&self, values: &[f32], output: &mut Vec<u8>) -> Result<(), ParseError> {
+        let byte = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+        match (self, values) {
+            (ColorSpace::DeviceGray | ColorSpace::CalGray | ColorSpace::ICCBased(1), [gray]) => {
+                output.push(byte(*gray))
+            }
+            // A tint of 1 is the full colorant, i.e. black.
+            (ColorSpace::Separation(_), [tint]) => output.push(byte(1.0 - tint)),
+            (ColorSpace::DeviceRGB | ColorSpace::CalRGB | ColorSpace::ICCBased(3), rgb) => {
+                output.extend(rgb.iter().map(|value| byte(*value)))
+            }
+            (ColorSpace::DeviceCMYK | ColorSpace::ICCBased(4), [c, m, y, k]) => {
+                output.extend([c, m, y].map(|value| byte((1.0 - value) * (1.0 - k))))
+            }
+            (
+                ColorSpace::Indexed {
+                    base,
+                    hival,
+                    lookup,
+                },
+                [index],
+            ) => {
+                let index = index.round().clamp(0.0, *hival as f32) as usize;
+                let n = base.components();
+                let entry = lookup
+                    .get(index * n..(index + 1) * n)
+                    .ok_or(ParseError::InvalidPDFImage)?;
+                let values: Vec<f32> = entry.iter().map(|&value| value as f32 / 255.0).collect();
+                base.convert(&values, output)?;
+            }
+            _ => {
+                return Err(ParseError::UnsupportedPDFColorSpace(
+                    self.family().to_string(),
+                ))
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The file format an image is exported to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    /// `/DCTDecode` data, which is a JPEG file as is.
+    Jpeg,
+    /// `/JPXDecode` data, which is a JPEG 2000 file as is.
+    Jpeg2000,
+    /// Decoded samples, encoded as PNG.
+    Png,
+}
+
+impl ImageFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ImageFormat::Jpeg => "jpg",
+            ImageFormat::Jpeg2000 => "jp2",
+            ImageFormat::Png => "png",
+        }
+    }
+}
+
+/// An image XObject or inline image.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Image {
+    /// The name of the XObject resource, `None` for inline images.
+    pub name: Option<String>,
+    pub reference: Option<IndirectReference>,
+    pub width: u32,
+    pub height: u32,
+    pub bits_per_component: u32,
+    /// `None` for stencil masks and for images that leave it to a JPX codestream.
+    pub color_space: Option<ColorSpace>,
+    /// The filters of the image data in decoding order, abbreviations expanded.
+    pub filters: Vec<String>,
+    /// Whether the image is a stencil mask painted with the fill colour.
+    pub image_mask: bool,
+    /// The `/Decode` array mapping samples to colour components.
+    pub decode: Option<Vec<f32>>,
+    /// Maps the unit square of the image to the page.
+    pub matrix: Matrix,
+    /// The raw (still encoded) image data.
+    pub data: Vec<u8>,
+    decode_parms: Vec<DecodeParms>,
+}
+
+impl Image {
+    /// Reads an image dictionary, either an XObject or inline image parameters.
+    pub fn from_draw(pdf: &PDF, image: &ImageDraw) -> Self {
+        let dictionary = image.dictionary;
+        // Inline images abbreviate their keys. `/F` is a file specification in
+        // stream dictionaries, so XObjects are not looked up by abbreviation.
+        let inline = image.reference.is_none();
+        let get_raw = |key: &str, abbreviation: &str| {
+            dictionary
+                .get(key)
+                .or_else(|| dictionary.get(abbreviation).filter(|_| inline))
+        };
+        let get = |key: &str, abbreviation: &str| {
+            get_raw(key, abbreviation).map(|value| pdf.resolve(value))
+        };
+        let integer = |key: &str, abbreviation: &str| {
+            get(key, abbreviation)
+                .and_then(Object::as_i32)
+                .and_then(|value| u32::try_from(value).ok())
+        };
+
+        let image_mask = get("ImageMask", "IM")
+            .and_then(Object::as_bool)
+            .unwrap_or(false);
+        let color_space = if image_mask {
+            None
+        } else {
+            get("ColorSpace", "CS")
+                .and_then(|space| ColorSpace::from_object(pdf, space, image.resources))
+        };
+        Self {
+            name: image.name.map(str::to_string),
+            reference: image.reference,
+            width: integer("Width", "W").unwrap_or(0),
+            height: integer("Height", "H").unwrap_or(0),
+            bits_per_component: match image_mask {
+                true => 1,
+                false => integer("BitsPerComponent", "BPC").unwrap_or(8),
+            },
+            color_space,
+            filters: crate::filter::filter_list(get_raw("Filter", "F"))
+                .into_iter()
+                .map(|filter| crate::filter::full_name(filter).to_string())
+                .collect(),
+            image_mask,
+            decode: get("Decode", "D").and_then(Object::as_array).map(|array| {
+                array
+                    .iter()
+                    .filter_map(|value| pdf.resolve(value).as_f32())
+                    .collect()
+            }),
+            matrix: image.matrix,
+            data: image.data.to_vec(),
+            decode_parms: crate::filter::decode_parms(dictionary),
+        }
+    }
+
+    /// The format `export` produces, depending on the image filter.
+    pub fn format(&self) -> ImageFormat {
+        match self.image_filter() {
+            Some("DCTDecode") => ImageFormat::Jpeg,
+            Some("JPXDecode") => ImageFormat::Jpeg2000,
+            _ => ImageFormat::Png,
+        }
+    }
+
+    /// The first filter producing an encoded image, if any.
+    fn image_filter(&self) -> Option<&str> {
+        self.filters
+            .iter()
+            .map(String::as_str)
+            .find(|filter| IMAGE_FILTERS.contains(filter))
+    }
+
+    /// Applies the filters up to the image codec, if any, so that the result is
+    /// either an encoded image (JPEG, JPEG 2000...) or the image samples.
+    pub fn decoded_data(&self) -> Result<Vec<u8>, ParseError> {
+        let mut data = self.data.clone();
+        for (index, filter) in self.filters.iter().enumerate() {
+            if IMAGE_FILTERS.contains(&filter.as_str()) {
+                break;
+            }
+            let parms = self.decode_parms.get(index).copied().unwrap_or_default();
+            data = crate::filter::decode_filter_with(filter, &parms, &data)?;
+        }
+        Ok(data)
+    }
+
+    /// Exports the image as a file: JPEG and JPEG 2000 data as is, anything else
+    /// decoded and converted to an 8-bit gray or RGB PNG.
+    pub fn export(&self) -> Result<(ImageFormat, Vec<u8>), ParseError> {
+        let format = self.format();
+        let data = self.decoded_data()?;
+        match (format, self.image_filter()) {
+            (ImageFormat::Png, Some(filter)) => {
+                Err(ParseError::UnsupportedPDFFilter(filter.to_string()))
+            }
+            (ImageFormat::Png, None) => Ok((format, self.to_png(&data)?)),
+            _ => Ok((format, data)),
+        }
+    }
+
+    fn to_png(&self, samples: &[u8]) -> Result<Vec<u8>, ParseError> {
+        let color_space = match &self.color_space {
+            Some(color_space) => color_space.clone(),
+            None if self.image_mask => ColorSpace::DeviceGray,
+            None => return Err(ParseError::InvalidPDFImage),
+        };
+        let bits = self.bits_per_component;
+        if !matches!(bits, 1 | 2 | 4 | 8 | 16) || self.width == 0 || self.height == 0 {
+            return Err(ParseError::InvalidPDFImage);
+        }
+        let components = color_space.components();
+        let max = ((1u32 << bits) - 1) as f32;
+        // Samples map to [0, 1] by default, palette indices to themselves.
+        let decode: Vec<(f32, f32)> = match &self.decode {
+            Some(decode) if decode.len() >= components * 2 => {
+                decode.chunks(2).map(|pair| (pair[0], pair[1])).collect()
+            }
+            _ if matches!(color_space, ColorSpace::Indexed { .. }) => vec![(0.0, max)],
+            _ => vec![(0.0, 1.0); components],
+        };
+
+        let row_length = (self.width as usize)
+            .checked_mul(components * bits as usize)
+            .ok_or(ParseError::InvalidPDFImage)?
+            .div_ceil(8);
+        let expected = row_length
+            .checked_mul(self.height as usize)
+            .ok_or(ParseError::InvalidPDFImage)?;
+        if components == 0 || samples.len() < expected {
+            return Err(ParseError::InvalidPDFImage);
+        }
+        let channels = if color_space.is_gray() { 1 } else { 3 };
+        let pixel_count = (self.width as usize)
+            .checked_mul(self.height as usize)
+            .and_then(|count| count.checked_mul(channels))
+            .ok_or(ParseError::InvalidPDFImage)?;
+        let mut pixels = Vec::with_capacity(pixel_count);
+        let mut values = vec![0.0; components];
+        for row in samples[..expected].chunks(row_length) {
+            for x in 0..self.width as usize {
+                for (component, value) in values.iter_mut().enumerate() {
+                    let sample = read_sample(row, x * components + component, bits) as f32;
+                    let (min, max_value) = decode[component];
+                    *value = min + sample * (max_value - min) / max;
+                }
+                color_space.convert(&values, &mut pixels)?;
+            }
+        }
+
+        let mut result = Vec::new();
+        let mut encoder = png::Encoder::new(&mut result, self.width, self.height);
+        encoder.set_color(match channels {
+            1 => png::ColorType::Grayscale,
+            _ => png::ColorType::Rgb,
+        });
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(&pixels))
+            .map_err(|_| ParseError::InvalidPDFImage)?;
+        Ok(result)
+    }
+}
+
+/// Reads the `index`th sample of `bits` bits from a row, most significant bits first.
+fn read_sample(row: &[u8], index: usize, bits: u32) -> u32 {
+    match bits {
+        8 => row[index] as u32,
+        16 => u16::from_be_bytes([row[index * 2], row[index * 2 + 1]]) as u32,
+        _ => {
+            let bit = index * bits as usize;
+            let shift = 8 - bits as usize - bit % 8;
+            (row[bit / 8] >> shift) as u32 & ((1 << bits) - 1)
+        }
+    }
+}
+
+/// Collects the images drawn by a content stream.
+struct ImageCollector<'p, 'a> {
+    pdf: &'p PDF<'a>,
+    images: Vec<Image>,
+    seen: HashSet<IndirectReference>,
+}
+
+impl Visitor for ImageCollector<'_, '_> {
+    fn image(&mut self, image: &ImageDraw, _state: &GraphicsState) {
+        // An XObject drawn several times is listed once, where it is first drawn.
+        if let Some(reference) = image.reference {
+            if !self.seen.insert(reference) {
+                return;
+            }
+        }
+        self.images.push(Image::from_draw(self.pdf, image));
+    }
+}
+
+impl<'p, 'a> Page<'p, 'a> {
+    /// Returns the images drawn by the page, including those of form XObjects
+    /// and inline images, in drawing order, followed by the image XObjects of
+    /// the page resources that are not drawn.
+    pub fn images(&self) -> Result<Vec<Image>, ParseError> {
+        let mut collector = ImageCollector {
+            pdf: self.pdf,
+            images: Vec::new(),
+            seen: HashSet::new(),
+        };
+        self.interpret(&mut collector)?;
+        let mut images = collector.images;
+        images.extend(self.resource_images().into_iter().filter(|image| {
+            image
+                .reference
+                .is_some_and(|r| !collector.seen.contains(&r))
+        }));
+        Ok(images)
+    }
+
+    /// Returns the image XObjects of the page `/Resources`, and of the form
+    /// XObjects found there, whether the content draws them or not. Each is
+    /// listed once, with the identity matrix.
+    pub fn resource_images(&self) -> Vec<Image> {
+        let mut images = Vec::new();
+        let mut seen = HashSet::new();
+        if let Some(resources) = self.resources() {
+            collect_resource_images(self.pdf, resources, &mut seen, &mut images);
+        }
+        images
+    }
+}
+
+/// Lists the image XObjects of `resources`, recursing into the resources of
+/// forms. Streams are indirect objects, so `seen` holds their references.
+fn collect_resource_images(
+    pdf: &PDF,
+    resources: &DictionaryObject,
+    seen: &mut HashSet<IndirectReference>,
+    images: &mut Vec<Image>,
+) {
+    let xobjects = match resources
+        .get("XObject")
+        .and_then(|xobjects| pdf.resolve(xobjects).as_dictionary())
+    {
+        Some(xobjects) => xobjects,
+        None => return,
+    };
+    let mut names: Vec<_> = xobjects.keys().collect();
+    names.sort();
+    for name in names {
+        let object = &xobjects[name];
+        let reference = match object.as_reference() {
+            Some(reference) if seen.insert(reference) => reference,
+            _ => continue,
+        };
+        let (dictionary, data) = match pdf.resolve(object).as_stream() {
+            Some(stream) => stream,
+            None => continue,
+        };
+        match dictionary.get("Subtype").and_then(Object::as_name) {
+            Some("Image") => images.push(Image::from_draw(
+                pdf,
+                &ImageDraw {
+                    name: Some(name.0),
+                    reference: Some(reference),
+                    dictionary,
+                    data,
+                    matrix: Matrix::IDENTITY,
+                    resources: Some(resources),
+                },
+            )),
+            Some("Form") => {
+                if let Some(form_resources) = dictionary
+                    .get("Resources")
+                    .and_then(|resources| pdf.resolve(resources).as_dictionary())
+                {
+                    collect_resource_images(pdf, form_resources, seen, images);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The images of a document, see `PDF::images`.
+#[derive(Debug, Default)]
+pub struct DocumentImages {
+    /// The images, with the index of their page.
+    pub images: Vec<(usize, Image)>,
+    /// The pages whose content could not be interpreted, with the error. Their
+    /// resource images are listed nonetheless.
+    pub errors: Vec<(usize, ParseError)>,
+}
+
+impl PDF<'_> {
+    /// Returns the images of every page, with the index of the page. A page
+    /// whose content cannot be interpreted does not stop the others.
+    pub fn images(&self) -> Result<DocumentImages, ParseError> {
+        let mut result = DocumentImages::default();
+        for (index, page) in self.pages()?.iter().enumerate() {
+            let images = page.images().unwrap_or_else(|error| {
+                result.errors.push((index, error));
+                page.resource_images()
+            });
+            result
+                .images
+                .extend(images.into_iter().map(|image| (index, image)));
+        }
+        Ok(result)
+    }
+}
@@ -0,0 +1,830 @@
+use std::collections::{HashMap, HashSet};
+use std::ops::Mul;
+use std::rc::Rc;
+
+use crate::content::{Content, Operation};
+use crate::document::Page;
+use crate::error::ParseError;
+use crate::font::Font;
+use crate::object::{DictionaryObject, IndirectReference, Object, PDF};
+
+/// Maximum nesting of form XObjects drawn from one another.
+const MAX_FORM_DEPTH: usize = 32;
+
+/// A transformation matrix `[a b c d e f]`, mapping `(x, y)` to
+/// `(a x + c y + e, b x + d y + f)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Matrix {
+    pub a: f32,
+    pub b: f32,
+    pub c: f32,
+    pub d: f32,
+    pub e: f32,
+    pub f: f32,
+}
+
+impl Matrix {
+    pub const IDENTITY: Matrix = Matrix::new(1.0, 0.0, 0.0, 1.0, 0.0, 0.0);
+
+    pub const fn new(a: f32, b: f32, c: f32, d: f32, e: f32, f: f32) -> Self {
+        Self { a, b, c, d, e, f }
+    }
+
+    pub const fn translate(x: f32, y: f32) -> Self {
+        Matrix::new(1.0, 0.0, 0.0, 1.0, x, y)
+    }
+
+    /// Reads a six-number array.
+    pub fn from_object(pdf: &PDF, object: &Object) -> Option<Self> {
+        let numbers = numbers(pdf, pdf.resolve(object).as_array()?)?;
+        Matrix::from_slice(&numbers)
+    }
+
+    pub fn from_slice(numbers: &[f32]) -> Option<Self> {
+        match *numbers {
+            [a, b, c, d, e, f] => Some(Matrix::new(a, b, c, d, e, f)),
+            _ => None,
+        }
+    }
+
+    pub fn transform(&self, x: f32, y: f32) -> (f32, f32) {
+        (
+            self.a * x + self.c * y + self.e,
+            self.b * x + self.d * y + self.f,
+        )
+    }
+}
+
+/// `m1 * m2` applies `m1` first, then `m2`, as in the PDF specification.
+impl Mul for Matrix {
+    type Output = Matrix;
+
+    fn mul(self, other: Matrix) -> Matrix {
+        Matrix::new(
+            self.a * other.a + self.b * other.c,
+            self.a * other.b + self.b * other.d,
+            self.c * other.a + self.d * other.c,
+            self.c * other.b + self.d * other.d,
+            self.e * other.a + self.f * other.c + other.e,
+            self.e * other.b + self.f * other.d + other.f,
+        )
+    }
+}
+
+/// An axis-aligned rectangle, with `(x0, y0)` the lower-left corner
+/// and `(x1, y1)` the upper-right corner.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rect {
+    pub x0: f32,
+    pub y0: f32,
+    pub x1: f32,
+    pub y1: f32,
+}
+
+impl Rect {
+    pub const fn new(x0: f32, y0: f32, x1: f32, y1: f32) -> Self {
+        Self { x0, y0, x1, y1 }
+    }
+
+    /// Reads a rectangle array such as `/Rect` or `/MediaBox`, given by any two
+    /// opposite corners.
+    pub fn from_object(pdf: &PDF, object: &Object) -> Option<Self> {
+        match numbers(pdf, pdf.resolve(object).as_array()?)?[..] {
+            [x0, y0, x1, y1] => Rect::bounding([(x0, y0), (x1, y1)]),
+            _ => None,
+        }
+    }
+
+    /// Returns the smallest rectangle containing all `points`, `None` if there are none.
+    pub fn bounding(points: impl IntoIterator<Item = (f32, f32)>) -> Option<Self> {
+        points.into_iter().fold(None, |rect, (x, y)| {
+            Some(match rect {
+                None => Rect::new(x, y, x, y),
+                Some(rect) => Rect::new(
+                    rect.x0.min(x),
+                    rect.y0.min(y),
+                    rect.x1.max(x),
+                    rect.y1.max(y),
+                ),
+            })
+        })
+    }
+
+    /// Returns the bounding box of this rectangle once transformed by `matrix`.
+    pub fn transform(&self, matrix: &Matrix) -> Rect {
+        let corners = [
+            (self.x0, self.y0),
+            (self.x1, self.y0),
+            (self.x1, self.y1),
+            (self.x0, self.y1),
+        ];
+        Rect::bounding(corners.map(|(x, y)| matrix.transform(x, y))).unwrap_or(*self)
+    }
+
+    /// Returns the smallest rectangle containing both rectangles.
+    pub fn union(&self, other: &Rect) -> Rect {
+        Rect::new(
+            self.x0.min(other.x0),
+            self.y0.min(other.y0),
+            self.x1.max(other.x1),
+            self.y1.max(other.y1),
+        )
+    }
+
+    pub fn width(&self) -> f32 {
+        self.x1 - self.x0
+    }
+
+    pub fn height(&self) -> f32 {
+        self.y1 - self.y0
+    }
+}
+
+/// The text state parameters, set by `Tc`, `Tw`, `Tz`, `TL`, `Tf`, `Tr` and `Ts`.
+#[derive(Debug, Clone)]
+pub struct TextState {
+    pub character_spacing: f32,
+    pub word_spacing: f32,
+    /// Horizontal scaling as a fraction, i.e. `Tz` divided by 100.
+    pub horizontal_scaling: f32,
+    pub leading: f32,
+    /// The name of the font in the `/Font` resources.
+    pub font_name: Option<String>,
+    pub font: Option<Rc<Font>>,
+    pub font_size: f32,
+    pub render_mode: i32,
+    pub rise: f32,
+}
+
+impl Default for TextState {
+    fn default() -> Self {
+        Self {
+            character_spacing: 0.0,
+            word_spacing: 0.0,
+            horizontal_scaling: 1.0,
+            leading: 0.0,
+            font_name: None,
+            font: None,
+            font_size: 0.0,
+            render_mode: 0,
+            rise: 0.0,
+        }
+    }
+}
+
+/// A colour space and the colour components set in it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Color {
+    /// The colour space family or the name of a `/ColorSpace` resource.
+    pub space: String,
+    pub components: Vec<f32>,
+}
+
+impl Default for Color {
+    fn default() -> Self {
+        Self {
+            space: "DeviceGray".to_string(),
+            components: vec![0.0],
+        }
+    }
+}
+
+/// The graphics state saved by `q` and restored by `Q`.
+#[derive(Debug, Clone)]
+pub struct GraphicsState {
+    /// The current transformation matrix, mapping user space to the page.
+    pub ctm: Matrix,
+    pub stroke_color: Color,
+    pub fill_color: Color,
+    pub line_width: f32,
+    pub line_cap: i32,
+    pub line_join: i32,
+    pub miter_limit: f32,
+    pub dash_pattern: (Vec<f32>, f32),
+    pub text_state: TextState,
+}
+
+impl Default for GraphicsState {
+    fn default() -> Self {
+        Self {
+            ctm: Matrix::IDENTITY,
+            stroke_color: Color::default(),
+            fill_color: Color::default(),
+            line_width: 1.0,
+            line_cap: 0,
+            line_join: 0,
+            miter_limit: 10.0,
+            dash_pattern: (Vec::new(), 0.0),
+            text_state: TextState::default(),
+        }
+    }
+}
+
+/// A path construction step, with points already transformed by the CTM.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PathSegment {
+    MoveTo(f32, f32),
+    LineTo(f32, f32),
+    CurveTo(f32, f32, f32, f32, f32, f32),
+    ClosePath,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FillRule {
+    NonZero,
+    EvenOdd,
+}
+
+/// How a path is painted, from `S`, `f`, `B`, `n` and friends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Paint {
+    pub stroke: bool,
+    pub fill: Option<FillRule>,
+    /// Set if a preceding `W` or `W*` intersects the clipping path with this path.
+    pub clip: Option<FillRule>,
+}
+
+/// A glyph shown by a text operator.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Glyph {
+    pub code: u32,
+    /// The text rendering matrix, mapping text space of unit font size to the page.
+    pub matrix: Matrix,
+    /// The horizontal displacement of the glyph in text space, before font size,
+    /// spacing and scaling are applied.
+    pub width: f32,
+}
+
+/// A string shown by `Tj`, `TJ`, `'` or `"`.
+#[derive(Debug, Clone)]
+pub struct TextShow<'t> {
+    pub text: &'t [u8],
+    pub glyphs: Vec<Glyph>,
+}
+
+/// An image painted by `Do` or an inline image.
+#[derive(Debug, Clone)]
+pub struct ImageDraw<'o> {
+    /// The name of the XObject resource, `None` for inline images.
+    pub name: Option<&'o str>,
+    pub reference: Option<IndirectReference>,
+    pub dictionary: &'o DictionaryObject<'o>,
+    /// The raw (still encoded) image data.
+    pub data: &'o [u8],
+    /// Maps the unit square of the image to the page.
+    pub matrix: Matrix,
+    /// The resources in effect, where named colour spaces are defined.
+    pub resources: Option<&'o DictionaryObject<'o>>,
+}
+
+/// Receives what a content stream draws. All methods default to doing nothing.
+pub trait Visitor {
+    fn path(&mut self, _path: &[PathSegment], _paint: Paint, _state: &GraphicsState) {}
+    fn text(&mut self, _text: &TextShow, _state: &GraphicsState) {}
+    fn image(&mut self, _image: &ImageDraw, _state: &GraphicsState) {}
+}
+
+/// Executes content stream operations, maintaining the graphics state.
+pub struct Interpreter<'p, 'a> {
+    pdf: &'p PDF<'a>,
+    state: GraphicsState,
+    stack: Vec<GraphicsState>,
+    text_matrix: Matrix,
+    text_line_matrix: Matrix,
+    path: Vec<PathSegment>,
+    current_point: (f32, f32),
+    clip: Option<FillRule>,
+    fonts: HashMap<IndirectReference, Rc<Font>>,
+    forms: HashSet<IndirectReference>,
+}
+
+impl<'p, 'a> Interpreter<'p, 'a> {
+    pub fn new(pdf: &'p PDF<'a>) -> Self {
+        Self {
+            pdf,
+            state: GraphicsState::default(),
+            stack: Vec::new(),
+            text_matrix: Matrix::IDENTITY,
+            text_line_matrix: Matrix::IDENTITY,
+            path: Vec::new(),
+            current_point: (0.0, 0.0),
+            clip: None,
+            fonts: HashMap::new(),
+            forms: HashSet::new(),
+        }
+    }
+
+    pub fn state(&self) -> &GraphicsState {
+        &self.state
+    }
+
+    /// Runs the content of a page, starting from the default graphics state.
+    pub fn run_page(
+        &mut self,
+        page: &Page<'p, 'a>,
+        visitor: &mut impl Visitor,
+    ) -> Result<(), ParseError> {
+        let data = page.contents()?;
+        let content = Content::parse(&data)?;
+        self.state = GraphicsState::default();
+        self.stack.clear();
+        self.run(&content, page.resources(), visitor)
+    }
+
+    /// Runs `content`, looking up named resources in `resources`.
+    pub fn run(
+        &mut self,
+        content: &Content,
+        resources: Option<&'p DictionaryObject<'a>>,
+        visitor: &mut impl Visitor,
+    ) -> Result<(), ParseError> {
+        for operation in &content.operations {
+            self.execute(operation, resources, visitor)?;
+        }
+        Ok(())
+    }
+
+    fn execute(
+        &mut self,
+        operation: &Operation,
+        resources: Option<&'p DictionaryObject<'a>>,
+        visitor: &mut impl Visitor,
+    ) -> Result<(), ParseError> {
+        let operands = &operation.operands;
+        match operation.operator {
+            // General graphics state
+            "q" => self.stack.push(self.state.clone()),
+            // An unbalanced `Q` is ignored, as viewers do.
+            "Q" => {
+                if let Some(state) = self.stack.pop() {
+                    self.state = state;
+                }
+            }
+            "cm" => self.state.ctm = matrix(operands)? * self.state.ctm,
+            "w" => self.state.line_width = number(operands, 0)?,
+            "J" => self.state.line_cap = number(operands, 0)? as i32,
+            "j" => self.state.line_join = number(operands, 0)? as i32,
+            "M" => self.state.miter_limit = number(operands, 0)?,
+            "d" => self.state.dash_pattern = dash_pattern(self.pdf, operands)?,
+            "gs" => self.set_extended_state(name(operands, 0)?, resources)?,
+
+            // Path construction
+            "m" => {
+                let (x, y) = self.point(operands, 0)?;
+                self.current_point = (x, y);
+                self.path.push(PathSegment::MoveTo(x, y));
+            }
+            "l" => {
+                let (x, y) = self.point(operands, 0)?;
+                self.current_point = (x, y);
+                self.path.push(PathSegment::LineTo(x, y));
+            }
+            "c" | "v" | "y" => {
+                let (x1, y1, x2, y2, x3, y3) = match operation.operator {
+                    "c" => {
+                        let (x1, y1) = self.point(operands, 0)?;
+                        let (x2, y2) = self.point(operands, 2)?;
+                        let (x3, y3) = self.point(operands, 4)?;
+                        (x1, y1, x2, y2, x3, y3)
+                    }
+                    "v" => {
+                        let (x1, y1) = self.current_point;
+                        let (x2, y2) = self.point(operands, 0)?;
+                        let (x3, y3) = self.point(operands, 2)?;
+                        (x1, y1, x2, y2, x3, y3)
+                    }
+                    _ => {
+                        let (x1, y1) = self.point(operands, 0)?;
+                        let (x3, y3) = self.point(operands, 2)?;
+                        (x1, y1, x3, y3, x3, y3)
+                    }
+                };
+                self.current_point = (x3, y3);
+                self.path.push(PathSegment::CurveTo(x1, y1, x2, y2, x3, y3));
+            }
+            "h" => self.path.push(PathSegment::ClosePath),
+            "re" => {
+                let x = number(operands, 0)?;
+                let y = number(operands, 1)?;
+                let width = number(operands, 2)?;
+                let height = number(operands, 3)?;
+                let ctm = self.state.ctm;
+                let (x0, y0) = ctm.transform(x, y);
+                let (x1, y1) = ctm.transform(x + width, y);
+                let (x2, y2) = ctm.transform(x + width, y + height);
+                let (x3, y3) = ctm.transform(x, y + height);
+                self.path.extend([
+                    PathSegment::MoveTo(x0, y0),
+                    PathSegment::LineTo(x1, y1),
+                    PathSegment::LineTo(x2, y2),
+                    PathSegment::LineTo(x3, y3),
+                    PathSegment::ClosePath,
+                ]);
+                self.current_point = (x0, y0);
+            }
+
+            // Path painting and clipping
+            "S" => self.paint(true, None, false, visitor),
+            "s" => self.paint(true, None, true, visitor),
+            "f" | "F" => self.paint(false, Some(FillRule::NonZero), false, visitor),
+            "f*" => self.paint(false, Some(FillRule::EvenOdd), false, visitor),
+            "B" => self.paint(true, Some(FillRule::NonZero), false, visitor),
+            "B*" => self.paint(true, Some(FillRule::EvenOdd), false, visitor),
+            "b" => self.paint(true, Some(FillRule::NonZero), true, visitor),
+            "b*" => self.paint(true, Some(FillRule::EvenOdd), true, visitor),
+            "n" => self.paint(false, None, false, visitor),
+            "W" => self.clip = Some(FillRule::NonZero),
+            "W*" => self.clip = Some(FillRule::EvenOdd),
+
+            // Colour
+            "CS" => self.state.stroke_color = initial_color(name(operands, 0)?),
+            "cs" => self.state.fill_color = initial_color(name(operands, 0)?),
+            "SC" | "SCN" => self.state.stroke_color.components = components(operands),
+            "sc" | "scn" => self.state.fill_color.components = components(operands),
+            "G" => self.state.stroke_color = device_color("DeviceGray", operands, 1)?,
+            "g" => self.state.fill_color = device_color("DeviceGray", operands, 1)?,
+            "RG" => self.state.stroke_color = device_color("DeviceRGB", operands, 3)?,
+            "rg" => self.state.fill_color = device_color("DeviceRGB", operands, 3)?,
+            "K" => self.state.stroke_color = device_color("DeviceCMYK", operands, 4)?,
+            "k" => self.state.fill_color = device_color("DeviceCMYK", operands, 4)?,
+
+            // Text objects and state
+            "BT" => {
+                self.text_matrix = Matrix::IDENTITY;
+                self.text_line_matrix = Matrix::IDENTITY;
+            }
+            "ET" => {}
+            "Tc" => self.state.text_state.character_spacing = number(operands, 0)?,
+            "Tw" => self.state.text_state.word_spacing = number(operands, 0)?,
+            "Tz" => self.state.text_state.horizontal_scaling = number(operands, 0)? / 100.0,
+            "TL" => self.state.text_state.leading = number(operands, 0)?,
+            "Tf" => {
+                let font_name = name(operands, 0)?;
+                self.state.text_state.font = Some(self.load_font(font_name, resources));
+                self.state.text_state.font_name = Some(font_name.to_string());
+                self.state.text_state.font_size = number(operands, 1)?;
+            }
+            "Tr" => self.state.text_state.render_mode = number(operands, 0)? as i32,
+            "Ts" => self.state.text_state.rise = number(operands, 0)?,
+
+            // Text positioning
+            "Td" => self.move_text(number(operands, 0)?, number(operands, 1)?),
+            "TD" => {
+                let ty = number(operands, 1)?;
+                self.state.text_state.leading = -ty;
+                self.move_text(number(operands, 0)?, ty);
+            }
+            "Tm" => {
+                self.text_matrix = matrix(operands)?;
+                self.text_line_matrix = self.text_matrix;
+            }
+            "T*" => self.move_text(0.0, -self.state.text_state.leading),
+
+            // Text showing
+            "Tj" => self.show_text(&string(operands, 0)?, visitor),
+            "'" => {
+                self.move_text(0.0, -self.state.text_state.leading);
+                self.show_text(&string(operands, 0)?, visitor);
+            }
+            "\"" => {
+                self.state.text_state.word_spacing = number(operands, 0)?;
+                self.state.text_state.character_spacing = number(operands, 1)?;
+                self.move_text(0.0, -self.state.text_state.leading);
+                self.show_text(&string(operands, 2)?, visitor);
+            }
+            "TJ" => {
+                let elements = operands
+                    .first()
+                    .and_then(Object::as_array)
+                    .ok_or(ParseError::InvalidPDFContentStream)?;
+                for element in elements {
+                    match element.as_f32() {
+                        Some(adjustment) => {
+                            let text_state = &self.state.text_state;
+                            let tx = -adjustment / 1000.0
+                                * text_state.font_size
+                                * text_state.horizontal_scaling;
+                            self.text_matrix = Matrix::translate(tx, 0.0) * self.text_matrix;
+                        }
+                        None => {
+                            let text = element
+                                .string_bytes()
+                                .ok_or(ParseError::InvalidPDFContentStream)?;
+                            self.show_text(&text, visitor);
+                        }
+                    }
+                }
+            }
+
+            // XObjects and inline images
+            "Do" => self.draw_xobject(name(operands, 0)?, resources, visitor)?,
+            "BI" => {
+                if let Some((dictionary, data)) = operands.first().and_then(Object::as_stream) {
+                    let image = ImageDraw {
+                        name: None,
+                        reference: None,
+                        dictionary,
+                        data,
+                        matrix: self.state.ctm,
+                        resources,
+                    };
+                    visitor.image(&image, &self.state);
+                }
+            }
+
+            // Marked content, shading, Type 3 glyph metrics and compatibility
+            // sections do not affect the graphics state tracked here.
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn point(&self, operands: &[Object], index: usize) -> Result<(f32, f32), ParseError> {
+        let x = number(operands, index)?;
+        let y = number(operands, index + 1)?;
+        Ok(self.state.ctm.transform(x, y))
+    }
+
+    fn paint(
+        &mut self,
+        stroke: bool,
+        fill: Option<FillRule>,
+        close: bool,
+        visitor: &mut impl Visitor,
+    ) {
+        if close {
+            self.path.push(PathSegment::ClosePath);
+        }
+        let paint = Paint {
+            stroke,
+            fill,
+            clip: self.clip.take(),
+        };
+        let path = std::mem::take(&mut self.path);
+        if !path.is_empty() {
+            visitor.path(&path, paint, &self.state);
+        }
+    }
+
+    fn move_text(&mut self, tx: f32, ty: f32) {
+        self.text_line_matrix = Matrix::translate(tx, ty) * self.text_line_matrix;
+        self.text_matrix = self.text_line_matrix;
+    }
+
+    fn show_text(&mut self, text: &[u8], visitor: &mut impl Visitor) {
+        let text_state = &self.state.text_state;
+        let font = text_state.font.clone().unwrap_or_default();
+        let parameters = Matrix::new(
+            text_state.font_size * text_state.horizontal_scaling,
+            0.0,
+            0.0,
+            text_state.font_size,
+            0.0,
+            text_state.rise,
+        );
+
+        let mut glyphs = Vec::new();
+        for code in font.codes(text) {
+            let width = font.width(code) * font.font_matrix.a;
+            glyphs.push(Glyph {
+                code,
+                matrix: parameters * self.text_matrix * self.state.ctm,
+                width,
+            });
+
+            let mut spacing = text_state.character_spacing;
+            if font.is_space(code) {
+                spacing += text_state.word_spacing;
+            }
+            let tx = (width * text_state.font_size + spacing) * text_state.horizontal_scaling;
+            self.text_matrix = Matrix::translate(tx, 0.0) * self.text_matrix;
+        }
+
+        visitor.text(&TextShow { text, glyphs }, &self.state);
+    }
+
+    fn load_font(&mut self, name: &str, resources: Option<&'p DictionaryObject<'a>>) -> Rc<Font> {
+        let object = match resources
+            .and_then(|resources| resources.get("Font"))
+            .and_then(|fonts| self.pdf.resolve(fonts).get(name))
+        {
+            Some(object) => object,
+            None => return Rc::new(Font::default()),
+        };
+        match object.as_reference() {
+            Some(reference) => self
+                .fonts
+                .entry(reference)
+                .or_insert_with(|| Rc::new(Font::from_object(self.pdf, object)))
+                .clone(),
+            None => Rc::new(Font::from_object(self.pdf, object)),
+        }
+    }
+
+    fn set_extended_state(
+        &mut self,
+        name: &str,
+        resources: Option<&'p DictionaryObject<'a>>,
+    ) -> Result<(), ParseError> {
+        let pdf = self.pdf;
+        let parameters = match resources
+            .and_then(|resources| resources.get("ExtGState"))
+            .and_then(|states| pdf.get(states, name))
+            .and_then(Object::as_dictionary)
+        {
+            Some(parameters) => parameters,
+            None => return Ok(()),
+        };
+        for (key, value) in parameters {
+            let value = pdf.resolve(value);
+            match key.0 {
+                "LW" => self.state.line_width = value.as_f32().unwrap_or(self.state.line_width),
+                "LC" => self.state.line_cap = value.as_i32().unwrap_or(self.state.line_cap),
+                "LJ" => self.state.line_join = value.as_i32().unwrap_or(self.state.line_join),
+                "ML" => self.state.miter_limit = value.as_f32().unwrap_or(self.state.miter_limit),
+                "D" => {
+                    if let Some(operands) = value.as_array() {
+                        self.state.dash_pattern = dash_pattern(pdf, operands)?;
+                    }
+                }
+                "Font" => {
+                    if let Some([font, size]) = value.as_array() {
+                        self.state.text_state.font = Some(Rc::new(Font::from_object(pdf, font)));
+                        self.state.text_state.font_name = None;
+                        self.state.text_state.font_size =
+                            pdf.resolve(size).as_f32().unwrap_or_default();
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn draw_xobject(
+        &mut self,
+        name: &str,
+        resources: Option<&'p DictionaryObject<'a>>,
+        visitor: &mut impl Visitor,
+    ) -> Result<(), ParseError> {
+        let pdf = self.pdf;
+        let object = match resources
+            .and_then(|resources| resources.get("XObject"))
+            .and_then(|xobjects| pdf.resolve(xobjects).get(name))
+        {
+            Some(object) => object,
+            None => return Ok(()),
+        };
+        let reference = object.as_reference();
+        let (dictionary, data) = match pdf.resolve(object).as_stream() {
+            Some(stream) => stream,
+            None => return Ok(()),
+        };
+
+        match dictionary.get("Subtype").and_then(Object::as_name) {
+            Some("Image") => {
+                let image = ImageDraw {
+                    name: Some(name),
+                    reference,
+                    dictionary,
+                    data,
+                    matrix: self.state.ctm,
+                    resources,
+                };
+                visitor.image(&image, &self.state);
+            }
+            Some("Form") => {
+                // A form drawing itself, directly or not, is skipped rather than recursed into.
+                if let Some(reference) = reference {
+                    if self.forms.len() >= MAX_FORM_DEPTH || !self.forms.insert(reference) {
+                        return Ok(());
+                    }
+                }
+                let result = self.run_form(dictionary, data, resources, visitor);
+                if let Some(reference) = reference {
+                    self.forms.remove(&reference);
+                }
+                result?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn run_form(
+        &mut self,
+        dictionary: &'p DictionaryObject<'a>,
+        data: &[u8],
+        resources: Option<&'p DictionaryObject<'a>>,
+        visitor: &mut impl Visitor,
+    ) -> Result<(), ParseError> {
+        let data = crate::filter::decode(dictionary, data)?;
+        let content = Content::parse(&data)?;
+        let form_resources = dictionary
+            .get("Resources")
+            .and_then(|resources| self.pdf.resolve(resources).as_dictionary())
+            .or(resources);
+
+        // The form runs as if surrounded by `q` and `Q`, with its own text and path state.
+        let saved = (
+            self.state.clone(),
+            self.stack.len(),
+            self.text_matrix,
+            self.text_line_matrix,
+            std::mem::take(&mut self.path),
+        );
+        if let Some(form_matrix) = dictionary
+            .get("Matrix")
+            .and_then(|m| Matrix::from_object(self.pdf, m))
+        {
+            self.state.ctm = form_matrix * self.state.ctm;
+        }
+        let result = self.run(&content, form_resources, visitor);
+        let (state, depth, text_matrix, text_line_matrix, path) = saved;
+        self.state = state;
+        self.stack.truncate(depth);
+        self.text_matrix = text_matrix;
+        self.text_line_matrix = text_line_matrix;
+        self.path = path;
+        result
+    }
+}
+
+impl<'p, 'a> Page<'p, 'a> {
+    /// Runs the page content through an [`Interpreter`], reporting to `visitor`.
+    pub fn interpret(&self, visitor: &mut impl Visitor) -> Result<(), ParseError> {
+        Interpreter::new(self.pdf).run_page(self, visitor)
+    }
+}
+
+fn number(operands: &[Object], index: usize) -> Result<f32, ParseError> {
+    operands
+        .get(index)
+        .and_then(Object::as_f32)
+        .ok_or(ParseError::InvalidPDFContentStream)
+}
+
+fn name<'o>(operands: &[Object<'o>], index: usize) -> Result<&'o str, ParseError> {
+    operands
+        .get(index)
+        .and_then(Object::as_name)
+        .ok_or(ParseError::InvalidPDFContentStream)
+}
+
+fn string(operands: &[Object], index: usize) -> Result<Vec<u8>, ParseError> {
+    operands
+        .get(index)
+        .and_then(Object::string_bytes)
+        .ok_or(ParseError::InvalidPDFContentStream)
+}
+
+fn matrix(operands: &[Object]) -> Result<Matrix, ParseError> {
+    let numbers: Vec<f32> = operands.iter().filter_map(Object::as_f32).collect();
+    Matrix::from_slice(&numbers).ok_or(ParseError::InvalidPDFContentStream)
+}
+
+fn numbers(pdf: &PDF, objects: &[Object]) -> Option<Vec<f32>> {
+    objects
+        .iter()
+        .map(|object| pdf.resolve(object).as_f32())
+        .collect()
+}
+
+fn dash_pattern(pdf: &PDF, operands: &[Object]) -> Result<(Vec<f32>, f32), ParseError> {
+    let array = operands
+        .first()
+        .and_then(Object::as_array)
+        .and_then(|array| numbers(pdf, array))
+        .ok_or(ParseError::InvalidPDFContentStream)?;
+    let phase = number(operands, 1)?;
+    Ok((array, phase))
+}
+
+// Numeric components only; the pattern name of `SCN` and `scn` is dropped.
+fn components(operands: &[Object]) -> Vec<f32> {
+    operands.iter().filter_map(Object::as_f32).collect()
+}
+
+fn initial_color(space: &str) -> Color {
+    let components = match space {
+        "DeviceRGB" | "CalRGB" | "Lab" => vec![0.0; 3],
+        "DeviceCMYK" => vec![0.0, 0.0, 0.0, 1.0],
+        "Pattern" => Vec::new(),
+        _ => vec![0.0],
+    };
+    Color {
+        space: space.to_string(),
+        components,
+    }
+}
+
+fn device_color(space: &str, operands: &[Object], count: usize) -> Result<Color, ParseError> {
+    let components = components(operands);
+    if components.len() != count {
+        return Err(ParseError::InvalidPDFContentStream);
+    }
+    Ok(Color {
+        space: space.to_string(),
+        components,
+    })
+}
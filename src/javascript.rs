@@ -0,0 +1,37 @@
+use crate::error::ParseError;
+use crate::object::{IndirectReference, PDF};
+
+/// A JavaScript snippet found in the document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JavaScript {
+    /// How the action is reached from the trailer, e.g. `/Root/Pages[0]/Annots[1]/AA/U`
+    /// or `/Root/Names/JavaScript(init)/Next[0]`.
+    pub path: String,
+    /// The action holding the script, if it is an indirect object.
+    pub reference: Option<IndirectReference>,
+    /// The decoded source. Streams that cannot be decoded are read as raw text.
+    pub source: String,
+}
+
+impl<'a> PDF<'a> {
+    /// Collects the JavaScript of the document: the catalog `/OpenAction` and `/AA`,
+    /// the `/Names` `/JavaScript` tree, page and annotation actions, form fields,
+    /// outline items, and the actions chained to them with `/Next`.
+    pub fn javascript(&self) -> Result<Vec<JavaScript>, ParseError> {
+        let graph = self.action_graph()?;
+        let mut scripts = Vec::new();
+        for trigger in &graph.triggers {
+            graph.walk(trigger.action, trigger.path.clone(), &mut |index, path| {
+                let node = &graph.actions[index];
+                if let Some(source) = node.action.javascript() {
+                    scripts.push(JavaScript {
+                        path: path.to_string(),
+                        reference: node.reference,
+                        source: source.to_string(),
+                    });
+                }
+            });
+        }
+        Ok(scripts)
+    }
+}
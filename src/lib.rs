@@ -2,4 +2,31 @@ pub mod error;
 pub mod parser;
 pub mod object;
 pub mod utils;
-
+pub mod content;
+pub mod document;
+pub mod filter;
+pub mod font;
+pub mod interpreter;
+pub mod text;
+pub mod cmap;
+pub mod encoding;
+pub mod glyph_list;
+pub mod image;
+pub mod name_tree;
+pub mod javascript;
+pub mod action;
+pub mod form;
+pub mod writer;
+pub mod fill;
+pub mod xfa;
+pub mod annotation;
+pub mod link;
+pub mod embedded_file;
+pub mod outline;
+pub mod destination;
+pub mod metadata;
+pub mod page_label;
+pub mod loader;
+#[cfg(feature = "rayon")]
+pub mod parallel;
+pub mod options;
@@ -0,0 +1,163 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::action::{Action, TriggerSource};
+use crate::error::ParseError;
+use crate::object::{IndirectReference, PDF};
+
+/// URL schemes recognized in scripts and page text.
+const SCHEMES: [&str; 6] = [
+    "http://", "https://", "ftp://", "file://", "mailto:", "www.",
+];
+
+/// Where a URL was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LinkKind {
+    /// The `/URI` of a URI action.
+    Uri,
+    /// The file of a `/Launch` action.
+    Launch,
+    /// The file of a `/GoToR` action.
+    RemoteGoTo,
+    /// The `/F` of a `/SubmitForm` action.
+    SubmitForm,
+    /// A URL written in the source of a JavaScript action.
+    JavaScript,
+    /// A URL written in the text of a page.
+    Text,
+}
+
+/// A URL or file reachable from the document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Link {
+    pub url: String,
+    pub kind: LinkKind,
+    /// The index of the page the URL belongs to, `None` for document-level
+    /// actions, outline items and fields without widgets on a page.
+    pub page: Option<usize>,
+    /// The object the URL came from: the action if it is an indirect object,
+    /// otherwise the object holding it, such as an annotation or the page.
+    pub reference: Option<IndirectReference>,
+    /// How the action is reached from the trailer, as in `ActionGraph`, or
+    /// `/Root/Pages[i]/Contents` for page text.
+    pub path: String,
+}
+
+/// Finds the URLs written in `text`: words starting with a known scheme or
+/// `www.`, without trailing punctuation.
+pub fn find_urls(text: &str) -> Vec<String> {
+    let mut urls = Vec::new();
+    // Scans the text once, checking each character boundary for a scheme.
+    let mut next = 0;
+    for (start, _) in text.char_indices() {
+        if start < next {
+            continue;
+        }
+        let scheme = match SCHEMES
+            .iter()
+            .find(|scheme| starts_with_ignore_case(&text[start..], scheme))
+        {
+            Some(scheme) => scheme,
+            None => continue,
+        };
+        let candidate = &text[start..];
+        let end = candidate
+            .find(|c: char| c.is_whitespace() || "\"'<>()[]{}\\`".contains(c))
+            .unwrap_or(candidate.len());
+        let url = candidate[..end].trim_end_matches(['.', ',', ';', ':', '!', '?']);
+        if url.len() > scheme.len() {
+            urls.push(url.to_string());
+        }
+        next = start + end.max(scheme.len());
+    }
+    urls
+}
+
+/// Checks whether `text` starts with an ASCII pattern, regardless of case.
+fn starts_with_ignore_case(text: &str, pattern: &str) -> bool {
+    text.as_bytes()
+        .get(..pattern.len())
+        .is_some_and(|bytes| bytes.eq_ignore_ascii_case(pattern.as_bytes()))
+}
+
+impl<'a> PDF<'a> {
+    /// Collects the URLs of the document: the targets of URI, Launch, GoToR and
+    /// SubmitForm actions wherever they are attached, URLs in JavaScript actions,
+    /// and URLs in the text of each page. A URL is listed once per object it is
+    /// found in, with the first path reaching it. Pages whose text cannot be
+    /// extracted are skipped, and a broken page tree only hides the page and
+    /// annotation links, so that the other action links are still returned.
+    pub fn links(&self) -> Result<Vec<Link>, ParseError> {
+        let graph = self.action_graph()?;
+        // Fields are found through their widgets, merged or not.
+        let mut annotation_pages = HashMap::new();
+        for (page, annotation) in self.annotations().unwrap_or_default() {
+            if let Some(reference) = annotation.reference {
+                annotation_pages.entry(reference).or_insert(page);
+            }
+        }
+
+        let mut links = Vec::new();
+        let mut seen = HashSet::new();
+        // Widgets merged with their field are reached twice, once as an annotation
+        // and once as a field, so the path is left out of the comparison.
+        let mut push = |link: Link| {
+            if seen.insert((link.url.clone(), link.kind, link.page, link.reference)) {
+                links.push(link);
+            }
+        };
+        for trigger in &graph.triggers {
+            let page = match trigger.source {
+                TriggerSource::Page(page) => Some(page),
+                TriggerSource::Annotation { page, .. } => Some(page),
+                _ => trigger
+                    .owner
+                    .and_then(|owner| annotation_pages.get(&owner).copied()),
+            };
+            graph.walk(trigger.action, trigger.path.clone(), &mut |index, path| {
+                let node = &graph.actions[index];
+                let (kind, urls) = match &node.action {
+                    Action::URI { uri, .. } => (LinkKind::Uri, vec![uri.clone()]),
+                    Action::Launch { file, .. } => {
+                        (LinkKind::Launch, file.iter().cloned().collect())
+                    }
+                    Action::GoToR { file, .. } => {
+                        (LinkKind::RemoteGoTo, file.iter().cloned().collect())
+                    }
+                    Action::SubmitForm { url, .. } => {
+                        (LinkKind::SubmitForm, url.iter().cloned().collect())
+                    }
+                    action => match action.javascript() {
+                        Some(source) => (LinkKind::JavaScript, find_urls(source)),
+                        None => return,
+                    },
+                };
+                for url in urls {
+                    push(Link {
+                        url,
+                        kind,
+                        page,
+                        reference: node.reference.or(trigger.owner),
+                        path: path.to_string(),
+                    });
+                }
+            });
+        }
+
+        for (index, page) in self.pages().unwrap_or_default().iter().enumerate() {
+            let text = match page.extract_text() {
+                Ok(text) => text,
+                Err(_) => continue,
+            };
+            for url in find_urls(&text) {
+                push(Link {
+                    url,
+                    kind: LinkKind::Text,
+                    page: Some(index),
+                    reference: Some(page.reference),
+                    path: format!("/Root/Pages[{}]/Contents", index),
+                });
+            }
+        }
+        Ok(links)
+    }
+}
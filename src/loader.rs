@@ -0,0 +1,286 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::io::{Read, Seek, SeekFrom};
+
+use nom::multi::many0;
+
+use crate::error::ParseError;
+use crate::object::{
+    CrossReferenceEntry, CrossReferenceTable, Header, IndirectReference, Object, Trailer, PDF,
+};
+
+/// The number of bytes read at the start of the file for the header, and at its
+/// end for `startxref`.
+const PROBE_SIZE: u64 = 1024;
+/// The initial number of bytes read for a cross-reference section. It doubles
+/// until the section and its trailer fit.
+const XREF_CHUNK_SIZE: usize = 64 * 1024;
+/// The default for the most bytes read for a cross-reference section and its
+/// trailer, see `Loader::with_max_section_size`.
+pub const MAX_SECTION_SIZE: usize = 64 * 1024 * 1024;
+/// The default for the most bytes read for an object, see `Loader::with_limits`.
+pub const MAX_OBJECT_SIZE: usize = 256 * 1024 * 1024;
+
+/// Reads a document lazily from a seekable source: the header, the cross-reference
+/// sections and the last trailer are read up front, objects only when loaded.
+/// Memory is bounded by the cross-reference table and the objects kept by the
+/// caller. A memory-mapped file can be read through `std::io::Cursor`.
+#[derive(Debug)]
+pub struct Loader<R> {
+    source: R,
+    length: u64,
+    header: Header,
+    /// The latest entry of each object in use.
+    entries: BTreeMap<IndirectReference, u64>,
+    /// The offsets where objects or cross-reference sections start, and the end
+    /// of the file. An object ends at the next one.
+    boundaries: BTreeSet<u64>,
+    /// The bytes of the last trailer, from `trailer` to `%%EOF`.
+    trailer: Vec<u8>,
+    max_section_size: usize,
+    max_object_size: usize,
+}
+
+/// The bytes of an indirect object, read by a `Loader`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjectBuffer {
+    pub reference: IndirectReference,
+    pub offset: u64,
+    pub bytes: Vec<u8>,
+}
+
+impl ObjectBuffer {
+    /// Parses the object, checking that it is the one expected.
+    pub fn object(&self) -> Result<Object<'_>, ParseError> {
+        let invalid =
+            ParseError::InvalidPDFObjectReference(self.reference.id, self.reference.generation);
+        match Object::parse_indirect_object(&self.bytes) {
+            Ok((
+                _,
+                Object::IndirectObject {
+                    id,
+                    generation,
+                    dictionary,
+                },
+            )) if id == self.reference.id && generation == self.reference.generation => {
+                Ok(*dictionary)
+            }
+            _ => Err(invalid),
+        }
+    }
+}
+
+impl<R: Read + Seek> Loader<R> {
+    /// Reads the header, then the cross-reference sections from the last one
+    /// back through `/Prev`.
+    pub fn new(source: R) -> Result<Self, ParseError> {
+        Loader::with_max_section_size(source, MAX_SECTION_SIZE)
+    }
+
+    /// As `new`, reading at most `max_section_size` bytes for a cross-reference
+    /// section and its trailer, so that a wrong offset into a large file fails
+    /// rather than reading the rest of it.
+    pub fn with_max_section_size(source: R, max_section_size: usize) -> Result<Self, ParseError> {
+        Loader::with_limits(source, max_section_size, MAX_OBJECT_SIZE)
+    }
+
+    /// As `with_max_section_size`, also failing to load an object spanning more
+    /// than `max_object_size` bytes.
+    pub fn with_limits(
+        mut source: R,
+        max_section_size: usize,
+        max_object_size: usize,
+    ) -> Result<Self, ParseError> {
+        let length = source.seek(SeekFrom::End(0))?;
+        let start = read_at(&mut source, 0, PROBE_SIZE.min(length) as usize)?;
+        let (_, header) = Header::parse(&start).map_err(|_| ParseError::InvalidPDFHeader)?;
+
+        let tail_start = length.saturating_sub(PROBE_SIZE);
+        let tail = read_at(&mut source, tail_start, (length - tail_start) as usize)?;
+        let position = rfind(&tail, b"startxref").ok_or(ParseError::InvalidPDFTrailer)?;
+        let digits: String = tail[position + b"startxref".len()..]
+            .iter()
+            .map(|&byte| byte as char)
+            .skip_while(|c| c.is_ascii_whitespace())
+            .take_while(char::is_ascii_digit)
+            .collect();
+        let startxref: u64 = digits.parse().map_err(|_| ParseError::InvalidPDFTrailer)?;
+
+        let mut loader = Loader {
+            source,
+            length,
+            header,
+            entries: BTreeMap::new(),
+            boundaries: BTreeSet::from([length]),
+            trailer: Vec::new(),
+            max_section_size,
+            max_object_size,
+        };
+        let mut next = Some(startxref);
+        let mut visited = HashSet::new();
+        while let Some(offset) = next {
+            if !visited.insert(offset) {
+                break;
+            }
+            next = loader.read_section(offset)?;
+        }
+        Ok(loader)
+    }
+
+    /// Reads the cross-reference section at `offset`, keeping entries already read
+    /// from later sections, and returns the `/Prev` offset of its trailer.
+    fn read_section(&mut self, offset: u64) -> Result<Option<u64>, ParseError> {
+        if offset >= self.length {
+            return Err(ParseError::InvalidPDFXrefTable);
+        }
+        self.boundaries.insert(offset);
+        let available = ((self.length - offset) as usize).min(self.max_section_size);
+        let mut size = XREF_CHUNK_SIZE;
+        loop {
+            let bytes = read_at(&mut self.source, offset, size.min(available))?;
+            let parsed = many0(CrossReferenceTable::parse_section)(bytes.as_slice())
+                .ok()
+                .filter(|(_, sections)| !sections.is_empty())
+                .and_then(|(rest, sections)| {
+                    let (after, trailer) = Trailer::parse(rest).ok()?;
+                    Some((
+                        sections,
+                        trailer,
+                        rest.len() - after.len(),
+                        bytes.len() - rest.len(),
+                    ))
+                });
+            let (sections, trailer, trailer_length, trailer_start) = match parsed {
+                Some(parsed) => parsed,
+                None if size < available => {
+                    size *= 2;
+                    continue;
+                }
+                None => return Err(ParseError::InvalidPDFXrefTable),
+            };
+
+            for table in sections.into_iter().flatten() {
+                for (index, entry) in table.entries.iter().enumerate() {
+                    self.add_entry(table.id + index as u32, entry);
+                }
+            }
+            let prev = trailer
+                .dictionary
+                .get("Prev")
+                .and_then(Object::as_i32)
+                .and_then(|prev| u64::try_from(prev).ok());
+            if self.trailer.is_empty() {
+                self.trailer = bytes[trailer_start..trailer_start + trailer_length].to_vec();
+            }
+            return Ok(prev);
+        }
+    }
+
+    fn add_entry(&mut self, id: u32, entry: &CrossReferenceEntry) {
+        if entry.free {
+            return;
+        }
+        let reference = IndirectReference {
+            id,
+            generation: entry.generation,
+        };
+        // Later sections are read first and take precedence.
+        if let std::collections::btree_map::Entry::Vacant(vacant) = self.entries.entry(reference) {
+            vacant.insert(entry.offset as u64);
+            self.boundaries.insert(entry.offset as u64);
+        }
+    }
+
+    /// Reads the bytes of an object, from its offset to the start of the next
+    /// object or cross-reference section. Fails without reading if there are more
+    /// than the maximum object size.
+    pub fn load(&mut self, reference: IndirectReference) -> Result<ObjectBuffer, ParseError> {
+        let missing = ParseError::InvalidPDFObjectReference(reference.id, reference.generation);
+        let offset = *self.entries.get(&reference).ok_or(missing.clone())?;
+        let end = self
+            .boundaries
+            .range(offset + 1..)
+            .next()
+            .copied()
+            .ok_or(missing)?;
+        if end - offset > self.max_object_size as u64 {
+            return Err(ParseError::PDFLimitExceeded(format!(
+                "object {} {} spans more than {} bytes",
+                reference.id, reference.generation, self.max_object_size
+            )));
+        }
+        let bytes = read_at(&mut self.source, offset, (end - offset) as usize)?;
+        Ok(ObjectBuffer {
+            reference,
+            offset,
+            bytes,
+        })
+    }
+}
+
+impl<R> Loader<R> {
+    pub fn header(&self) -> Header {
+        self.header
+    }
+
+    /// The size of the source in bytes.
+    pub fn len(&self) -> u64 {
+        self.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Parses the last trailer.
+    pub fn trailer(&self) -> Result<Trailer<'_>, ParseError> {
+        Trailer::parse(&self.trailer)
+            .map(|(_, trailer)| trailer)
+            .map_err(|_| ParseError::InvalidPDFTrailer)
+    }
+
+    /// The objects in use, sorted by reference.
+    pub fn references(&self) -> impl Iterator<Item = IndirectReference> + '_ {
+        self.entries.keys().copied()
+    }
+
+    /// Builds a document from loaded objects and the last trailer, so that the
+    /// `PDF` methods can run on the objects they need. References to objects not
+    /// given resolve to `null`.
+    pub fn document<'b>(&'b self, objects: &'b [ObjectBuffer]) -> Result<PDF<'b>, ParseError> {
+        let body = objects
+            .iter()
+            .map(|buffer| {
+                let reference = buffer.reference;
+                buffer.object().map(|object| Object::IndirectObject {
+                    id: reference.id,
+                    generation: reference.generation,
+                    dictionary: Box::new(object),
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(PDF {
+            header: self.header,
+            body,
+            cross_reference_tables: Vec::new(),
+            trailer: self.trailer()?,
+            index: Default::default(),
+        })
+    }
+}
+
+fn read_at<R: Read + Seek>(
+    source: &mut R,
+    offset: u64,
+    length: usize,
+) -> Result<Vec<u8>, ParseError> {
+    source.seek(SeekFrom::Start(offset))?;
+    let mut bytes = vec![0; length];
+    source.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn rfind(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .rposition(|window| window == needle)
+}
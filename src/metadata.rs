@@ -0,0 +1,463 @@
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::name::ResolveResult;
+use quick_xml::NsReader;
+
+use crate::error::ParseError;
+use crate::object::{Object, PDF};
+
+const DC: &[u8] = b"http://purl.org/dc/elements/1.1/";
+const XMP: &[u8] = b"http://ns.adobe.com/xap/1.0/";
+const ADOBE_PDF: &[u8] = b"http://ns.adobe.com/pdf/1.3/";
+const RDF: &[u8] = b"http://www.w3.org/1999/02/22-rdf-syntax-ns#";
+
+/// A date and time, as written in PDF date strings and XMP.
+/// Fields missing from the string take their earliest value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Date {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+    /// The offset from UTC in minutes, `None` if unknown.
+    pub offset: Option<i16>,
+}
+
+impl Date {
+    /// Parses a PDF date string, `D:YYYYMMDDHHmmSSOHH'mm'` where everything after
+    /// the year is optional and `O` is `+`, `-` or `Z`. The `D:` prefix and the
+    /// final apostrophe are often left out, and are accepted without.
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        let text = text.strip_prefix("D:").unwrap_or(text);
+        let digits = text
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(text.len());
+        let (fields, zone) = text.split_at(digits);
+        if fields.len() < 4 || fields.len() % 2 != 0 || fields.len() > 14 {
+            return None;
+        }
+        let field = |start: usize, default: u8| match fields.get(start..start + 2) {
+            Some(field) => field.parse().ok(),
+            None => Some(default),
+        };
+        let offset = match zone.as_bytes().first() {
+            None => None,
+            Some(b'Z') => Some(0),
+            Some(sign @ (b'+' | b'-')) => {
+                let zone: Vec<&str> = zone[1..]
+                    .split('\'')
+                    .filter(|part| !part.is_empty())
+                    .collect();
+                let hours: i16 = zone.first()?.parse().ok()?;
+                let minutes: i16 = match zone.get(1) {
+                    Some(minutes) => minutes.parse().ok()?,
+                    None => 0,
+                };
+                Some(zone_offset(*sign, hours, minutes)?)
+            }
+            Some(_) => return None,
+        };
+        Date {
+            year: fields[..4].parse().ok()?,
+            month: field(4, 1)?,
+            day: field(6, 1)?,
+            hour: field(8, 0)?,
+            minute: field(10, 0)?,
+            second: field(12, 0)?,
+            offset,
+        }
+        .validate()
+    }
+
+    /// Parses an XMP date, i.e. ISO 8601: `YYYY-MM-DDThh:mm:ss.s+hh:mm` where
+    /// everything after the year is optional.
+    pub fn parse_xmp(text: &str) -> Option<Self> {
+        let text = text.trim();
+        let (day, time) = match text.split_once('T') {
+            Some((day, time)) => (day, Some(time)),
+            None => (text, None),
+        };
+        let mut day = day.split('-');
+        let year = day.next().filter(|year| year.len() == 4)?.parse().ok()?;
+        let part = |parts: &mut std::str::Split<char>, default| match parts.next() {
+            Some(part) if part.len() == 2 => part.parse().ok(),
+            Some(_) => None,
+            None => Some(default),
+        };
+        let month = part(&mut day, 1)?;
+        let day = part(&mut day, 1)?;
+
+        let (mut hour, mut minute, mut second, mut offset) = (0, 0, 0, None);
+        if let Some(time) = time {
+            let zone_start = time.find(['Z', '+', '-']).unwrap_or(time.len());
+            let (clock, zone) = time.split_at(zone_start);
+            let mut clock = clock.split(':');
+            hour = clock.next()?.parse().ok()?;
+            minute = clock.next()?.parse().ok()?;
+            if let Some(seconds) = clock.next() {
+                // Fractions of a second are dropped.
+                second = seconds.split('.').next()?.parse().ok()?;
+            }
+            offset = match zone.as_bytes().first() {
+                None => None,
+                Some(b'Z') => Some(0),
+                Some(sign) => {
+                    let (hours, minutes) = zone[1..].split_once(':')?;
+                    Some(zone_offset(
+                        *sign,
+                        hours.parse().ok()?,
+                        minutes.parse().ok()?,
+                    )?)
+                }
+            };
+        }
+        Date {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+            offset,
+        }
+        .validate()
+    }
+
+    fn validate(self) -> Option<Self> {
+        let valid = (1..=12).contains(&self.month)
+            && (1..=31).contains(&self.day)
+            && self.hour < 24
+            && self.minute < 60
+            && self.second < 60;
+        valid.then_some(self)
+    }
+
+    /// The number of seconds since 1970-01-01 00:00:00 UTC, reading dates
+    /// without an offset as UTC.
+    pub fn timestamp(&self) -> i64 {
+        // Days from civil, see http://howardhinnant.github.io/date_algorithms.html.
+        let year = self.year as i64 - (self.month <= 2) as i64;
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let month = self.month as i64;
+        let day_of_year =
+            (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + self.day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        let days = era * 146097 + day_of_era - 719468;
+        let seconds = self.hour as i64 * 3600 + self.minute as i64 * 60 + self.second as i64;
+        days * 86400 + seconds - self.offset.unwrap_or(0) as i64 * 60
+    }
+}
+
+fn zone_offset(sign: u8, hours: i16, minutes: i16) -> Option<i16> {
+    if hours > 23 || minutes > 59 {
+        return None;
+    }
+    let offset = hours * 60 + minutes;
+    Some(if sign == b'-' { -offset } else { offset })
+}
+
+/// The descriptive metadata of a document, from either the `/Info` dictionary or
+/// the XMP packet.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Metadata {
+    pub title: Option<String>,
+    /// `/Author`, or the `dc:creator` list joined with `; `.
+    pub author: Option<String>,
+    /// `/Subject`, or `dc:description`.
+    pub subject: Option<String>,
+    pub keywords: Option<String>,
+    /// The application that created the original document: `/Creator`, or
+    /// `xmp:CreatorTool`.
+    pub creator: Option<String>,
+    /// The application that converted it to PDF.
+    pub producer: Option<String>,
+    pub creation_date: Option<Date>,
+    pub modification_date: Option<Date>,
+}
+
+/// A field whose value differs between `/Info` and XMP.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    /// The name of the `/Info` entry, e.g. `Title` or `CreationDate`.
+    pub field: &'static str,
+    pub info: String,
+    pub xmp: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Title,
+    Author,
+    Subject,
+    Keywords,
+    Creator,
+    Producer,
+    CreationDate,
+    ModificationDate,
+}
+
+/// An XMP property being read.
+struct Property {
+    field: Field,
+    /// The depth of the property element.
+    depth: usize,
+    text: String,
+    /// The `rdf:li` items, and whether each is in the default language.
+    items: Vec<(bool, String)>,
+}
+
+impl Field {
+    fn from_xmp(namespace: &[u8], name: &[u8]) -> Option<Self> {
+        Some(match (namespace, name) {
+            (DC, b"title") => Field::Title,
+            (DC, b"creator") => Field::Author,
+            (DC, b"description") => Field::Subject,
+            (ADOBE_PDF, b"Keywords") => Field::Keywords,
+            (XMP, b"CreatorTool") => Field::Creator,
+            (ADOBE_PDF, b"Producer") => Field::Producer,
+            (XMP, b"CreateDate") => Field::CreationDate,
+            (XMP, b"ModifyDate") => Field::ModificationDate,
+            _ => return None,
+        })
+    }
+}
+
+impl Metadata {
+    /// Reads an `/Info` dictionary. Dates that cannot be parsed are left out.
+    pub fn from_info(pdf: &PDF, info: &Object) -> Self {
+        let text = |key: &str| pdf.get(info, key).and_then(Object::text_string);
+        let date = |key: &str| text(key).and_then(|date| Date::parse(&date));
+        Metadata {
+            title: text("Title"),
+            author: text("Author"),
+            subject: text("Subject"),
+            keywords: text("Keywords"),
+            creator: text("Creator"),
+            producer: text("Producer"),
+            creation_date: date("CreationDate"),
+            modification_date: date("ModDate"),
+        }
+    }
+
+    /// Reads the properties of an XMP packet, written either as elements or as
+    /// attributes of `rdf:Description`. Of language alternatives, `x-default` is
+    /// preferred, then the first. Reading stops at the first malformed markup.
+    pub fn from_xmp(xmp: &[u8]) -> Self {
+        let mut reader = NsReader::from_reader(xmp);
+        reader.config_mut().check_end_names = false;
+
+        let mut metadata = Metadata::default();
+        let mut depth = 0;
+        let mut property: Option<Property> = None;
+        // Whether the list item being read is in the default language.
+        let mut item: Option<bool> = None;
+        loop {
+            match reader.read_resolved_event() {
+                Ok((namespace, Event::Start(element))) => {
+                    depth += 1;
+                    if property.is_some() {
+                        if element.local_name().as_ref() == b"li" {
+                            item = Some(is_default_language(&element));
+                            if let Some(property) = &mut property {
+                                property.items.push((item == Some(true), String::new()));
+                            }
+                        }
+                        continue;
+                    }
+                    let field = bound(&namespace).and_then(|namespace| {
+                        Field::from_xmp(namespace, element.local_name().as_ref())
+                    });
+                    let description = is_description(&namespace, &element);
+                    match field {
+                        Some(field) => {
+                            property = Some(Property {
+                                field,
+                                depth,
+                                text: String::new(),
+                                items: Vec::new(),
+                            })
+                        }
+                        None if description => metadata.read_attributes(&reader, &element),
+                        None => {}
+                    }
+                }
+                Ok((namespace, Event::Empty(element))) => {
+                    if property.is_none() && is_description(&namespace, &element) {
+                        metadata.read_attributes(&reader, &element);
+                    }
+                }
+                Ok((_, Event::Text(text))) => {
+                    if let Some(property) = &mut property {
+                        let text = text
+                            .unescape()
+                            .map(|text| text.into_owned())
+                            .unwrap_or_else(|_| String::from_utf8_lossy(&text).into_owned());
+                        match (item, property.items.last_mut()) {
+                            (Some(_), Some((_, item))) => item.push_str(&text),
+                            _ => property.text.push_str(&text),
+                        }
+                    }
+                }
+                Ok((_, Event::End(element))) => {
+                    if element.local_name().as_ref() == b"li" {
+                        item = None;
+                    }
+                    if let Some(read) = property.take_if(|property| property.depth == depth) {
+                        metadata.set(read.field, &read.text, &read.items);
+                    }
+                    depth -= 1;
+                }
+                Ok((_, Event::Eof)) | Err(_) => break,
+                Ok(_) => {}
+            }
+        }
+        metadata
+    }
+
+    /// Reads the properties given as attributes of an `rdf:Description`.
+    fn read_attributes(&mut self, reader: &NsReader<&[u8]>, element: &BytesStart) {
+        for attribute in element.attributes().flatten() {
+            let (namespace, name) = reader.resolve_attribute(attribute.key);
+            let field = match bound(&namespace)
+                .and_then(|namespace| Field::from_xmp(namespace, name.as_ref()))
+            {
+                Some(field) => field,
+                None => continue,
+            };
+            let value = attribute
+                .unescape_value()
+                .map(|value| value.into_owned())
+                .unwrap_or_else(|_| String::from_utf8_lossy(&attribute.value).into_owned());
+            self.set(field, &value, &[]);
+        }
+    }
+
+    fn set(&mut self, field: Field, value: &str, items: &[(bool, String)]) {
+        let value = match (field, items) {
+            (_, []) => value.trim().to_string(),
+            (Field::Author, items) => items
+                .iter()
+                .map(|(_, item)| item.trim())
+                .collect::<Vec<_>>()
+                .join("; "),
+            (_, items) => items
+                .iter()
+                .find(|(default, _)| *default)
+                .unwrap_or(&items[0])
+                .1
+                .trim()
+                .to_string(),
+        };
+        if value.is_empty() {
+            return;
+        }
+        let text = Some(value.clone());
+        match field {
+            Field::Title => self.title = text,
+            Field::Author => self.author = text,
+            Field::Subject => self.subject = text,
+            Field::Keywords => self.keywords = text,
+            Field::Creator => self.creator = text,
+            Field::Producer => self.producer = text,
+            Field::CreationDate => self.creation_date = Date::parse_xmp(&value),
+            Field::ModificationDate => self.modification_date = Date::parse_xmp(&value),
+        }
+    }
+
+    /// Compares the fields set on both sides: texts once trimmed, dates as
+    /// instants. Fields set on one side only are not mismatches.
+    pub fn mismatches(&self, xmp: &Metadata) -> Vec<Mismatch> {
+        let mut mismatches = Vec::new();
+        let texts = [
+            ("Title", &self.title, &xmp.title),
+            ("Author", &self.author, &xmp.author),
+            ("Subject", &self.subject, &xmp.subject),
+            ("Keywords", &self.keywords, &xmp.keywords),
+            ("Creator", &self.creator, &xmp.creator),
+            ("Producer", &self.producer, &xmp.producer),
+        ];
+        for (field, info, xmp) in texts {
+            if let (Some(info), Some(xmp)) = (info, xmp) {
+                if info.trim() != xmp.trim() {
+                    mismatches.push(Mismatch {
+                        field,
+                        info: info.clone(),
+                        xmp: xmp.clone(),
+                    });
+                }
+            }
+        }
+        let dates = [
+            ("CreationDate", &self.creation_date, &xmp.creation_date),
+            ("ModDate", &self.modification_date, &xmp.modification_date),
+        ];
+        for (field, info, xmp) in dates {
+            if let (Some(info), Some(xmp)) = (info, xmp) {
+                if info.timestamp() != xmp.timestamp() {
+                    mismatches.push(Mismatch {
+                        field,
+                        info: format!("{:?}", info),
+                        xmp: format!("{:?}", xmp),
+                    });
+                }
+            }
+        }
+        mismatches
+    }
+}
+
+fn bound<'n>(namespace: &ResolveResult<'n>) -> Option<&'n [u8]> {
+    match namespace {
+        ResolveResult::Bound(namespace) => Some(namespace.into_inner()),
+        _ => None,
+    }
+}
+
+fn is_description(namespace: &ResolveResult, element: &BytesStart) -> bool {
+    bound(namespace) == Some(RDF) && element.local_name().as_ref() == b"Description"
+}
+
+fn is_default_language(element: &BytesStart) -> bool {
+    element.attributes().flatten().any(|attribute| {
+        attribute.key.as_ref() == b"xml:lang" && attribute.value.as_ref() == b"x-default"
+    })
+}
+
+impl<'a> PDF<'a> {
+    /// Reads the trailer `/Info` dictionary, `None` if there is none.
+    pub fn info(&self) -> Option<Metadata> {
+        let info = self.get(&self.trailer.dictionary, "Info")?;
+        info.as_dictionary()?;
+        Some(Metadata::from_info(self, info))
+    }
+
+    /// Returns the decoded XMP packet of the catalog `/Metadata` stream, `None` if
+    /// there is none.
+    pub fn xmp(&self) -> Result<Option<Vec<u8>>, ParseError> {
+        let metadata = match self.resolve(self.catalog()?).get("Metadata") {
+            Some(metadata) => metadata,
+            None => return Ok(None),
+        };
+        let (dictionary, data) = self
+            .stream_data(metadata)
+            .ok_or(ParseError::InvalidPDFStreamData)?;
+        crate::filter::decode(dictionary, data).map(Some)
+    }
+
+    /// Reads the properties of the XMP packet, `None` if there is none.
+    pub fn xmp_metadata(&self) -> Result<Option<Metadata>, ParseError> {
+        Ok(self.xmp()?.map(|xmp| Metadata::from_xmp(&xmp)))
+    }
+
+    /// Lists the fields whose values differ between `/Info` and XMP, see
+    /// `Metadata::mismatches`.
+    pub fn metadata_mismatches(&self) -> Result<Vec<Mismatch>, ParseError> {
+        match (self.info(), self.xmp_metadata()?) {
+            (Some(info), Some(xmp)) => Ok(info.mismatches(&xmp)),
+            _ => Ok(Vec::new()),
+        }
+    }
+}
@@ -0,0 +1,327 @@
+use std::collections::HashSet;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::slice::ChunksExact;
+
+use crate::error::ParseError;
+use crate::object::{IndirectReference, Object, PDF};
+
+/// Maximum depth of `/Kids` followed from the root of a tree.
+const MAX_TREE_DEPTH: usize = 64;
+
+/// The kind of keys of a tree, and the entry holding its key-value pairs.
+pub trait TreeKind {
+    type Key: Ord + Clone + Debug;
+    /// `Names` or `Nums`.
+    const ENTRIES: &'static str;
+
+    /// Reads a key, `None` if the object is not of the right type.
+    fn key(object: &Object) -> Option<Self::Key>;
+    /// Formats a key for error messages.
+    fn display(key: &Self::Key) -> String;
+    fn error(message: String) -> ParseError;
+}
+
+/// The least and greatest keys of a node.
+type KeyRange<K> = (<K as TreeKind>::Key, <K as TreeKind>::Key);
+
+/// Keys of name trees: strings, compared byte by byte.
+#[derive(Debug, Clone, Copy)]
+pub struct Names;
+
+impl TreeKind for Names {
+    type Key = Vec<u8>;
+    const ENTRIES: &'static str = "Names";
+
+    fn key(object: &Object) -> Option<Vec<u8>> {
+        object.string_bytes()
+    }
+
+    fn display(key: &Vec<u8>) -> String {
+        format!("({})", String::from_utf8_lossy(key))
+    }
+
+    fn error(message: String) -> ParseError {
+        ParseError::InvalidPDFNameTree(message)
+    }
+}
+
+/// Keys of number trees: integers.
+#[derive(Debug, Clone, Copy)]
+pub struct Numbers;
+
+impl TreeKind for Numbers {
+    type Key = i32;
+    const ENTRIES: &'static str = "Nums";
+
+    fn key(object: &Object) -> Option<i32> {
+        object.as_i32()
+    }
+
+    fn display(key: &i32) -> String {
+        key.to_string()
+    }
+
+    fn error(message: String) -> ParseError {
+        ParseError::InvalidPDFNumberTree(message)
+    }
+}
+
+/// A tree mapping keys to objects through `/Names` or `/Nums` leaves and `/Kids`
+/// nodes, whose `/Limits` give the least and greatest keys below them.
+#[derive(Debug, Clone, Copy)]
+pub struct Tree<'p, 'a, K> {
+    pdf: &'p PDF<'a>,
+    root: &'p Object<'a>,
+    kind: PhantomData<K>,
+}
+
+/// A name tree, mapping strings to objects.
+pub type NameTree<'p, 'a> = Tree<'p, 'a, Names>;
+/// A number tree, mapping integers to objects.
+pub type NumberTree<'p, 'a> = Tree<'p, 'a, Numbers>;
+
+impl<'p, 'a, K: TreeKind> Tree<'p, 'a, K> {
+    pub fn new(pdf: &'p PDF<'a>, root: &'p Object<'a>) -> Self {
+        Self {
+            pdf,
+            root,
+            kind: PhantomData,
+        }
+    }
+
+    /// Iterates over the keys and values of the tree in order. Values are as
+    /// stored, so indirect objects keep their reference.
+    /// Nodes reached twice, e.g. through a cycle of `/Kids`, are read once.
+    pub fn iter(&self) -> TreeIter<'p, 'a, K> {
+        TreeIter {
+            pdf: self.pdf,
+            nodes: vec![(self.root, 0)],
+            entries: [].chunks_exact(2),
+            visited: HashSet::new(),
+            kind: PhantomData,
+        }
+    }
+
+    /// Returns the keys and values of the tree in order, as `iter`.
+    pub fn entries(&self) -> Vec<(K::Key, &'p Object<'a>)> {
+        self.iter().collect()
+    }
+
+    /// Looks up the value of `key`, only descending into the kids whose `/Limits`
+    /// include it. Kids without `/Limits` are searched too.
+    pub fn get(&self, key: &K::Key) -> Option<&'p Object<'a>> {
+        self.find(self.root, key, 0, &mut HashSet::new())
+    }
+
+    fn find(
+        &self,
+        node: &'p Object<'a>,
+        key: &K::Key,
+        depth: usize,
+        visited: &mut HashSet<IndirectReference>,
+    ) -> Option<&'p Object<'a>> {
+        if depth > MAX_TREE_DEPTH {
+            return None;
+        }
+        if let Some(reference) = node.as_reference() {
+            if !visited.insert(reference) {
+                return None;
+            }
+        }
+        if let Some(entries) = self.pdf.get(node, K::ENTRIES).and_then(Object::as_array) {
+            let found = entries
+                .chunks_exact(2)
+                .find(|pair| K::key(self.pdf.resolve(&pair[0])).as_ref() == Some(key));
+            if let Some(pair) = found {
+                return Some(&pair[1]);
+            }
+        }
+        let kids = self.pdf.get(node, "Kids").and_then(Object::as_array)?;
+        kids.iter()
+            .filter(|kid| match self.limits(kid) {
+                Some(Some((low, high))) => low <= *key && *key <= high,
+                _ => true,
+            })
+            .find_map(|kid| self.find(kid, key, depth + 1, visited))
+    }
+
+    /// Reads the `/Limits` of a node: `None` if there are none, `Some(None)` if
+    /// they are not a pair of keys.
+    fn limits(&self, node: &Object<'a>) -> Option<Option<KeyRange<K>>> {
+        let limits = self.pdf.get(node, "Limits")?;
+        Some(match self.pdf.resolve(limits).as_array() {
+            Some([low, high]) => K::key(self.pdf.resolve(low)).zip(K::key(self.pdf.resolve(high))),
+            _ => None,
+        })
+    }
+
+    /// Checks the structure of the tree: the keys of each leaf are sorted, each
+    /// kid has `/Limits` covering its keys, the kids of a node are in order and
+    /// do not overlap, and no node is reached twice.
+    pub fn validate(&self) -> Result<(), ParseError> {
+        self.check(self.root, "", 0, &mut HashSet::new())?;
+        Ok(())
+    }
+
+    /// Checks the subtree of `node` and returns the least and greatest keys in it,
+    /// `None` if it is empty.
+    fn check(
+        &self,
+        node: &'p Object<'a>,
+        path: &str,
+        depth: usize,
+        visited: &mut HashSet<IndirectReference>,
+    ) -> Result<Option<KeyRange<K>>, ParseError> {
+        let at = |message: String| match path.is_empty() {
+            true => K::error(message),
+            false => K::error(format!("{}: {}", path, message)),
+        };
+        if depth > MAX_TREE_DEPTH {
+            return Err(at(format!("deeper than {} levels", MAX_TREE_DEPTH)));
+        }
+        if let Some(reference) = node.as_reference() {
+            if !visited.insert(reference) {
+                return Err(at(format!(
+                    "{} {} R is reached twice",
+                    reference.id, reference.generation
+                )));
+            }
+        }
+        if self.pdf.resolve(node).as_dictionary().is_none() {
+            return Err(at("not a dictionary".to_string()));
+        }
+
+        let mut range: Option<KeyRange<K>> = None;
+        let mut extend = |low: K::Key, high: K::Key, what: &str| {
+            if let Some((_, last)) = &range {
+                if low <= *last {
+                    return Err(at(format!(
+                        "{} {} is not after {}",
+                        what,
+                        K::display(&low),
+                        K::display(last)
+                    )));
+                }
+            }
+            range = Some(match range.take() {
+                Some((first, _)) => (first, high),
+                None => (low, high),
+            });
+            Ok(())
+        };
+
+        if let Some(entries) = self.pdf.get(node, K::ENTRIES) {
+            let entries = self
+                .pdf
+                .resolve(entries)
+                .as_array()
+                .ok_or_else(|| at(format!("/{} is not an array", K::ENTRIES)))?;
+            if entries.len() % 2 != 0 {
+                return Err(at(format!("/{} has an odd length", K::ENTRIES)));
+            }
+            for (index, pair) in entries.chunks_exact(2).enumerate() {
+                let key = K::key(self.pdf.resolve(&pair[0]))
+                    .ok_or_else(|| at(format!("key {} is invalid", index)))?;
+                extend(key.clone(), key, "key")?;
+            }
+        }
+        if let Some(kids) = self.pdf.get(node, "Kids") {
+            let kids = self
+                .pdf
+                .resolve(kids)
+                .as_array()
+                .ok_or_else(|| at("/Kids is not an array".to_string()))?;
+            for (index, kid) in kids.iter().enumerate() {
+                let kid_path = format!("{}/Kids[{}]", path, index);
+                if let Some((low, high)) = self.check(kid, &kid_path, depth + 1, visited)? {
+                    extend(low, high, "kid starting at")?;
+                }
+            }
+        }
+
+        // Every node but the root has `/Limits`.
+        if depth == 0 {
+            return Ok(range);
+        }
+        let limits = match self.limits(node) {
+            Some(Some(limits)) => limits,
+            Some(None) => return Err(at("invalid /Limits".to_string())),
+            None => return Err(at("missing /Limits".to_string())),
+        };
+        if limits.0 > limits.1 {
+            return Err(at(format!(
+                "/Limits [{} {}] are reversed",
+                K::display(&limits.0),
+                K::display(&limits.1)
+            )));
+        }
+        if let Some((low, high)) = &range {
+            if *low < limits.0 || *high > limits.1 {
+                return Err(at(format!(
+                    "keys {} to {} are outside /Limits [{} {}]",
+                    K::display(low),
+                    K::display(high),
+                    K::display(&limits.0),
+                    K::display(&limits.1)
+                )));
+            }
+        }
+        Ok(range)
+    }
+}
+
+/// An iterator over the entries of a tree, see `Tree::iter`.
+#[derive(Debug, Clone)]
+pub struct TreeIter<'p, 'a, K> {
+    pdf: &'p PDF<'a>,
+    /// The nodes left to read with their depth, the next one last.
+    nodes: Vec<(&'p Object<'a>, usize)>,
+    /// The pairs of the current leaf left to read.
+    entries: ChunksExact<'p, Object<'a>>,
+    visited: HashSet<IndirectReference>,
+    kind: PhantomData<K>,
+}
+
+impl<'p, 'a, K: TreeKind> Iterator for TreeIter<'p, 'a, K> {
+    type Item = (K::Key, &'p Object<'a>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(pair) = self.entries.next() {
+                match K::key(self.pdf.resolve(&pair[0])) {
+                    Some(key) => return Some((key, &pair[1])),
+                    None => continue,
+                }
+            }
+            let (node, depth) = self.nodes.pop()?;
+            if depth > MAX_TREE_DEPTH {
+                continue;
+            }
+            if let Some(reference) = node.as_reference() {
+                if !self.visited.insert(reference) {
+                    continue;
+                }
+            }
+            if let Some(entries) = self.pdf.get(node, K::ENTRIES).and_then(Object::as_array) {
+                self.entries = entries.chunks_exact(2);
+            }
+            if let Some(kids) = self.pdf.get(node, "Kids").and_then(Object::as_array) {
+                self.nodes
+                    .extend(kids.iter().rev().map(|kid| (kid, depth + 1)));
+            }
+        }
+    }
+}
+
+impl<'a> PDF<'a> {
+    /// Returns the name tree `key` of the catalog `/Names` dictionary, such as
+    /// `Dests`, `JavaScript` or `EmbeddedFiles`.
+    pub fn name_tree(&self, key: &str) -> Result<Option<NameTree<'_, 'a>>, ParseError> {
+        let names = match self.get(self.catalog()?, "Names") {
+            Some(names) => names,
+            None => return Ok(None),
+        };
+        Ok(self.get(names, key).map(|root| NameTree::new(self, root)))
+    }
+}
@@ -1,4 +1,5 @@
 use std::{
+    borrow::{Borrow, Cow},
     collections::HashMap,
     hash::{Hash, Hasher},
 };
@@ -16,8 +17,17 @@ pub struct PDF<'a> {
     pub body: Vec<Object<'a>>,
     pub cross_reference_tables: Vec<CrossReferenceTable>,
     pub trailer: Trailer<'a>,
+    /// The position in `body` of each object, see `PDF::get_object`.
+    pub(crate) index: ObjectIndex,
 }
 
+/// Maps each object to the position of its last definition in `body`, built on
+/// the first lookup, with the length of `body` at that time.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ObjectIndex(
+    pub(crate) std::sync::OnceLock<(usize, HashMap<IndirectReference, usize>)>,
+);
+
 /// The PDF header.
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 pub struct Header {
@@ -64,23 +74,41 @@ pub struct IndirectReference {
 /// A name object.
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 pub struct NameObject<'a>(pub &'a str);
+
+impl<'a> NameObject<'a> {
+    /// Returns the name with its `#xx` escapes decoded.
+    pub fn decode(&self) -> Cow<'a, str> {
+        crate::utils::decode_name(self.0)
+    }
+}
+
+// Lets dictionaries be looked up by plain `&str` keys.
+impl<'a> Borrow<str> for NameObject<'a> {
+    fn borrow(&self) -> &str {
+        self.0
+    }
+}
+
 /// A PDF dictionary object.
 pub type DictionaryObject<'a> = HashMap<NameObject<'a>, Object<'a>>;
-pub type HexString<'a> = &'a str;
+pub type HexString<'a> = &'a [u8];
 
 #[derive(Debug, Clone)]
 pub enum Object<'a> {
     Boolean(bool),
     Integer(i32),
     Real(f32),
-    LiteralString(&'a str),
+    /// The raw bytes between the parentheses, with escapes not decoded.
+    LiteralString(&'a [u8]),
     HexadecimalString(HexString<'a>),
     Name(NameObject<'a>),
     Array(Vec<Object<'a>>),
-    Dictionary(DictionaryObject<'a>, &'a str),
+    /// A dictionary and the raw data of the stream that follows it, if any.
+    Dictionary(DictionaryObject<'a>, &'a [u8]),
     Stream(DictionaryObject<'a>, &'a [u8]),
     Null,
-    Comment(&'a str),
+    /// The bytes after `%`, e.g. the binary marker after the header.
+    Comment(&'a [u8]),
     IndirectReference {
         id: u32,
         generation: u32,
@@ -136,3 +164,90 @@ impl<'a> Hash for Object<'a> {
         }
     }
 }
+
+impl<'a> Object<'a> {
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Object::Boolean(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn as_i32(&self) -> Option<i32> {
+        match self {
+            Object::Integer(i) => Some(*i),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of an integer or a real object.
+    pub fn as_f32(&self) -> Option<f32> {
+        match self {
+            Object::Integer(i) => Some(*i as f32),
+            Object::Real(f) => Some(*f),
+            _ => None,
+        }
+    }
+
+    pub fn as_name(&self) -> Option<&'a str> {
+        match self {
+            Object::Name(NameObject(name)) => Some(name),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Object<'a>]> {
+        match self {
+            Object::Array(array) => Some(array),
+            _ => None,
+        }
+    }
+
+    /// Returns the dictionary of a dictionary or stream object.
+    pub fn as_dictionary(&self) -> Option<&DictionaryObject<'a>> {
+        match self {
+            Object::Dictionary(dictionary, _) | Object::Stream(dictionary, _) => Some(dictionary),
+            _ => None,
+        }
+    }
+
+    /// Returns the raw (still encoded) data of a stream object.
+    pub fn as_stream(&self) -> Option<(&DictionaryObject<'a>, &'a [u8])> {
+        match self {
+            Object::Dictionary(dictionary, stream) | Object::Stream(dictionary, stream) => {
+                Some((dictionary, stream))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the bytes of a literal or hexadecimal string, with escapes decoded.
+    pub fn string_bytes(&self) -> Option<Vec<u8>> {
+        match self {
+            Object::LiteralString(s) => Some(crate::utils::unescape_literal_string(s)),
+            Object::HexadecimalString(s) => Some(crate::utils::decode_hexadecimal_string(s)),
+            _ => None,
+        }
+    }
+
+    /// Returns the text of a string meant for humans, decoded from PDFDocEncoding
+    /// or UTF-16BE.
+    pub fn text_string(&self) -> Option<String> {
+        Some(crate::encoding::decode_text_string(&self.string_bytes()?))
+    }
+
+    pub fn as_reference(&self) -> Option<IndirectReference> {
+        match self {
+            Object::IndirectReference { id, generation } => Some(IndirectReference {
+                id: *id,
+                generation: *generation,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Looks up `key` if this is a dictionary or stream object.
+    pub fn get(&self, key: &str) -> Option<&Object<'a>> {
+        self.as_dictionary()?.get(key)
+    }
+}
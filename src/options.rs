@@ -0,0 +1,46 @@
+/// Options of parsing and decoding: limits, as documents may be crafted to
+/// exhaust the stack or memory, and leniency towards malformed files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// The deepest nesting of arrays and dictionaries.
+    pub max_depth: usize,
+    /// The largest number of indirect objects in the body.
+    pub max_objects: usize,
+    /// The largest size of a decoded stream, in bytes.
+    pub max_decoded_size: usize,
+    /// The largest ratio of the decoded size of a stream to its encoded size.
+    pub max_decompression_ratio: usize,
+    /// Whether common deviations from the specification are accepted, see
+    /// `ParseWarningKind`. Strict mode, the default, rejects them.
+    pub lenient: bool,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: 100,
+            // The implementation limit of PDF 32000-1:2008, annex C.
+            max_objects: 8_388_607,
+            max_decoded_size: 256 * 1024 * 1024,
+            max_decompression_ratio: 4096,
+            lenient: false,
+        }
+    }
+}
+
+impl ParseOptions {
+    /// The default limits, in lenient mode.
+    pub fn lenient() -> Self {
+        Self {
+            lenient: true,
+            ..Self::default()
+        }
+    }
+
+    /// The largest size `encoded` bytes of stream data may decode to.
+    pub fn decoded_limit(&self, encoded: usize) -> usize {
+        self.max_decompression_ratio
+            .saturating_mul(encoded)
+            .min(self.max_decoded_size)
+    }
+}
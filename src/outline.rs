@@ -0,0 +1,227 @@
+use std::collections::HashSet;
+
+use crate::action::Action;
+use crate::destination::DestinationResolver;
+use crate::error::ParseError;
+use crate::object::{DictionaryObject, IndirectReference, NameObject, Object, PDF};
+use crate::writer::{encode_text_string, hex_string, reference_object, Writer};
+
+/// Maximum depth of outline items followed.
+const MAX_DEPTH: usize = 64;
+
+/// An item of the document outline, i.e. a bookmark.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutlineItem {
+    pub title: String,
+    pub reference: Option<IndirectReference>,
+    /// The index of the page of the `/Dest` destination, or of the destination
+    /// of a `/GoTo` action.
+    pub page: Option<usize>,
+    /// The `/A` action.
+    pub action: Option<Action>,
+    /// The `/Count`: if positive, the item is open and shows that many
+    /// descendants, if negative, it is closed.
+    pub count: i32,
+    pub children: Vec<OutlineItem>,
+}
+
+impl OutlineItem {
+    /// Whether the children of the item are shown.
+    pub fn is_open(&self) -> bool {
+        self.count > 0
+    }
+}
+
+impl<'a> PDF<'a> {
+    /// Reads the `/Outlines` of the catalog, empty if there are none. Items
+    /// reached twice, e.g. through a cycle of `/Next`, are read once.
+    pub fn outline(&self) -> Result<Vec<OutlineItem>, ParseError> {
+        let first = match self
+            .get(self.catalog()?, "Outlines")
+            .and_then(|outlines| self.resolve(outlines).get("First"))
+        {
+            Some(first) => first,
+            None => return Ok(Vec::new()),
+        };
+        let mut reader = OutlineReader {
+            pdf: self,
+            destinations: DestinationResolver::new(self)?,
+            visited: HashSet::new(),
+        };
+        Ok(reader.items(first, 0))
+    }
+}
+
+struct OutlineReader<'p, 'a> {
+    pdf: &'p PDF<'a>,
+    destinations: DestinationResolver<'p, 'a>,
+    visited: HashSet<IndirectReference>,
+}
+
+impl<'p, 'a> OutlineReader<'p, 'a> {
+    /// Reads `first` and its siblings.
+    fn items(&mut self, first: &'p Object<'a>, depth: usize) -> Vec<OutlineItem> {
+        let mut items = Vec::new();
+        if depth > MAX_DEPTH {
+            return items;
+        }
+        let mut next = Some(first);
+        while let Some(item) = next {
+            if let Some(reference) = item.as_reference() {
+                if !self.visited.insert(reference) {
+                    break;
+                }
+            }
+            let dictionary = match self.pdf.resolve(item).as_dictionary() {
+                Some(dictionary) => dictionary,
+                None => break,
+            };
+            let get = |key: &str| dictionary.get(key).map(|value| self.pdf.resolve(value));
+            let action = get("A").and_then(|action| Action::from_object(self.pdf, action));
+            let destination = match (get("Dest"), &action) {
+                (Some(destination), _) => Some(destination),
+                (None, Some(Action::GoTo)) => get("A").and_then(|action| self.pdf.get(action, "D")),
+                _ => None,
+            };
+            let children = match dictionary.get("First") {
+                Some(child) => self.items(child, depth + 1),
+                None => Vec::new(),
+            };
+            items.push(OutlineItem {
+                title: get("Title")
+                    .and_then(Object::text_string)
+                    .unwrap_or_default(),
+                reference: item.as_reference(),
+                page: destination
+                    .and_then(|destination| self.destinations.resolve_object(destination))
+                    .map(|destination| destination.page),
+                action,
+                count: get("Count").and_then(Object::as_i32).unwrap_or(0),
+                children,
+            });
+            next = dictionary.get("Next");
+        }
+        items
+    }
+}
+
+/// A bookmark to write, going to a page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bookmark {
+    pub title: String,
+    /// The index of the page, shown whole.
+    pub page: usize,
+    /// Whether the children are shown.
+    pub open: bool,
+    pub children: Vec<Bookmark>,
+}
+
+impl Bookmark {
+    /// Makes an open bookmark without children.
+    pub fn new(title: &str, page: usize) -> Self {
+        Self {
+            title: title.to_string(),
+            page,
+            open: true,
+            children: Vec::new(),
+        }
+    }
+
+    /// The number of descendants shown.
+    fn visible(&self) -> i32 {
+        match self.open {
+            true => visible(&self.children),
+            false => 0,
+        }
+    }
+}
+
+/// The number of items shown for `bookmarks` and their open descendants.
+fn visible(bookmarks: &[Bookmark]) -> i32 {
+    bookmarks
+        .iter()
+        .map(|bookmark| 1 + bookmark.visible())
+        .sum()
+}
+
+impl<'p, 'a> Writer<'p, 'a> {
+    /// Replaces the outline of the document with `bookmarks`, or removes it if
+    /// there are none. The previous items are left unreferenced.
+    pub fn set_outline(&mut self, bookmarks: &[Bookmark]) -> Result<(), ParseError> {
+        let pdf = self.pdf();
+        let catalog = pdf
+            .trailer
+            .dictionary
+            .get("Root")
+            .and_then(Object::as_reference)
+            .ok_or(ParseError::InvalidPDFTrailer)?;
+        if bookmarks.is_empty() {
+            self.remove(catalog, "Outlines");
+            return Ok(());
+        }
+        let pages: Vec<IndirectReference> =
+            pdf.pages()?.iter().map(|page| page.reference).collect();
+
+        let root = self.reserve();
+        let items = self.bookmarks(bookmarks, root, &pages)?;
+        let mut dictionary = DictionaryObject::new();
+        dictionary.insert(NameObject("Type"), Object::Name(NameObject("Outlines")));
+        dictionary.insert(NameObject("First"), reference_object(items[0]));
+        dictionary.insert(NameObject("Last"), reference_object(items[items.len() - 1]));
+        dictionary.insert(NameObject("Count"), Object::Integer(visible(bookmarks)));
+        self.insert(root, &Object::Dictionary(dictionary, &[]));
+        self.set(catalog, "Outlines", &reference_object(root));
+        Ok(())
+    }
+
+    /// Adds the items of `bookmarks` under `parent` and returns their references.
+    fn bookmarks(
+        &mut self,
+        bookmarks: &[Bookmark],
+        parent: IndirectReference,
+        pages: &[IndirectReference],
+    ) -> Result<Vec<IndirectReference>, ParseError> {
+        let items: Vec<IndirectReference> = bookmarks.iter().map(|_| self.reserve()).collect();
+        for (index, bookmark) in bookmarks.iter().enumerate() {
+            let page = pages.get(bookmark.page).ok_or_else(|| {
+                ParseError::InvalidPDFOutline(format!(
+                    "page {} of {} is out of range",
+                    bookmark.page, bookmark.title
+                ))
+            })?;
+            let children = self.bookmarks(&bookmark.children, items[index], pages)?;
+
+            let title = hex_string(&encode_text_string(&bookmark.title));
+            let mut dictionary = DictionaryObject::new();
+            dictionary.insert(
+                NameObject("Title"),
+                Object::HexadecimalString(title.as_bytes()),
+            );
+            dictionary.insert(NameObject("Parent"), reference_object(parent));
+            dictionary.insert(
+                NameObject("Dest"),
+                Object::Array(vec![
+                    reference_object(*page),
+                    Object::Name(NameObject("Fit")),
+                ]),
+            );
+            if index > 0 {
+                dictionary.insert(NameObject("Prev"), reference_object(items[index - 1]));
+            }
+            if let Some(next) = items.get(index + 1) {
+                dictionary.insert(NameObject("Next"), reference_object(*next));
+            }
+            if let (Some(first), Some(last)) = (children.first(), children.last()) {
+                dictionary.insert(NameObject("First"), reference_object(*first));
+                dictionary.insert(NameObject("Last"), reference_object(*last));
+                let count = match bookmark.open {
+                    true => bookmark.visible(),
+                    false => -visible(&bookmark.children),
+                };
+                dictionary.insert(NameObject("Count"), Object::Integer(count));
+            }
+            self.insert(items[index], &Object::Dictionary(dictionary, &[]));
+        }
+        Ok(items)
+    }
+}
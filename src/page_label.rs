@@ -0,0 +1,189 @@
+use crate::error::ParseError;
+use crate::name_tree::NumberTree;
+use crate::object::{Object, PDF};
+
+/// The most times a letter is repeated in a roman or alphabetic label. Larger
+/// numbers, e.g. from a hostile `/St`, are written in decimal instead.
+const MAX_REPEATS: u32 = 100;
+/// The most characters kept from a `/P` prefix, as every label repeats it.
+const MAX_PREFIX_LENGTH: usize = 256;
+
+/// The numbering style of a page label range, `/S`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LabelStyle {
+    /// `D`: 1, 2, 3...
+    Decimal,
+    /// `R`: I, II, III...
+    UpperRoman,
+    /// `r`: i, ii, iii...
+    LowerRoman,
+    /// `A`: A to Z, then AA to ZZ, AAA to ZZZ...
+    UpperAlpha,
+    /// `a`: a to z, then aa to zz, aaa to zzz...
+    LowerAlpha,
+}
+
+impl LabelStyle {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "D" => Some(LabelStyle::Decimal),
+            "R" => Some(LabelStyle::UpperRoman),
+            "r" => Some(LabelStyle::LowerRoman),
+            "A" => Some(LabelStyle::UpperAlpha),
+            "a" => Some(LabelStyle::LowerAlpha),
+            _ => None,
+        }
+    }
+
+    /// Formats a number. Numbers that roman numerals and letters cannot write,
+    /// 0 or too large ones, are written in decimal.
+    pub fn format(&self, number: u32) -> String {
+        let repeats = match self {
+            LabelStyle::Decimal => 0,
+            LabelStyle::UpperRoman | LabelStyle::LowerRoman => number / 1000,
+            LabelStyle::UpperAlpha | LabelStyle::LowerAlpha => number.saturating_sub(1) / 26 + 1,
+        };
+        if number == 0 || repeats > MAX_REPEATS {
+            return number.to_string();
+        }
+        match self {
+            LabelStyle::Decimal => number.to_string(),
+            LabelStyle::UpperRoman => roman(number),
+            LabelStyle::LowerRoman => roman(number).to_lowercase(),
+            LabelStyle::UpperAlpha => alpha(number),
+            LabelStyle::LowerAlpha => alpha(number).to_lowercase(),
+        }
+    }
+}
+
+/// Writes a number in roman numerals, with as many `M` as needed for thousands.
+fn roman(mut number: u32) -> String {
+    const NUMERALS: [(u32, &str); 13] = [
+        (1000, "M"),
+        (900, "CM"),
+        (500, "D"),
+        (400, "CD"),
+        (100, "C"),
+        (90, "XC"),
+        (50, "L"),
+        (40, "XL"),
+        (10, "X"),
+        (9, "IX"),
+        (5, "V"),
+        (4, "IV"),
+        (1, "I"),
+    ];
+    let mut text = String::new();
+    for (value, numeral) in NUMERALS {
+        while number >= value {
+            text.push_str(numeral);
+            number -= value;
+        }
+    }
+    text
+}
+
+/// Writes a number as letters: the letter of `(number - 1) % 26`, repeated once
+/// more for each 26.
+fn alpha(number: u32) -> String {
+    let letter = (b'A' + ((number - 1) % 26) as u8) as char;
+    std::iter::repeat_n(letter, ((number - 1) / 26 + 1) as usize).collect()
+}
+
+/// A range of pages labelled alike, from an entry of the `/PageLabels` tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageLabelRange {
+    /// The index of the first page of the range.
+    pub start: usize,
+    /// The numbering style, `None` for labels made of the prefix only.
+    pub style: Option<LabelStyle>,
+    /// The `/P` prefix.
+    pub prefix: String,
+    /// The `/St` number of the first page.
+    pub first: u32,
+}
+
+impl PageLabelRange {
+    /// Reads a page label dictionary for the range starting at `start`.
+    pub fn from_object(pdf: &PDF, start: usize, label: &Object) -> Self {
+        PageLabelRange {
+            start,
+            style: pdf
+                .get(label, "S")
+                .and_then(Object::as_name)
+                .and_then(LabelStyle::from_name),
+            prefix: pdf
+                .get(label, "P")
+                .and_then(Object::text_string)
+                .map(|prefix| prefix.chars().take(MAX_PREFIX_LENGTH).collect())
+                .unwrap_or_default(),
+            first: pdf
+                .get(label, "St")
+                .and_then(Object::as_i32)
+                .and_then(|first| u32::try_from(first).ok())
+                .unwrap_or(1)
+                .max(1),
+        }
+    }
+
+    /// The label of the page `index`, which must be in the range.
+    pub fn label(&self, index: usize) -> String {
+        let number = self.first.saturating_add((index - self.start) as u32);
+        match self.style {
+            Some(style) => format!("{}{}", self.prefix, style.format(number)),
+            None => self.prefix.clone(),
+        }
+    }
+}
+
+impl<'a> PDF<'a> {
+    /// Reads the ranges of the catalog `/PageLabels` number tree, sorted by first
+    /// page. Entries with a negative page index are ignored.
+    pub fn page_label_ranges(&self) -> Result<Vec<PageLabelRange>, ParseError> {
+        let root = match self.resolve(self.catalog()?).get("PageLabels") {
+            Some(root) => root,
+            None => return Ok(Vec::new()),
+        };
+        let mut ranges: Vec<PageLabelRange> = NumberTree::new(self, root)
+            .iter()
+            .filter_map(|(start, label)| {
+                let start = usize::try_from(start).ok()?;
+                Some(PageLabelRange::from_object(self, start, label))
+            })
+            .collect();
+        ranges.sort_by_key(|range| range.start);
+        Ok(ranges)
+    }
+
+    /// Returns the labels of all pages, `None` for pages before the first range
+    /// or if the document has no page labels.
+    pub fn page_labels(&self) -> Result<Vec<Option<String>>, ParseError> {
+        let ranges = self.page_label_ranges()?;
+        let count = self.pages()?.len();
+        Ok((0..count).map(|index| label_at(&ranges, index)).collect())
+    }
+
+    /// Returns the label of the page `index`, `None` if the page does not exist or
+    /// has no label. Use `page_labels` to list the labels of all pages.
+    pub fn page_label(&self, index: usize) -> Result<Option<String>, ParseError> {
+        let ranges = self.page_label_ranges()?;
+        if index >= self.pages()?.len() {
+            return Ok(None);
+        }
+        Ok(label_at(&ranges, index))
+    }
+
+    /// Finds the index of the first page labelled `label`.
+    pub fn page_index(&self, label: &str) -> Result<Option<usize>, ParseError> {
+        let ranges = self.page_label_ranges()?;
+        let count = self.pages()?.len();
+        Ok((0..count).find(|&index| label_at(&ranges, index).as_deref() == Some(label)))
+    }
+}
+
+/// The label of the page `index` from ranges sorted by first page: that of the
+/// last range starting at or before it.
+fn label_at(ranges: &[PageLabelRange], index: usize) -> Option<String> {
+    let after = ranges.partition_point(|range| range.start <= index);
+    Some(ranges[after.checked_sub(1)?].label(index))
+}
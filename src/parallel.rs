@@ -0,0 +1,154 @@
+use std::collections::{BTreeSet, HashSet};
+
+use nom::multi::many0;
+use rayon::prelude::*;
+
+use crate::error::ParseError;
+use crate::object::{CrossReferenceTable, Header, IndirectReference, Object, Trailer, PDF};
+use crate::options::ParseOptions;
+
+/// The objects, cross-reference sections and trailer parsed from a region of the
+/// file.
+type Region<'a> = (
+    Vec<Object<'a>>,
+    Vec<CrossReferenceTable>,
+    Option<Trailer<'a>>,
+);
+
+impl<'a> PDF<'a> {
+    /// Parses a document as `PDF::parse` does, with its objects parsed in
+    /// parallel. The file is split at the offsets of the cross-reference tables,
+    /// reached from `startxref` through `/Prev`, and the regions are parsed
+    /// independently. If a region cannot be read whole, e.g. an offset is wrong,
+    /// the document is parsed sequentially, so the result is always that of
+    /// `PDF::parse`.
+    pub fn parse_parallel(input: &'a [u8]) -> Result<Self, ParseError> {
+        PDF::parse_parallel_with(input, &ParseOptions::default())
+    }
+
+    /// Parses a document as `PDF::parse_with` does, with its objects parsed in
+    /// parallel as in `PDF::parse_parallel`. A document over the limits is
+    /// parsed sequentially, so that the error is that of `PDF::parse_with`.
+    pub fn parse_parallel_with(
+        input: &'a [u8],
+        options: &ParseOptions,
+    ) -> Result<Self, ParseError> {
+        match PDF::parse_regions(input, options) {
+            Some(pdf) => Ok(pdf),
+            None => PDF::parse_with(input, options),
+        }
+    }
+
+    fn parse_regions(input: &'a [u8], options: &ParseOptions) -> Option<Self> {
+        let (rest, header) = Header::parse(input).ok()?;
+        let start = input.len() - rest.len();
+
+        let mut boundaries = BTreeSet::from([input.len()]);
+        let mut visited = HashSet::new();
+        let mut next = Some(startxref(input)?);
+        let section = |input| CrossReferenceTable::parse_section_with(input, options);
+        while let Some(offset) = next {
+            if !visited.insert(offset) {
+                break;
+            }
+            let (rest, sections) = many0(section)(input.get(offset..)?).ok()?;
+            let (_, trailer) = Trailer::parse_with(rest, options).ok()?;
+            boundaries.insert(offset);
+            for table in sections.iter().flatten() {
+                boundaries.extend(
+                    table
+                        .entries
+                        .iter()
+                        .filter(|entry| !entry.free)
+                        .map(|entry| entry.offset as usize),
+                );
+            }
+            next = trailer
+                .dictionary
+                .get("Prev")
+                .and_then(Object::as_i32)
+                .and_then(|prev| usize::try_from(prev).ok());
+        }
+
+        // The first region starts after the header, with the comments before the
+        // first object.
+        if *boundaries.first()? < start || *boundaries.last()? > input.len() {
+            return None;
+        }
+        boundaries.pop_first();
+        let mut ranges = Vec::with_capacity(boundaries.len());
+        let mut from = start;
+        for to in boundaries {
+            ranges.push((from, to));
+            from = to;
+        }
+
+        let regions: Vec<Region<'a>> = ranges
+            .into_par_iter()
+            .map(|(from, to)| parse_region(&input[from..to], options))
+            .collect::<Option<_>>()?;
+
+        let mut body = Vec::new();
+        let mut cross_reference_tables = Vec::new();
+        let mut last = None;
+        for (objects, sections, trailer) in regions {
+            body.extend(objects);
+            cross_reference_tables.extend(sections);
+            last = trailer;
+        }
+        if body.len() > options.max_objects {
+            return None;
+        }
+        // As in `PDF::parse`, the file must end with a trailer.
+        Some(PDF {
+            header,
+            body,
+            cross_reference_tables,
+            trailer: last?,
+            index: Default::default(),
+        })
+    }
+
+    /// Decodes the streams of the body in parallel, with the same results as
+    /// `PDF::decode_streams`.
+    pub fn decode_streams_parallel(&self) -> Vec<(IndirectReference, Result<Vec<u8>, ParseError>)> {
+        self.body
+            .par_iter()
+            .filter_map(|object| self.decode_stream(object))
+            .collect()
+    }
+}
+
+/// Parses a region as `PDF::parse` does: objects, then optionally cross-reference
+/// sections and a trailer. `None` unless the whole region is read.
+fn parse_region<'a>(region: &'a [u8], options: &ParseOptions) -> Option<Region<'a>> {
+    let (rest, objects) = many0(|input| Object::parse_body_with(input, options))(region).ok()?;
+    if rest.is_empty() {
+        return Some((objects, Vec::new(), None));
+    }
+    let (rest, sections) =
+        many0(|input| CrossReferenceTable::parse_section_with(input, options))(rest).ok()?;
+    let (rest, trailer) = Trailer::parse_with(rest, options).ok()?;
+    match rest.is_empty() {
+        true => Some((
+            objects,
+            sections.into_iter().flatten().collect(),
+            Some(trailer),
+        )),
+        false => None,
+    }
+}
+
+/// Reads the offset after the last `startxref` of the file.
+fn startxref(input: &[u8]) -> Option<usize> {
+    let position = input
+        .windows(b"startxref".len())
+        .rposition(|window| window == b"startxref")?;
+    let digits: String = input[position + b"startxref".len()..]
+        .iter()
+        .map(|&byte| byte as char)
+        .skip_while(|c| c.is_ascii_whitespace())
+        .take_while(char::is_ascii_digit)
+        .collect();
+    digits.parse().ok()
+}
@@ -1,43 +1,149 @@
 use nom::branch::alt;
 use nom::bytes::complete::take_until;
-use nom::character::complete::{hex_digit1, newline};
-use nom::multi::{many0, many_m_n};
+use nom::combinator::{opt, value};
+use nom::multi::many0;
 use nom::sequence::{delimited, tuple};
 use nom::{bytes::complete::tag, character::complete::char};
 
+use crate::error::{ParseError, ParseResult, ParseWarning, ParseWarningKind};
 use crate::object::{
-    CrossReferenceEntry, CrossReferenceTable, DictionaryObject, NameObject, Object, Trailer, PDF,
+    CrossReferenceEntry, CrossReferenceTable, DictionaryObject, Header, NameObject, Object,
+    Trailer, PDF,
 };
+use crate::options::ParseOptions;
 use crate::utils::{
-    digit1_u32, digit1_u32_validate_length, take_bracketed, take_till_newline,
-    take_till_whitespace, take_while_separator, take_while_whitespace,
+    digit1_u32, digit1_u32_validate_length, end_of_line, take_bracketed, take_till_delimiter,
+    take_till_newline, take_till_whitespace, take_while_hexadecimal, take_while_separator,
+    take_while_whitespace,
 };
-use crate::{error::ParseResult, object::Header};
 
 impl<'a> PDF<'a> {
-    pub fn parse(input: &'a [u8]) -> Result<Self, crate::error::ParseError> {
-        // let (input, header) = Header::parse(input)?;
-        let (input, header) =
-            Header::parse(input).map_err(|_| crate::error::ParseError::InvalidPDFHeader)?;
-        let (input, objects) = many0(Object::parse_body)(input)
-            .map_err(|_| crate::error::ParseError::InvalidPDFBody)?;
-        let (input, xref_table) = many0(CrossReferenceTable::parse)(input)
-            .map_err(|_| crate::error::ParseError::InvalidPDFXrefTable)?;
-        let (input, trailer) =
-            Trailer::parse(input).map_err(|_| crate::error::ParseError::InvalidPDFTrailer)?;
-        debug_assert!(input.is_empty());
-
-        Ok(Self {
+    pub fn parse(input: &'a [u8]) -> Result<Self, ParseError> {
+        PDF::parse_with(input, &ParseOptions::default())
+    }
+
+    /// Parses a document within the limits of `options`. Reaching a limit fails
+    /// with `ParseError::PDFLimitExceeded`.
+    pub fn parse_with(input: &'a [u8], options: &ParseOptions) -> Result<Self, ParseError> {
+        PDF::parse_with_warnings(input, options).map(|(pdf, _)| pdf)
+    }
+
+    /// Parses a document as `parse_with` does, and returns the deviations from
+    /// the specification accepted in lenient mode, in file order. In strict
+    /// mode they are errors, so there are no warnings.
+    pub fn parse_with_warnings(
+        input: &'a [u8],
+        options: &ParseOptions,
+    ) -> Result<(Self, Vec<ParseWarning>), ParseError> {
+        let file = input;
+        let (input, header) = Header::parse(input).map_err(|_| ParseError::InvalidPDFHeader)?;
+        let mut input = input;
+        let mut body = Vec::new();
+        let mut cross_reference_tables = Vec::new();
+        let mut deviations = Vec::new();
+        // Incremental updates append further body, xref and trailer sections;
+        // the last trailer describes the document.
+        let trailer = loop {
+            let (rest, _) = Object::parse_objects(input, options, &mut body, &mut deviations)
+                .map_err(|error| limit_or(error, ParseError::InvalidPDFBody))?;
+            let (rest, sections) =
+                CrossReferenceTable::parse_sections(rest, options, &mut deviations)
+                    .map_err(|_| ParseError::InvalidPDFXrefTable)?;
+            let (rest, trailer) = Trailer::parse_tracked(rest, options, &mut deviations)
+                .map_err(|error| limit_or(error, ParseError::InvalidPDFTrailer))?;
+            cross_reference_tables.extend(sections.into_iter().flatten());
+            input = rest;
+            if input.is_empty() {
+                break trailer;
+            }
+        };
+
+        let warnings = deviations
+            .into_iter()
+            .map(|(position, kind)| ParseWarning {
+                offset: file.len() - position.len(),
+                kind,
+            })
+            .collect();
+        let pdf = Self {
             header,
-            body: objects,
-            cross_reference_tables: xref_table,
+            body,
+            cross_reference_tables,
             trailer,
-        })
+            index: Default::default(),
+        };
+        Ok((pdf, warnings))
+    }
+}
+
+/// A deviation accepted in lenient mode, with the input remaining where it was
+/// found, from which its offset in the file is computed.
+type Deviation<'a> = (&'a [u8], ParseWarningKind);
+
+/// Runs `parser` as `many0` does, dropping the deviations noted by the attempt
+/// which ends the repetition.
+fn many0_tracked<'a, T>(
+    mut input: &'a [u8],
+    deviations: &mut Vec<Deviation<'a>>,
+    mut parser: impl FnMut(&'a [u8], &mut Vec<Deviation<'a>>) -> ParseResult<'a, T>,
+) -> ParseResult<'a, Vec<T>> {
+    let mut results = Vec::new();
+    loop {
+        let noted = deviations.len();
+        match parser(input, deviations) {
+            Ok((rest, result)) if rest.len() < input.len() => {
+                results.push(result);
+                input = rest;
+            }
+            Ok(_) | Err(nom::Err::Error(_)) => {
+                deviations.truncate(noted);
+                return Ok((input, results));
+            }
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+/// Reads a cross-reference entry field of `width` digits, or in lenient mode of
+/// any width, which is noted.
+fn xref_field<'a>(
+    input: &'a [u8],
+    width: usize,
+    options: &ParseOptions,
+    deviations: &mut Vec<Deviation<'a>>,
+) -> ParseResult<'a, u32> {
+    match digit1_u32_validate_length(input, width) {
+        Err(nom::Err::Error(_)) if options.lenient => {
+            let (rest, value) = digit1_u32(input)?;
+            deviations.push((input, ParseWarningKind::XrefEntryWidth));
+            Ok((rest, value))
+        }
+        result => result,
+    }
+}
+
+/// Keeps the error if a limit was reached, otherwise replaces it with `error`.
+fn limit_or(result: nom::Err<ParseError>, error: ParseError) -> ParseError {
+    match result {
+        nom::Err::Failure(limit @ ParseError::PDFLimitExceeded(_)) => limit,
+        _ => error,
+    }
+}
+
+/// Fails, without backtracking, once arrays and dictionaries nest deeper than
+/// `options.max_depth`.
+fn check_depth(options: &ParseOptions, depth: usize) -> Result<(), nom::Err<ParseError>> {
+    match depth < options.max_depth {
+        true => Ok(()),
+        false => Err(nom::Err::Failure(ParseError::PDFLimitExceeded(format!(
+            "nesting deeper than {}",
+            options.max_depth
+        )))),
     }
 }
 
 impl Header {
-    pub fn parse(input: &[u8]) -> ParseResult<Header> {
+    pub fn parse(input: &[u8]) -> ParseResult<'_, Header> {
         let (input, (_, major, _, minor, _)) = tuple((
             tag(b"%PDF-"),
             digit1_u32,
@@ -71,49 +177,67 @@ impl<'a> Object<'a> {
         Ok((input, Object::Real(result)))
     }
 
-    pub fn parse_numeric(input: &'a [u8]) -> ParseResult<Object<'a>> {
+    pub fn parse_numeric(input: &'a [u8]) -> ParseResult<'a, Object<'a>> {
         let (input, result) = alt((Object::parse_integer, Object::parse_real))(input)?;
 
         Ok((input, result))
     }
 
-    pub fn parse_literal_string(input: &'a [u8]) -> ParseResult<Object<'a>> {
+    pub fn parse_literal_string(input: &'a [u8]) -> ParseResult<'a, Object<'a>> {
         let (input, (value, _)) = tuple((
             delimited(char('('), take_bracketed(b'(', b')'), char(')')),
-            take_while_separator,
+            // The closing parenthesis is a delimiter, no separator is required.
+            take_while_whitespace,
         ))(input)?;
-        let result = std::str::from_utf8(value).map_err(crate::error::ParseError::UTF8Error)?;
 
-        Ok((input, Object::LiteralString(result)))
+        Ok((input, Object::LiteralString(value)))
     }
 
-    pub fn parse_hexadecimal_string(input: &'a [u8]) -> ParseResult<Object<'a>> {
+    pub fn parse_hexadecimal_string(input: &'a [u8]) -> ParseResult<'a, Object<'a>> {
         let (input, (value, _)) = tuple((
-            delimited(char('<'), hex_digit1, char('>')),
-            take_while_separator,
+            delimited(char('<'), take_while_hexadecimal, char('>')),
+            take_while_whitespace,
         ))(input)?;
-        let result = std::str::from_utf8(value).map_err(crate::error::ParseError::UTF8Error)?;
 
-        Ok((input, Object::HexadecimalString(result)))
+        Ok((input, Object::HexadecimalString(value)))
     }
 
-    pub fn parse_name(input: &'a [u8]) -> ParseResult<Object<'a>> {
+    pub fn parse_name(input: &'a [u8]) -> ParseResult<'a, Object<'a>> {
+        let (input, name) = Object::parse_name_object(input)?;
+
+        Ok((input, Object::Name(name)))
+    }
+
+    fn parse_name_object(input: &'a [u8]) -> ParseResult<'a, NameObject<'a>> {
         let (input, (_, value, _)) =
-            tuple((char('/'), take_till_whitespace, take_while_separator))(input)?;
-        let result = std::str::from_utf8(value).map_err(crate::error::ParseError::UTF8Error)?;
+            tuple((char('/'), take_till_delimiter, take_while_separator))(input)?;
+        let result = std::str::from_utf8(value).map_err(ParseError::UTF8Error)?;
+
+        Ok((input, NameObject(result)))
+    }
 
-        Ok((input, Object::Name(NameObject(result))))
+    pub fn parse_array(input: &'a [u8]) -> ParseResult<'a, Object<'a>> {
+        Object::parse_array_nested(input, &ParseOptions::default(), 0)
     }
 
-    pub fn parse_array(input: &'a [u8]) -> ParseResult<Object<'a>> {
+    /// Parses an array enclosed in `depth` arrays and dictionaries.
+    fn parse_array_nested(
+        input: &'a [u8],
+        options: &ParseOptions,
+        depth: usize,
+    ) -> ParseResult<'a, Object<'a>> {
         let (outer_input, value) =
             delimited(char('['), take_bracketed(b'[', b']'), char(']'))(input)?;
+        check_depth(options, depth)?;
 
         let mut elements = Vec::new();
         let (mut inner_input, _) = take_while_whitespace(value)?;
+        if inner_input.is_empty() {
+            return Ok((outer_input, Object::Array(elements)));
+        }
 
         loop {
-            let (input, element) = Object::parse(inner_input)?;
+            let (input, element) = Object::parse_nested(inner_input, options, depth + 1)?;
 
             elements.push(element);
             let (input, _) = take_while_whitespace(input)?;
@@ -127,27 +251,30 @@ impl<'a> Object<'a> {
         Ok((outer_input, Object::Array(elements)))
     }
 
-    pub fn parse_dictionary(input: &'a [u8]) -> ParseResult<Object<'a>> {
+    pub fn parse_dictionary(input: &'a [u8]) -> ParseResult<'a, Object<'a>> {
+        Object::parse_dictionary_nested(input, &ParseOptions::default(), 0)
+    }
+
+    /// Parses a dictionary, and the stream following it, enclosed in `depth`
+    /// arrays and dictionaries.
+    fn parse_dictionary_nested(
+        input: &'a [u8],
+        options: &ParseOptions,
+        depth: usize,
+    ) -> ParseResult<'a, Object<'a>> {
         let (outer_input, inner_input) =
             delimited(char('<'), take_bracketed(b'<', b'>'), char('>'))(input)?;
         let (_, inner_input) =
             delimited(char('<'), take_bracketed(b'<', b'>'), char('>'))(inner_input)?;
+        check_depth(options, depth)?;
 
         let mut elements = DictionaryObject::new();
         let (mut inner_input, _) = take_while_whitespace(inner_input)?;
 
         if !inner_input.is_empty() {
             loop {
-                let (input, key_object) = Object::parse_name(inner_input)?;
-                let key_name_object = {
-                    if let Object::Name(name_object) = key_object {
-                        name_object
-                    } else {
-                        unreachable!()
-                    }
-                };
-
-                let (input, value_object) = Object::parse(input)?;
+                let (input, key_name_object) = Object::parse_name_object(inner_input)?;
+                let (input, value_object) = Object::parse_nested(input, options, depth + 1)?;
 
                 elements.insert(key_name_object, value_object);
 
@@ -161,28 +288,27 @@ impl<'a> Object<'a> {
         }
 
         let (outer_input, _) = take_while_whitespace(outer_input)?;
-        let (outer_input, stream) = many0(Object::parse_stream)(outer_input)?;
-        assert!(stream.len() <= 1);
-        let stream = *stream.first().unwrap_or(&"");
+        // A second stream is left unparsed, so the enclosing object fails.
+        let (outer_input, stream) = opt(Object::parse_stream)(outer_input)?;
+        let stream = stream.unwrap_or_default();
 
         Ok((outer_input, Object::Dictionary(elements, stream)))
     }
 
-    pub fn parse_stream(input: &'a [u8]) -> ParseResult<&'a str> {
+    pub fn parse_stream(input: &'a [u8]) -> ParseResult<'a, &'a [u8]> {
         let (input, (_, _, _, stream, _, _)) = tuple((
             tag("stream"),
             take_till_whitespace,
-            newline,
+            end_of_line,
             take_until("endstream"),
             tag("endstream"),
             take_while_separator,
         ))(input)?;
-        let stream = std::str::from_utf8(stream).map_err(crate::error::ParseError::UTF8Error)?;
 
         Ok((input, stream))
     }
 
-    pub fn parse_indirect_reference(input: &'a [u8]) -> ParseResult<Object<'a>> {
+    pub fn parse_indirect_reference(input: &'a [u8]) -> ParseResult<'a, Object<'a>> {
         let (input, (id, _, generation, _, _)) = tuple((
             digit1_u32,
             char(' '),
@@ -194,17 +320,18 @@ impl<'a> Object<'a> {
         Ok((input, Object::IndirectReference { id, generation }))
     }
 
-    pub fn parse_indirect_object(input: &'a [u8]) -> ParseResult<Object<'a>> {
-        let (input, (id, _, generation, _, _, dictionary, _, _)) = tuple((
-            digit1_u32,
-            char(' '),
-            digit1_u32,
-            tag(" obj"),
-            take_while_separator,
-            Object::parse_dictionary,
-            tag("endobj"),
-            take_while_separator,
-        ))(input)?;
+    pub fn parse_indirect_object(input: &'a [u8]) -> ParseResult<'a, Object<'a>> {
+        Object::parse_indirect_object_nested(input, &ParseOptions::default(), 0)
+    }
+
+    fn parse_indirect_object_nested(
+        input: &'a [u8],
+        options: &ParseOptions,
+        depth: usize,
+    ) -> ParseResult<'a, Object<'a>> {
+        let (input, (id, generation, dictionary)) =
+            Object::parse_indirect_object_start(input, options, depth)?;
+        let (input, _) = tuple((tag("endobj"), take_while_separator))(input)?;
 
         Ok((
             input,
@@ -216,20 +343,50 @@ impl<'a> Object<'a> {
         ))
     }
 
-    pub fn parse_comment(input: &'a [u8]) -> ParseResult<Object<'a>> {
+    /// Parses an indirect object up to `endobj`.
+    fn parse_indirect_object_start(
+        input: &'a [u8],
+        options: &ParseOptions,
+        depth: usize,
+    ) -> ParseResult<'a, (u32, u32, Object<'a>)> {
+        let (input, (id, _, generation, _, _, dictionary)) = tuple((
+            digit1_u32,
+            char(' '),
+            digit1_u32,
+            tag(" obj"),
+            take_while_separator,
+            |input| Object::parse_dictionary_nested(input, options, depth),
+        ))(input)?;
+
+        Ok((input, (id, generation, dictionary)))
+    }
+
+    pub fn parse_comment(input: &'a [u8]) -> ParseResult<'a, Object<'a>> {
         let (input, (_, comment, _)) =
             tuple((char('%'), take_till_newline, take_while_separator))(input)?;
-        let comment = std::str::from_utf8(comment).map_err(crate::error::ParseError::UTF8Error)?;
 
         Ok((input, Object::Comment(comment)))
     }
 
-    pub fn parse(input: &'a [u8]) -> ParseResult<Object<'a>> {
+    pub fn parse(input: &'a [u8]) -> ParseResult<'a, Object<'a>> {
+        Object::parse_with(input, &ParseOptions::default())
+    }
+
+    /// Parses an object within the limits of `options`.
+    pub fn parse_with(input: &'a [u8], options: &ParseOptions) -> ParseResult<'a, Object<'a>> {
+        Object::parse_nested(input, options, 0)
+    }
+
+    fn parse_nested(
+        input: &'a [u8],
+        options: &ParseOptions,
+        depth: usize,
+    ) -> ParseResult<'a, Object<'a>> {
         let (input, value_object) = alt((
-            Object::parse_indirect_object,
+            |input| Object::parse_indirect_object_nested(input, options, depth),
             Object::parse_comment,
-            Object::parse_dictionary,
-            Object::parse_array,
+            |input| Object::parse_dictionary_nested(input, options, depth),
+            |input| Object::parse_array_nested(input, options, depth),
             Object::parse_indirect_reference,
             Object::parse_name,
             Object::parse_literal_string,
@@ -244,11 +401,71 @@ impl<'a> Object<'a> {
 
     // Parse PDF indirect object.
     // This function ignores all comments.
-    pub fn parse_body(input: &'a [u8]) -> ParseResult<Object<'a>> {
+    pub fn parse_body(input: &'a [u8]) -> ParseResult<'a, Object<'a>> {
+        Object::parse_body_with(input, &ParseOptions::default())
+    }
+
+    pub fn parse_body_with(input: &'a [u8], options: &ParseOptions) -> ParseResult<'a, Object<'a>> {
+        Object::parse_body_tracked(input, options, &mut Vec::new())
+    }
+
+    /// Parses an indirect object of the body. In lenient mode, a missing `endobj`
+    /// is noted.
+    fn parse_body_tracked(
+        input: &'a [u8],
+        options: &ParseOptions,
+        deviations: &mut Vec<Deviation<'a>>,
+    ) -> ParseResult<'a, Object<'a>> {
         let (input, _) = many0(Object::parse_comment)(input)?;
-        let (input, value_object) = Object::parse_indirect_object(input)?;
+        let (input, (id, generation, dictionary)) =
+            Object::parse_indirect_object_start(input, options, 0)?;
+        let input = match tuple((tag("endobj"), take_while_separator))(input) {
+            Ok((input, _)) => input,
+            Err(nom::Err::Error(_)) if options.lenient => {
+                deviations.push((input, ParseWarningKind::MissingEndobj { id, generation }));
+                input
+            }
+            Err(error) => return Err(error),
+        };
 
-        Ok((input, value_object))
+        Ok((
+            input,
+            Object::IndirectObject {
+                id,
+                generation,
+                dictionary: Box::new(dictionary),
+            },
+        ))
+    }
+
+    /// Parses indirect objects into `body` as `many0(Object::parse_body)` does,
+    /// failing once it holds more than `options.max_objects`.
+    fn parse_objects(
+        mut input: &'a [u8],
+        options: &ParseOptions,
+        body: &mut Vec<Object<'a>>,
+        deviations: &mut Vec<Deviation<'a>>,
+    ) -> ParseResult<'a, ()> {
+        loop {
+            let noted = deviations.len();
+            match Object::parse_body_tracked(input, options, deviations) {
+                Ok((rest, object)) => {
+                    if body.len() >= options.max_objects {
+                        return Err(nom::Err::Failure(ParseError::PDFLimitExceeded(format!(
+                            "more than {} objects",
+                            options.max_objects
+                        ))));
+                    }
+                    body.push(object);
+                    input = rest;
+                }
+                Err(nom::Err::Error(_)) => {
+                    deviations.truncate(noted);
+                    return Ok((input, ()));
+                }
+                Err(error) => return Err(error),
+            }
+        }
     }
 
     // pub fn parse(input: &'a [u8]) -> ParseResult<Vec<Object<'a>>> {
@@ -266,7 +483,7 @@ impl<'a> Object<'a> {
     //     }
 
     //     if result.is_empty() {
-    //         Err(nom::Err::Error(crate::error::ParseError::InvalidPDFObject))
+    //         Err(nom::Err::Error(ParseError::InvalidPDFObject))
     //     } else {
     //         Ok((remaining, result))
     //     }
@@ -275,21 +492,72 @@ impl<'a> Object<'a> {
 
 // TODO: implement CrossReferenceTable::parse
 impl CrossReferenceTable {
-    pub fn parse(input: &[u8]) -> ParseResult<CrossReferenceTable> {
-        let (input, (_, _, id, _, count, _, entries)) = tuple((
-            tag("xref"),
-            newline,
-            digit1_u32,
-            char(' '),
-            digit1_u32,
-            newline,
-            CrossReferenceEntry::parse_entries,
-        ))(input)?;
+    pub fn parse(input: &[u8]) -> ParseResult<'_, CrossReferenceTable> {
+        let (input, _) = tuple((tag("xref"), end_of_line))(input)?;
+        CrossReferenceTable::parse_subsection(input)
+    }
+
+    /// Parses an `xref` section, which may hold several subsections,
+    /// e.g. the objects changed by an incremental update.
+    pub fn parse_section(input: &[u8]) -> ParseResult<'_, Vec<CrossReferenceTable>> {
+        CrossReferenceTable::parse_section_with(input, &ParseOptions::default())
+    }
+
+    pub fn parse_section_with<'a>(
+        input: &'a [u8],
+        options: &ParseOptions,
+    ) -> ParseResult<'a, Vec<CrossReferenceTable>> {
+        CrossReferenceTable::parse_section_tracked(input, options, &mut Vec::new())
+    }
+
+    /// Parses consecutive `xref` sections as `many0(parse_section)` does.
+    fn parse_sections<'a>(
+        input: &'a [u8],
+        options: &ParseOptions,
+        deviations: &mut Vec<Deviation<'a>>,
+    ) -> ParseResult<'a, Vec<Vec<CrossReferenceTable>>> {
+        many0_tracked(input, deviations, |input, deviations| {
+            CrossReferenceTable::parse_section_tracked(input, options, deviations)
+        })
+    }
+
+    fn parse_section_tracked<'a>(
+        input: &'a [u8],
+        options: &ParseOptions,
+        deviations: &mut Vec<Deviation<'a>>,
+    ) -> ParseResult<'a, Vec<CrossReferenceTable>> {
+        let (input, _) = tuple((tag("xref"), end_of_line))(input)?;
+        let (input, subsections) = many0_tracked(input, deviations, |input, deviations| {
+            CrossReferenceTable::parse_subsection_tracked(input, options, deviations)
+        })?;
+        if subsections.is_empty() {
+            return Err(nom::Err::Error(ParseError::InvalidPDFXrefTable));
+        }
+
+        Ok((input, subsections))
+    }
+
+    pub fn parse_subsection(input: &[u8]) -> ParseResult<'_, CrossReferenceTable> {
+        CrossReferenceTable::parse_subsection_tracked(
+            input,
+            &ParseOptions::default(),
+            &mut Vec::new(),
+        )
+    }
+
+    fn parse_subsection_tracked<'a>(
+        input: &'a [u8],
+        options: &ParseOptions,
+        deviations: &mut Vec<Deviation<'a>>,
+    ) -> ParseResult<'a, CrossReferenceTable> {
+        let (input, (id, _, count, _)) =
+            tuple((digit1_u32, char(' '), digit1_u32, end_of_line))(input)?;
+        let (input, entries) = many0_tracked(input, deviations, |input, deviations| {
+            CrossReferenceEntry::parse_tracked(input, options, deviations)
+        })?;
 
         if count != entries.len() as u32 {
-            Err(nom::Err::Error(
-                crate::error::ParseError::InvalidPDFXrefTable,
-            ))
+            Err(nom::Err::Error(ParseError::InvalidPDFXrefTable))
         } else {
             Ok((input, CrossReferenceTable { id, count, entries }))
         }
@@ -297,20 +565,23 @@ impl CrossReferenceTable {
 }
 
 impl CrossReferenceEntry {
-    pub fn parse(input: &[u8]) -> ParseResult<CrossReferenceEntry> {
-        let (input, offset) = digit1_u32_validate_length(input, 10)?;
+    pub fn parse(input: &[u8]) -> ParseResult<'_, CrossReferenceEntry> {
+        CrossReferenceEntry::parse_tracked(input, &ParseOptions::default(), &mut Vec::new())
+    }
+
+    fn parse_tracked<'a>(
+        input: &'a [u8],
+        options: &ParseOptions,
+        deviations: &mut Vec<Deviation<'a>>,
+    ) -> ParseResult<'a, CrossReferenceEntry> {
+        let (input, offset) = xref_field(input, 10, options, deviations)?;
         let (input, _) = take_while_separator(input)?;
-        let (input, generation) = digit1_u32_validate_length(input, 5)?;
+        let (input, generation) = xref_field(input, 5, options, deviations)?;
         let (input, _) = take_while_separator(input)?;
-        let (input, in_use) = alt((char('n'), char('f')))(input)?;
+        let (input, free) = alt((value(false, char('n')), value(true, char('f'))))(input)?;
+        // Entries end with SP CR, SP LF or CR LF.
         let (input, _) = take_till_newline(input)?;
-        let (input, _) = many_m_n(0, 1, newline)(input)?;
-
-        let free = match in_use {
-            'n' => false,
-            'f' => true,
-            _ => unreachable!(),
-        };
+        let (input, _) = opt(end_of_line)(input)?;
 
         Ok((
             input,
@@ -322,23 +593,12 @@ impl CrossReferenceEntry {
         ))
     }
 
-    pub fn parse_entries(input: &[u8]) -> ParseResult<Vec<CrossReferenceEntry>> {
+    pub fn parse_entries(input: &[u8]) -> ParseResult<'_, Vec<CrossReferenceEntry>> {
         let mut entries = Vec::new();
         let mut remaining: &[u8] = input;
-        loop {
-            // println!(
-            //     "before input: {:?}",
-            //     std::str::from_utf8(remaining).unwrap()
-            // );
-            let result = CrossReferenceEntry::parse(remaining);
-            if result.is_err() {
-                // println!("breaking: {:?}", entries.len());
-                break;
-            } else {
-                let (input, entry) = result.unwrap();
-                entries.push(entry);
-                remaining = input;
-            }
+        while let Ok((input, entry)) = CrossReferenceEntry::parse(remaining) {
+            entries.push(entry);
+            remaining = input;
         }
 
         Ok((remaining, entries))
@@ -347,20 +607,37 @@ impl CrossReferenceEntry {
 
 // TODO: implement Trailer::parse
 impl<'a> Trailer<'a> {
-    pub fn parse(input: &'a [u8]) -> ParseResult<Trailer<'a>> {
-        let (input, (_, _, dictionary, _, _, startxref, _, _, _)) = tuple((
+    pub fn parse(input: &'a [u8]) -> ParseResult<'a, Trailer<'a>> {
+        Trailer::parse_with(input, &ParseOptions::default())
+    }
+
+    pub fn parse_with(input: &'a [u8], options: &ParseOptions) -> ParseResult<'a, Trailer<'a>> {
+        Trailer::parse_tracked(input, options, &mut Vec::new())
+    }
+
+    /// Parses a trailer. In lenient mode, a missing `%%EOF` is noted.
+    fn parse_tracked(
+        input: &'a [u8],
+        options: &ParseOptions,
+        deviations: &mut Vec<Deviation<'a>>,
+    ) -> ParseResult<'a, Trailer<'a>> {
+        let (input, (_, _, dictionary, _, _, startxref, _)) = tuple((
             tag("trailer"),
             take_while_separator,
-            crate::object::Object::parse_dictionary,
+            |input| Object::parse_dictionary_nested(input, options, 0),
             tag("startxref"),
             take_while_separator,
             digit1_u32,
             take_while_separator,
-            tag("%%EOF"),
-            take_while_separator,
         ))(input)?;
-
-        debug_assert!(input.is_empty()); // %%EOF should be the last thing in the file
+        let input = match tuple((tag("%%EOF"), take_while_separator))(input) {
+            Ok((input, _)) => input,
+            Err(nom::Err::Error(_)) if options.lenient => {
+                deviations.push((input, ParseWarningKind::MissingEof));
+                input
+            }
+            Err(error) => return Err(error),
+        };
 
         Ok((
             input,
@@ -0,0 +1,151 @@
+use crate::document::Page;
+use crate::error::ParseError;
+use crate::interpreter::{Color, GraphicsState, Rect, TextShow, Visitor};
+
+/// Fraction of the font size above which the gap between two glyphs is read as a space.
+/// Kerning is usually well below it, while word gaps made with `TJ` are above it.
+const SPACE_THRESHOLD: f32 = 0.2;
+/// Fraction of the font size within which two baselines are read as the same line.
+const LINE_THRESHOLD: f32 = 0.5;
+
+/// A glyph placed on the page, in page coordinates.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextGlyph {
+    pub code: u32,
+    /// The Unicode text of the glyph, U+FFFD if it is unknown.
+    pub text: String,
+    /// Where the glyph starts on the baseline.
+    pub origin: (f32, f32),
+    /// Where the next glyph would start without extra spacing.
+    pub end: (f32, f32),
+    /// The box from the font descent to its ascent, over the advance of the glyph.
+    pub bbox: Rect,
+}
+
+/// A run of glyphs shown by one `Tj`, `TJ`, `'` or `"` operator.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextSpan {
+    pub text: String,
+    /// The union of the glyph boxes.
+    pub bbox: Rect,
+    /// The `/BaseFont` of the font, if any.
+    pub font_name: Option<String>,
+    /// The font size in page space, i.e. the `Tf` size scaled by the text matrix and CTM.
+    pub font_size: f32,
+    pub fill_color: Color,
+    pub glyphs: Vec<TextGlyph>,
+}
+
+/// Collects the spans shown by a content stream.
+#[derive(Debug, Default)]
+struct SpanCollector {
+    spans: Vec<TextSpan>,
+}
+
+impl Visitor for SpanCollector {
+    fn text(&mut self, text: &TextShow, state: &GraphicsState) {
+        let font = state.text_state.font.clone().unwrap_or_default();
+        let glyphs: Vec<TextGlyph> = text
+            .glyphs
+            .iter()
+            .map(|glyph| {
+                let ascent = font.ascent * font.font_matrix.d;
+                let descent = font.descent * font.font_matrix.d;
+                TextGlyph {
+                    code: glyph.code,
+                    text: font.to_unicode(glyph.code),
+                    origin: glyph.matrix.transform(0.0, 0.0),
+                    end: glyph.matrix.transform(glyph.width, 0.0),
+                    bbox: Rect::new(0.0, descent, glyph.width, ascent).transform(&glyph.matrix),
+                }
+            })
+            .collect();
+        let bbox = match glyphs
+            .iter()
+            .map(|glyph| glyph.bbox)
+            .reduce(|a, b| a.union(&b))
+        {
+            Some(bbox) => bbox,
+            None => return,
+        };
+
+        self.spans.push(TextSpan {
+            text: glyphs.iter().map(|glyph| glyph.text.as_str()).collect(),
+            bbox,
+            font_name: font.base_font.clone(),
+            font_size: text.glyphs[0].matrix.c.hypot(text.glyphs[0].matrix.d),
+            fill_color: state.fill_color.clone(),
+            glyphs,
+        });
+    }
+}
+
+/// A glyph with the size of its span, for layout.
+struct PlacedGlyph<'s> {
+    glyph: &'s TextGlyph,
+    size: f32,
+}
+
+/// Groups glyphs into lines by baseline, orders lines top to bottom and glyphs
+/// left to right, and inserts spaces where glyphs are far apart.
+fn layout(spans: &[TextSpan]) -> String {
+    let mut lines: Vec<(f32, Vec<PlacedGlyph>)> = Vec::new();
+    for span in spans {
+        for glyph in &span.glyphs {
+            let (_, y) = glyph.origin;
+            let tolerance = span.font_size * LINE_THRESHOLD;
+            let glyph = PlacedGlyph {
+                glyph,
+                size: span.font_size,
+            };
+            match lines
+                .iter_mut()
+                .find(|(baseline, _)| (baseline - y).abs() <= tolerance)
+            {
+                Some((_, line)) => line.push(glyph),
+                None => lines.push((y, vec![glyph])),
+            }
+        }
+    }
+    lines.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+
+    let mut result = Vec::new();
+    for (_, mut line) in lines {
+        line.sort_by(|a, b| a.glyph.origin.0.total_cmp(&b.glyph.origin.0));
+        let mut text = String::new();
+        let mut previous: Option<&PlacedGlyph> = None;
+        for placed in &line {
+            if let Some(previous) = previous {
+                let gap = placed.glyph.origin.0 - previous.glyph.end.0;
+                if gap > previous.size.max(placed.size) * SPACE_THRESHOLD
+                    && !text.ends_with(char::is_whitespace)
+                    && !placed.glyph.text.starts_with(char::is_whitespace)
+                {
+                    text.push(' ');
+                }
+            }
+            text.push_str(&placed.glyph.text);
+            previous = Some(placed);
+        }
+        result.push(text);
+    }
+    result.join("\n")
+}
+
+impl<'p, 'a> Page<'p, 'a> {
+    /// Returns the text runs of the page in content stream order, with their
+    /// positions, fonts and colours.
+    pub fn text_spans(&self) -> Result<impl Iterator<Item = TextSpan>, ParseError> {
+        let mut collector = SpanCollector::default();
+        self.interpret(&mut collector)?;
+        Ok(collector.spans.into_iter())
+    }
+
+    /// Extracts the text of the page in reading order.
+    /// Spaces and line breaks are inferred from glyph positions, so words separated
+    /// only by `TJ` adjustments or text positioning are still kept apart.
+    pub fn extract_text(&self) -> Result<String, ParseError> {
+        let spans: Vec<TextSpan> = self.text_spans()?.collect();
+        Ok(layout(&spans))
+    }
+}
@@ -1,9 +1,11 @@
+use std::borrow::Cow;
+
 use nom::{
     branch::alt,
     bytes::complete::{tag, take_till, take_while, take_while1},
-    character::{complete::char, is_newline},
+    character::complete::char,
     character::complete::digit1,
-    combinator::{eof, recognize},
+    combinator::{eof, recognize, value},
     error::ErrorKind,
     number::complete::float,
     sequence::pair,
@@ -14,22 +16,19 @@ use crate::error::{ParseError, ParseResult};
 /// What PDF considers white space characters.
 pub const WHITE_SPACE_CHARS: [u8; 6] = [0x00, 0x09, 0x0A, 0x0C, 0x0D, 0x20];
 
-pub fn bool(input: &[u8]) -> ParseResult<bool> {
-    let (input, res) = alt((tag("true"), tag("false")))(input)?;
-    let res = match res {
-        b"true" => true,
-        b"false" => false,
-        _ => unreachable!(),
-    };
-    Ok((input, res))
+/// What PDF considers delimiter characters.
+pub const DELIMITER_CHARS: [u8; 10] = *b"()<>[]{}/%";
+
+pub fn bool(input: &[u8]) -> ParseResult<'_, bool> {
+    alt((value(true, tag("true")), value(false, tag("false"))))(input)
 }
 
-pub fn digit1_u32(input: &[u8]) -> ParseResult<u32> {
+pub fn digit1_u32(input: &[u8]) -> ParseResult<'_, u32> {
     let (input, digits) = digit1_u32_validate_length(input, 0)?;
     Ok((input, digits))
 }
 
-pub fn digit1_u32_validate_length(input: &[u8], length: usize) -> ParseResult<u32> {
+pub fn digit1_u32_validate_length(input: &[u8], length: usize) -> ParseResult<'_, u32> {
     let (input, digits) = digit1(input)?;
     // Convert the &[u8] to a str.
     let digits = std::str::from_utf8(digits).map_err(ParseError::UTF8Error)?;
@@ -41,7 +40,7 @@ pub fn digit1_u32_validate_length(input: &[u8], length: usize) -> ParseResult<u3
     Ok((input, digits))
 }
 
-pub fn digit1_i32(input: &[u8]) -> ParseResult<i32> {
+pub fn digit1_i32(input: &[u8]) -> ParseResult<'_, i32> {
     let (input, digits) = alt((
         recognize(pair(char('+'), digit1)),
         recognize(pair(char('-'), digit1)),
@@ -54,7 +53,7 @@ pub fn digit1_i32(input: &[u8]) -> ParseResult<i32> {
     Ok((input, digits))
 }
 
-pub fn float_f32(input: &[u8]) -> ParseResult<f32> {
+pub fn float_f32(input: &[u8]) -> ParseResult<'_, f32> {
     let (input, digits) = alt((
         recognize(pair(char('+'), float)),
         recognize(pair(char('-'), float)),
@@ -67,26 +66,51 @@ pub fn float_f32(input: &[u8]) -> ParseResult<f32> {
     Ok((input, digits))
 }
 
-pub fn take_till_whitespace(input: &[u8]) -> ParseResult<&[u8]> {
+pub fn take_till_whitespace(input: &[u8]) -> ParseResult<'_, &[u8]> {
     take_till(|c| WHITE_SPACE_CHARS.contains(&c))(input)
 }
 
-pub fn take_while_whitespace(input: &[u8]) -> ParseResult<&[u8]> {
+// Takes a run of regular characters, i.e. stops at white space or a delimiter.
+pub fn take_till_delimiter(input: &[u8]) -> ParseResult<'_, &[u8]> {
+    take_till(|c| WHITE_SPACE_CHARS.contains(&c) || DELIMITER_CHARS.contains(&c))(input)
+}
+
+pub fn take_while_whitespace(input: &[u8]) -> ParseResult<'_, &[u8]> {
     take_while(|c| WHITE_SPACE_CHARS.contains(&c))(input)
 }
 
-pub fn take_while1_whitespace(input: &[u8]) -> ParseResult<&[u8]> {
+// Takes the digits of a hexadecimal string, which may be empty and contain white
+// space.
+pub fn take_while_hexadecimal(input: &[u8]) -> ParseResult<'_, &[u8]> {
+    take_while(|c: u8| c.is_ascii_hexdigit() || WHITE_SPACE_CHARS.contains(&c))(input)
+}
+
+pub fn take_while1_whitespace(input: &[u8]) -> ParseResult<'_, &[u8]> {
     take_while1(|c| WHITE_SPACE_CHARS.contains(&c))(input)
 }
 
-// Some objects must be separated by white space and eof.
+// Some objects must be separated by white space, a delimiter or eof.
 // e.g., 'true' should be true, 'truee' should return an error.
-pub fn take_while_separator(input: &[u8]) -> ParseResult<&[u8]> {
-    alt((take_while1_whitespace, eof))(input)
+// A delimiter is not consumed, so '(a)(b)' parses as two strings.
+pub fn take_while_separator(input: &[u8]) -> ParseResult<'_, &[u8]> {
+    alt((take_while1_whitespace, eof, peek_delimiter))(input)
+}
+
+fn peek_delimiter(input: &[u8]) -> ParseResult<'_, &[u8]> {
+    match input.first() {
+        Some(c) if DELIMITER_CHARS.contains(c) => Ok((input, &input[..0])),
+        _ => Err(ParseError::NomError(ErrorKind::OneOf).into()),
+    }
+}
+
+/// Takes the rest of a line, i.e. stops at CR or LF.
+pub fn take_till_newline(input: &[u8]) -> ParseResult<'_, &[u8]> {
+    take_till(|c| c == b'\r' || c == b'\n')(input)
 }
 
-pub fn take_till_newline(input: &[u8]) -> ParseResult<&[u8]> {
-    take_till(is_newline)(input)
+/// Reads an end-of-line marker: CR LF, LF or CR.
+pub fn end_of_line(input: &[u8]) -> ParseResult<'_, &[u8]> {
+    alt((tag("\r\n"), tag("\n"), tag("\r")))(input)
 }
 
 // fn take_bracketed<'a>(input: &'a [u8], opening: &'a [u8], closing: &'a [u8]) -> ParseResult<'a, &'a [u8]> {
@@ -101,8 +125,20 @@ pub fn take_till_newline(input: &[u8]) -> ParseResult<&[u8]> {
 pub fn take_bracketed(opening: u8, closing: u8) -> impl Fn(&[u8]) -> ParseResult<&[u8]> {
     move |i: &[u8]| {
         let mut bracket_counter = 0;
+        // Literal strings may contain escaped, unbalanced parentheses such as `\(`.
+        let mut escaped = false;
 
         for (index, x) in i.iter().enumerate() {
+            if opening == b'(' {
+                if escaped {
+                    escaped = false;
+                    continue;
+                }
+                if *x == b'\\' {
+                    escaped = true;
+                    continue;
+                }
+            }
             match *x {
                 x if x == opening => {
                     bracket_counter += 1;
@@ -125,3 +161,92 @@ pub fn take_bracketed(opening: u8, closing: u8) -> impl Fn(&[u8]) -> ParseResult
         }
     }
 }
+
+/// Decodes the escape sequences of a literal string, e.g. `\n`, `\(` and `\053`.
+/// End-of-line markers are normalized to a single line feed.
+pub fn unescape_literal_string(input: &[u8]) -> Vec<u8> {
+    let mut result = Vec::with_capacity(input.len());
+    let mut iter = input.iter().copied().peekable();
+    while let Some(c) = iter.next() {
+        match c {
+            b'\\' => match iter.next() {
+                Some(b'n') => result.push(b'\n'),
+                Some(b'r') => result.push(b'\r'),
+                Some(b't') => result.push(b'\t'),
+                Some(b'b') => result.push(0x08),
+                Some(b'f') => result.push(0x0C),
+                Some(c @ b'0'..=b'7') => {
+                    let mut value = (c - b'0') as u32;
+                    for _ in 0..2 {
+                        match iter.peek() {
+                            Some(&c @ b'0'..=b'7') => {
+                                value = value * 8 + (c - b'0') as u32;
+                                iter.next();
+                            }
+                            _ => break,
+                        }
+                    }
+                    // High-order overflow is ignored.
+                    result.push(value as u8);
+                }
+                // A backslash at the end of a line continues the string on the next one.
+                Some(b'\r') if iter.peek() == Some(&b'\n') => {
+                    iter.next();
+                }
+                Some(b'\r') | Some(b'\n') => {}
+                // Otherwise the backslash is ignored, as in `\(`, `\)` and `\\`.
+                Some(c) => result.push(c),
+                None => {}
+            },
+            b'\r' => {
+                if iter.peek() == Some(&b'\n') {
+                    iter.next();
+                }
+                result.push(b'\n');
+            }
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+/// Decodes the digits of a hexadecimal string, ignoring white space.
+/// A final odd digit behaves as if it were followed by 0.
+pub fn decode_hexadecimal_string(input: &[u8]) -> Vec<u8> {
+    let digits: Vec<u8> = input
+        .iter()
+        .filter(|c| c.is_ascii_hexdigit())
+        .map(|&c| crate::filter::hex_value(c))
+        .collect();
+    digits
+        .chunks(2)
+        .map(|pair| pair[0] << 4 | pair.get(1).copied().unwrap_or(0))
+        .collect()
+}
+
+/// Decodes the `#xx` escapes of a name, e.g. `Java#53cript` or `application#2Fpdf`.
+pub fn decode_name(name: &str) -> Cow<'_, str> {
+    if !name.contains('#') {
+        return Cow::Borrowed(name);
+    }
+    let bytes = name.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escape = bytes
+            .get(i + 1..i + 3)
+            .filter(|_| bytes[i] == b'#')
+            .and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+        match escape {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    Cow::Owned(String::from_utf8_lossy(&decoded).into_owned())
+}
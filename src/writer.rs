@@ -0,0 +1,352 @@
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+
+use crate::object::{DictionaryObject, IndirectReference, Object, PDF};
+
+/// Serializes `object` in the syntax read by the parser.
+/// Dictionary keys are sorted so the output does not depend on hashing.
+pub fn write_object(object: &Object, out: &mut Vec<u8>) {
+    match object {
+        Object::Boolean(b) => write!(out, "{}", b).unwrap(),
+        Object::Integer(i) => write!(out, "{}", i).unwrap(),
+        Object::Real(f) => write!(out, "{}", f).unwrap(),
+        Object::LiteralString(s) => {
+            out.push(b'(');
+            out.extend_from_slice(s);
+            out.push(b')');
+        }
+        Object::HexadecimalString(s) => {
+            out.push(b'<');
+            out.extend_from_slice(s);
+            out.push(b'>');
+        }
+        Object::Name(name) => write!(out, "/{}", name.0).unwrap(),
+        Object::Array(array) => {
+            out.push(b'[');
+            for (i, element) in array.iter().enumerate() {
+                if i > 0 {
+                    out.push(b' ');
+                }
+                write_object(element, out);
+            }
+            out.push(b']');
+        }
+        Object::Dictionary(dictionary, data) | Object::Stream(dictionary, data) => {
+            write_dictionary(dictionary, &HashMap::new(), data, out)
+        }
+        Object::Null => out.extend_from_slice(b"null"),
+        Object::Comment(s) => {
+            out.push(b'%');
+            out.extend_from_slice(s);
+            out.push(b'\n');
+        }
+        Object::IndirectReference { id, generation } => {
+            write!(out, "{} {} R", id, generation).unwrap()
+        }
+        Object::IndirectObject {
+            id,
+            generation,
+            dictionary,
+        } => {
+            writeln!(out, "{} {} obj", id, generation).unwrap();
+            write_object(dictionary, out);
+            out.extend_from_slice(b"\nendobj\n");
+        }
+    }
+}
+
+/// Serializes a dictionary with some entries replaced (`Some`) or removed (`None`),
+/// followed by its stream if it has data or a `/Length`. `/Length` is rewritten to
+/// match the data.
+fn write_dictionary(
+    dictionary: &DictionaryObject,
+    overrides: &HashMap<String, Option<Vec<u8>>>,
+    data: &[u8],
+    out: &mut Vec<u8>,
+) {
+    let is_stream = !data.is_empty() || dictionary.contains_key("Length");
+    let mut entries: BTreeMap<&str, Vec<u8>> = BTreeMap::new();
+    for (key, value) in dictionary {
+        let mut bytes = Vec::new();
+        write_object(value, &mut bytes);
+        entries.insert(key.0, bytes);
+    }
+    for (key, value) in overrides {
+        match value {
+            Some(bytes) => entries.insert(key, bytes.clone()),
+            None => entries.remove(key.as_str()),
+        };
+    }
+    if is_stream {
+        entries.insert("Length", data.len().to_string().into_bytes());
+    }
+
+    out.extend_from_slice(b"<<");
+    for (key, value) in entries {
+        write!(out, " /{} ", key).unwrap();
+        out.extend_from_slice(&value);
+    }
+    out.extend_from_slice(b" >>");
+    if is_stream {
+        out.extend_from_slice(b"\nstream\n");
+        out.extend_from_slice(data);
+        out.extend_from_slice(b"\nendstream");
+    }
+}
+
+/// Makes an indirect reference object.
+pub fn reference_object<'a>(reference: IndirectReference) -> Object<'a> {
+    Object::IndirectReference {
+        id: reference.id,
+        generation: reference.generation,
+    }
+}
+
+/// Encodes the digits of a hexadecimal string object.
+pub fn hex_string(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02X}", b)).collect()
+}
+
+/// Encodes a text string, as plain bytes if it is ASCII and as UTF-16BE otherwise.
+pub fn encode_text_string(text: &str) -> Vec<u8> {
+    if text.is_ascii() {
+        return text.as_bytes().to_vec();
+    }
+    let mut bytes = vec![0xFE, 0xFF];
+    for unit in text.encode_utf16() {
+        bytes.extend_from_slice(&unit.to_be_bytes());
+    }
+    bytes
+}
+
+/// Changes to a document, saved either as an incremental update appended to the
+/// original file or as a full rewrite.
+#[derive(Debug, Clone)]
+pub struct Writer<'p, 'a> {
+    pdf: &'p PDF<'a>,
+    /// Replaced and removed entries of existing dictionaries, serialized.
+    edits: BTreeMap<IndirectReference, HashMap<String, Option<Vec<u8>>>>,
+    /// New stream data of existing streams.
+    data: BTreeMap<IndirectReference, Vec<u8>>,
+    /// New objects, serialized.
+    objects: BTreeMap<IndirectReference, Vec<u8>>,
+    next_id: u32,
+}
+
+impl<'p, 'a> Writer<'p, 'a> {
+    pub fn new(pdf: &'p PDF<'a>) -> Self {
+        let size = pdf
+            .trailer
+            .dictionary
+            .get("Size")
+            .and_then(Object::as_i32)
+            .unwrap_or(0)
+            .max(0) as u32;
+        let last = pdf
+            .body
+            .iter()
+            .filter_map(|object| match object {
+                Object::IndirectObject { id, .. } => Some(*id),
+                _ => None,
+            })
+            .max()
+            .unwrap_or(0);
+        Self {
+            pdf,
+            edits: BTreeMap::new(),
+            data: BTreeMap::new(),
+            objects: BTreeMap::new(),
+            next_id: size.max(last + 1),
+        }
+    }
+
+    pub fn pdf(&self) -> &'p PDF<'a> {
+        self.pdf
+    }
+
+    /// Adds a new object and returns its reference.
+    pub fn add(&mut self, object: &Object) -> IndirectReference {
+        let reference = self.reserve();
+        self.insert(reference, object);
+        reference
+    }
+
+    /// Reserves the reference of a new object, defined later with `insert`, so
+    /// that new objects can refer to each other.
+    pub fn reserve(&mut self) -> IndirectReference {
+        let reference = IndirectReference {
+            id: self.next_id,
+            generation: 0,
+        };
+        self.next_id += 1;
+        reference
+    }
+
+    /// Defines the new object `reference`, obtained from `reserve`.
+    pub fn insert(&mut self, reference: IndirectReference, object: &Object) {
+        let mut bytes = Vec::new();
+        write_object(object, &mut bytes);
+        self.objects.insert(reference, bytes);
+    }
+
+    /// Sets `key` of the dictionary or stream `reference` to `value`.
+    pub fn set(&mut self, reference: IndirectReference, key: &str, value: &Object) {
+        let mut bytes = Vec::new();
+        write_object(value, &mut bytes);
+        self.edits
+            .entry(reference)
+            .or_default()
+            .insert(key.to_string(), Some(bytes));
+    }
+
+    /// Removes `key` from the dictionary or stream `reference`.
+    pub fn remove(&mut self, reference: IndirectReference, key: &str) {
+        self.edits
+            .entry(reference)
+            .or_default()
+            .insert(key.to_string(), None);
+    }
+
+    /// Replaces the data of the stream `reference`, which must already be encoded
+    /// with its `/Filter`.
+    pub fn set_data(&mut self, reference: IndirectReference, data: Vec<u8>) {
+        self.data.insert(reference, data);
+    }
+
+    /// Appends the changed and new objects to `original`, the bytes `pdf` was
+    /// parsed from, with a cross-reference section pointing back with `/Prev`.
+    pub fn incremental(&self, original: &[u8]) -> Vec<u8> {
+        let mut out = original.to_vec();
+        if !out.ends_with(b"\n") {
+            out.push(b'\n');
+        }
+        let mut offsets = BTreeMap::new();
+        let changed: Vec<IndirectReference> = self
+            .edits
+            .keys()
+            .chain(self.data.keys())
+            .copied()
+            .filter(|reference| self.pdf.get_object(*reference).is_some())
+            .collect();
+        for reference in changed {
+            if let Entry::Vacant(entry) = offsets.entry(reference) {
+                entry.insert(out.len());
+                self.write_existing(reference, &mut out);
+            }
+        }
+        for (reference, bytes) in &self.objects {
+            offsets.insert(*reference, out.len());
+            write_indirect(*reference, bytes, &mut out);
+        }
+
+        let xref = out.len();
+        out.extend_from_slice(b"xref\n");
+        let references: Vec<IndirectReference> = offsets.keys().copied().collect();
+        for run in references.chunk_by(|a, b| a.id + 1 == b.id) {
+            writeln!(out, "{} {}", run[0].id, run.len()).unwrap();
+            for reference in run {
+                writeln!(
+                    out,
+                    "{:010} {:05} n ",
+                    offsets[reference], reference.generation
+                )
+                .unwrap();
+            }
+        }
+        let prev = self.pdf.trailer.startxref.to_string().into_bytes();
+        self.write_trailer(xref, Some(prev), &mut out);
+        out
+    }
+
+    /// Writes the whole document anew: the latest definition of every object with
+    /// the changes applied, then the new objects.
+    pub fn rewrite(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        let header = self.pdf.header;
+        writeln!(out, "%PDF-{}.{}", header.major, header.minor).unwrap();
+        out.extend_from_slice(b"%\xe2\xe3\xcf\xd3\n");
+
+        let mut offsets = BTreeMap::new();
+        let mut references: Vec<IndirectReference> = self
+            .pdf
+            .body
+            .iter()
+            .filter_map(|object| match object {
+                Object::IndirectObject { id, generation, .. } => Some(IndirectReference {
+                    id: *id,
+                    generation: *generation,
+                }),
+                _ => None,
+            })
+            .collect();
+        references.sort();
+        references.dedup();
+        for reference in references {
+            offsets.insert(reference.id, (out.len(), reference.generation));
+            self.write_existing(reference, &mut out);
+        }
+        for (reference, bytes) in &self.objects {
+            offsets.insert(reference.id, (out.len(), reference.generation));
+            write_indirect(*reference, bytes, &mut out);
+        }
+
+        let xref = out.len();
+        writeln!(out, "xref\n0 {}", self.next_id).unwrap();
+        for id in 0..self.next_id {
+            match offsets.get(&id) {
+                Some((offset, generation)) => {
+                    writeln!(out, "{:010} {:05} n ", offset, generation).unwrap()
+                }
+                None => out.extend_from_slice(b"0000000000 65535 f \n"),
+            }
+        }
+        self.write_trailer(xref, None, &mut out);
+        out
+    }
+
+    fn write_existing(&self, reference: IndirectReference, out: &mut Vec<u8>) {
+        let object = match self.pdf.get_object(reference) {
+            Some(object) => object,
+            None => return,
+        };
+        let mut bytes = Vec::new();
+        match object {
+            Object::Dictionary(dictionary, data) | Object::Stream(dictionary, data) => {
+                let no_edits = HashMap::new();
+                let overrides = self.edits.get(&reference).unwrap_or(&no_edits);
+                let data = self.data.get(&reference).map_or(*data, Vec::as_slice);
+                write_dictionary(dictionary, overrides, data, &mut bytes);
+            }
+            object => write_object(object, &mut bytes),
+        }
+        write_indirect(reference, &bytes, out);
+    }
+
+    /// Writes the trailer, keeping `/Root`, `/Info` and `/ID` of the original.
+    fn write_trailer(&self, xref: usize, prev: Option<Vec<u8>>, out: &mut Vec<u8>) {
+        let mut overrides = HashMap::new();
+        overrides.insert(
+            "Size".to_string(),
+            Some(self.next_id.to_string().into_bytes()),
+        );
+        overrides.insert("Prev".to_string(), prev);
+        overrides.insert("XRefStm".to_string(), None);
+        let empty = DictionaryObject::new();
+        let dictionary = self
+            .pdf
+            .trailer
+            .dictionary
+            .as_dictionary()
+            .unwrap_or(&empty);
+        out.extend_from_slice(b"trailer\n");
+        write_dictionary(dictionary, &overrides, &[], out);
+        writeln!(out, "\nstartxref\n{}\n%%EOF", xref).unwrap();
+    }
+}
+
+fn write_indirect(reference: IndirectReference, bytes: &[u8], out: &mut Vec<u8>) {
+    writeln!(out, "{} {} obj", reference.id, reference.generation).unwrap();
+    out.extend_from_slice(bytes);
+    out.extend_from_slice(b"\nendobj\n");
+}
@@ -0,0 +1,256 @@
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+use crate::error::ParseError;
+use crate::object::{IndirectReference, Object, PDF};
+
+/// A stream of the `/XFA` entry. The XML of a form may be split across streams
+/// anywhere, not only between packets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XfaPart {
+    /// The name paired with the stream, `None` if `/XFA` is a single stream.
+    pub name: Option<String>,
+    pub reference: Option<IndirectReference>,
+    /// The decoded data. Streams that cannot be decoded are kept raw.
+    pub data: Vec<u8>,
+}
+
+/// A packet of the XDP document, i.e. a child element of `<xdp:xdp>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XfaPacket {
+    /// The local name of the element, e.g. `template` for `<xfa:template>`.
+    pub name: String,
+    /// The XML of the element, from its start tag to its end tag.
+    pub xml: String,
+}
+
+/// A `<script>` element.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XfaScript {
+    /// The enclosing elements from the packet down, with their `name` attribute
+    /// if any, e.g. `template/subform[form1]/field[total]/event/script`.
+    pub path: String,
+    /// The byte offset of the start tag in the reassembled XML.
+    pub offset: usize,
+    /// `contentType`, e.g. `application/x-javascript` or `application/x-formcalc`.
+    pub content_type: Option<String>,
+    /// `runAt`: `client`, `server` or `both`.
+    pub run_at: Option<String>,
+    pub source: String,
+}
+
+/// The XFA form of a document, from `/AcroForm /XFA`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Xfa {
+    pub parts: Vec<XfaPart>,
+}
+
+impl<'a> PDF<'a> {
+    /// Reads the XFA streams of the interactive form, `None` if there are none.
+    pub fn xfa(&self) -> Result<Option<Xfa>, ParseError> {
+        let xfa = match self
+            .get(self.catalog()?, "AcroForm")
+            .and_then(|acro_form| acro_form.get("XFA"))
+        {
+            Some(xfa) => xfa,
+            None => return Ok(None),
+        };
+        let parts = match self.resolve(xfa) {
+            Object::Array(pairs) => pairs
+                .chunks_exact(2)
+                .filter_map(|pair| {
+                    let name = self.resolve(&pair[0]).text_string();
+                    self.xfa_part(name, &pair[1])
+                })
+                .collect(),
+            _ => self.xfa_part(None, xfa).into_iter().collect(),
+        };
+        Ok(Some(Xfa { parts }))
+    }
+
+    fn xfa_part(&self, name: Option<String>, stream: &Object<'a>) -> Option<XfaPart> {
+        // Parts may split the XML anywhere, so the data must end where `/Length` says.
+        let (dictionary, data) = self.stream_data(stream)?;
+        Some(XfaPart {
+            name,
+            reference: stream.as_reference(),
+            data: crate::filter::decode(dictionary, data).unwrap_or(data.to_vec()),
+        })
+    }
+}
+
+impl Xfa {
+    /// The XML document, the parts concatenated in order.
+    pub fn xml(&self) -> Vec<u8> {
+        self.parts
+            .iter()
+            .flat_map(|part| part.data.clone())
+            .collect()
+    }
+
+    /// Splits the document into packets. If the root element is not `<xdp:xdp>`,
+    /// it is the only packet. Reading stops at the first malformed markup, keeping
+    /// the packets read so far.
+    pub fn packets(&self) -> Vec<XfaPacket> {
+        let xml = self.xml();
+        let mut reader = Reader::from_reader(xml.as_slice());
+        reader.config_mut().check_end_names = false;
+
+        let mut packets = Vec::new();
+        let mut depth = 0;
+        let mut packet_depth = 1;
+        let mut start = None;
+        loop {
+            let position = reader.buffer_position() as usize;
+            let (name, is_start) = match reader.read_event() {
+                Ok(Event::Start(element)) => (local_name(&element), true),
+                Ok(Event::Empty(element)) => {
+                    if depth == packet_depth {
+                        packets.push(XfaPacket {
+                            name: local_name(&element),
+                            xml: text(&xml[position..reader.buffer_position() as usize]),
+                        });
+                    }
+                    continue;
+                }
+                Ok(Event::End(_)) if depth > 0 => (String::new(), false),
+                Ok(Event::Eof) | Err(_) => break,
+                Ok(_) => continue,
+            };
+            if is_start {
+                if depth == 0 && name != "xdp" {
+                    packet_depth = 0;
+                }
+                if depth == packet_depth {
+                    start = Some((name, position));
+                }
+                depth += 1;
+            } else {
+                depth -= 1;
+                if depth == packet_depth {
+                    if let Some((name, start)) = start.take() {
+                        packets.push(XfaPacket {
+                            name,
+                            xml: text(&xml[start..reader.buffer_position() as usize]),
+                        });
+                    }
+                }
+                if depth == 0 {
+                    break;
+                }
+            }
+        }
+        packets
+    }
+
+    /// Returns the first packet named `name`.
+    pub fn packet(&self, name: &str) -> Option<XfaPacket> {
+        self.packets()
+            .into_iter()
+            .find(|packet| packet.name == name)
+    }
+
+    /// Lists the `<script>` elements of all packets in document order.
+    pub fn scripts(&self) -> Vec<XfaScript> {
+        let xml = self.xml();
+        let mut reader = Reader::from_reader(xml.as_slice());
+        reader.config_mut().check_end_names = false;
+
+        let mut scripts = Vec::new();
+        // The open elements, as path components.
+        let mut path: Vec<String> = Vec::new();
+        // The script being read, with the depth of its element.
+        let mut script: Option<(usize, XfaScript)> = None;
+        loop {
+            let position = reader.buffer_position() as usize;
+            match reader.read_event() {
+                Ok(Event::Start(element)) => {
+                    path.push(component(&element));
+                    if script.is_none() && local_name(&element) == "script" {
+                        script = Some((path.len(), new_script(&element, &path, position)));
+                    }
+                }
+                Ok(Event::Empty(element)) => {
+                    if script.is_none() && local_name(&element) == "script" {
+                        path.push(component(&element));
+                        scripts.push(new_script(&element, &path, position));
+                        path.pop();
+                    }
+                }
+                Ok(Event::Text(content)) => {
+                    if let Some((_, script)) = &mut script {
+                        let content = content
+                            .unescape()
+                            .map(|content| content.into_owned())
+                            .unwrap_or_else(|_| text(&content));
+                        script.source.push_str(&content);
+                    }
+                }
+                Ok(Event::CData(content)) => {
+                    if let Some((_, script)) = &mut script {
+                        script.source.push_str(&text(&content));
+                    }
+                }
+                Ok(Event::End(_)) => {
+                    if script
+                        .as_ref()
+                        .is_some_and(|(depth, _)| *depth == path.len())
+                    {
+                        scripts.extend(script.take().map(|(_, script)| script));
+                    }
+                    path.pop();
+                }
+                Ok(Event::Eof) | Err(_) => break,
+                Ok(_) => {}
+            }
+        }
+        // A script cut short by the end of the document or malformed markup.
+        scripts.extend(script.map(|(_, script)| script));
+        scripts
+    }
+}
+
+fn local_name(element: &BytesStart) -> String {
+    text(element.local_name().as_ref())
+}
+
+fn attribute(element: &BytesStart, name: &str) -> Option<String> {
+    let attribute = element
+        .attributes()
+        .flatten()
+        .find(|attribute| attribute.key.local_name().as_ref() == name.as_bytes())?;
+    Some(
+        attribute
+            .unescape_value()
+            .map(|value| value.into_owned())
+            .unwrap_or_else(|_| text(&attribute.value)),
+    )
+}
+
+/// The path component of an element: its local name, and its `name` attribute in
+/// brackets.
+fn component(element: &BytesStart) -> String {
+    match attribute(element, "name") {
+        Some(name) => format!("{}[{}]", local_name(element), name),
+        None => local_name(element),
+    }
+}
+
+fn new_script(element: &BytesStart, path: &[String], offset: usize) -> XfaScript {
+    // Paths start at the packet, below the `<xdp:xdp>` root.
+    let skip = match path.first() {
+        Some(root) if root == "xdp" && path.len() > 1 => 1,
+        _ => 0,
+    };
+    XfaScript {
+        path: path[skip..].join("/"),
+        offset,
+        content_type: attribute(element, "contentType"),
+        run_at: attribute(element, "runAt"),
+        source: String::new(),
+    }
+}
+
+fn text(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes).into_owned()
+}
@@ -0,0 +1,171 @@
+#[cfg(test)]
+mod tests {
+    use pdf_parser::action::{Action, TriggerSource};
+    use pdf_parser::object::PDF;
+    use std::{fs::File, io::Read, path::PathBuf};
+
+    fn read_testcase(filename: &str) -> Vec<u8> {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("assets");
+        path.push(filename);
+        let mut file = File::open(path).unwrap();
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer).unwrap();
+        buffer
+    }
+
+    #[test]
+    fn test_action_types() {
+        let bytes = read_testcase("test_actions.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let result = pdf.action_graph();
+        assert!(result.is_ok());
+        let graph = result.unwrap();
+        let actions: Vec<&Action> = graph.actions.iter().map(|node| &node.action).collect();
+        assert_eq!(
+            actions,
+            vec![
+                &Action::Launch {
+                    file: Some("calc.exe".to_string()),
+                    parameters: Some("/c calc".to_string()),
+                    new_window: Some(false),
+                },
+                &Action::URI {
+                    uri: "http://example.com/a?b=c".to_string(),
+                    is_map: false,
+                },
+                &Action::Named {
+                    name: "NextPage".to_string()
+                },
+                &Action::GoToE {
+                    file: None,
+                    target: Some("attached.pdf".to_string()),
+                    new_window: Some(true),
+                },
+                &Action::GoToR {
+                    file: Some("other.pdf".to_string()),
+                    new_window: Some(true),
+                },
+                &Action::SubmitForm {
+                    url: Some("https://example.com/submit".to_string()),
+                    fields: vec!["Name".to_string(), "Submit".to_string()],
+                    flags: 4,
+                },
+                &Action::ImportData {
+                    file: Some("data.fdf".to_string())
+                },
+                &Action::ImportData {
+                    file: Some("data.fdf".to_string())
+                },
+            ]
+        );
+        assert_eq!(graph.actions[3].action.subtype(), "GoToE");
+    }
+
+    #[test]
+    fn test_trigger_graph() {
+        let bytes = read_testcase("test_actions.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let graph = pdf.action_graph().unwrap();
+        let triggers: Vec<(&TriggerSource, Option<u32>, &str, &str, usize)> = graph
+            .triggers
+            .iter()
+            .map(|t| {
+                (
+                    &t.source,
+                    t.owner.map(|r| r.id),
+                    t.event.as_str(),
+                    t.path.as_str(),
+                    t.action,
+                )
+            })
+            .collect();
+        let widget = TriggerSource::Annotation { page: 0, index: 1 };
+        assert_eq!(
+            triggers,
+            vec![
+                (
+                    &TriggerSource::Document,
+                    Some(1),
+                    "OpenAction",
+                    "/Root/OpenAction",
+                    0
+                ),
+                (
+                    &TriggerSource::Page(0),
+                    Some(3),
+                    "C",
+                    "/Root/Pages[0]/AA/C",
+                    0
+                ),
+                (
+                    &TriggerSource::Page(0),
+                    Some(3),
+                    "O",
+                    "/Root/Pages[0]/AA/O",
+                    3
+                ),
+                (
+                    &TriggerSource::Annotation { page: 0, index: 0 },
+                    Some(4),
+                    "A",
+                    "/Root/Pages[0]/Annots[0]/A",
+                    4
+                ),
+                (&widget, Some(5), "A", "/Root/Pages[0]/Annots[1]/A", 5),
+                (&widget, Some(5), "D", "/Root/Pages[0]/Annots[1]/AA/D", 6),
+                (
+                    &TriggerSource::Field,
+                    Some(5),
+                    "A",
+                    "/Root/AcroForm/Fields[0]/A",
+                    5
+                ),
+                (
+                    &TriggerSource::Field,
+                    Some(5),
+                    "D",
+                    "/Root/AcroForm/Fields[0]/AA/D",
+                    7
+                ),
+            ]
+        );
+        // Indirect actions are shared, direct ones are not.
+        assert_eq!(graph.actions.len(), 8);
+        assert_eq!(graph.actions[0].reference.map(|r| r.id), Some(10));
+    }
+
+    #[test]
+    fn test_next_cycle() {
+        let bytes = read_testcase("test_actions.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let graph = pdf.action_graph().unwrap();
+        assert_eq!(graph.actions[0].next, vec![1]);
+        assert_eq!(graph.actions[2].next, vec![0]);
+        assert_eq!(graph.cycles, vec![(2, 0)]);
+        assert_eq!(graph.chain(0), vec![0, 1, 2]);
+        assert_eq!(graph.chain(1), vec![1, 2, 0]);
+    }
+
+    #[test]
+    fn test_next_array_cycles() {
+        let bytes = read_testcase("test_javascript.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let graph = pdf.action_graph().unwrap();
+        let link = graph
+            .triggers
+            .iter()
+            .find(|t| t.path == "/Root/Pages[0]/Annots[0]/A")
+            .unwrap();
+        let chain = graph.chain(link.action);
+        let subtypes: Vec<&str> = chain
+            .iter()
+            .map(|&i| graph.actions[i].action.subtype())
+            .collect();
+        assert_eq!(subtypes, vec!["URI", "JavaScript", "JavaScript"]);
+        assert_eq!(graph.cycles.len(), 2);
+        for (from, to) in &graph.cycles {
+            assert!(chain.contains(from) && chain.contains(to));
+        }
+    }
+}
@@ -0,0 +1,149 @@
+#[cfg(test)]
+mod tests {
+    use pdf_parser::action::Action;
+    use pdf_parser::annotation::{AnnotationType, AppearanceStream, HIDDEN, INVISIBLE, PRINT};
+    use pdf_parser::interpreter::Rect;
+    use pdf_parser::object::{IndirectReference, PDF};
+    use std::{fs::File, io::Read, path::PathBuf};
+
+    fn read_testcase(filename: &str) -> Vec<u8> {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("assets");
+        path.push(filename);
+        let mut file = File::open(path).unwrap();
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer).unwrap();
+        buffer
+    }
+
+    #[test]
+    fn test_annotation_types() {
+        let bytes = read_testcase("test_annotations.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let annotations = pdf.annotations().unwrap();
+        let types: Vec<(usize, usize, &str)> = annotations
+            .iter()
+            .map(|(page, annotation)| (*page, annotation.index, annotation.subtype.name()))
+            .collect();
+        // The integer and the name in /Annots are skipped.
+        assert_eq!(
+            types,
+            vec![
+                (0, 0, "Link"),
+                (0, 1, "Text"),
+                (0, 2, "Highlight"),
+                (0, 3, "FileAttachment"),
+                (0, 4, "Screen"),
+                (0, 5, "Widget"),
+                (0, 8, "RichMedia"),
+                (0, 9, "Foo"),
+                (1, 0, "Popup"),
+            ]
+        );
+        assert_eq!(
+            annotations[7].1.subtype,
+            AnnotationType::Other("Foo".to_string())
+        );
+        assert_eq!(annotations[2].1.reference, None);
+    }
+
+    #[test]
+    fn test_annotation_entries() {
+        let bytes = read_testcase("test_annotations.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let pages = pdf.pages().unwrap();
+        let annotations = pages[0].annotations();
+
+        let link = &annotations[0];
+        assert_eq!(
+            link.reference,
+            Some(IndirectReference {
+                id: 10,
+                generation: 0
+            })
+        );
+        assert_eq!(link.rect, Some(Rect::new(72.0, 700.0, 200.0, 720.0)));
+        assert!(link.has_flag(PRINT));
+        assert_eq!(
+            link.action,
+            Some(Action::URI {
+                uri: "https://example.com/".to_string(),
+                is_map: false
+            })
+        );
+
+        let text = &annotations[1];
+        assert!(text.has_flag(INVISIBLE) && text.has_flag(HIDDEN));
+        assert!(!text.has_flag(PRINT));
+        assert_eq!(text.contents.as_deref(), Some("été"));
+        assert_eq!(text.author.as_deref(), Some("Alice"));
+        assert_eq!(text.name.as_deref(), Some("note-1"));
+        assert_eq!(text.modified.as_deref(), Some("D:20230101120000Z"));
+        assert!(text.subtype.is_markup());
+
+        let highlight = &annotations[2];
+        assert_eq!(highlight.contents.as_deref(), Some("marked"));
+        assert_eq!(highlight.author.as_deref(), Some("Bob"));
+
+        assert_eq!(annotations[3].file.as_deref(), Some("report.exe"));
+
+        let screen = &annotations[4];
+        assert_eq!(screen.action, None);
+        let events: Vec<(&str, &str)> = screen
+            .additional_actions
+            .iter()
+            .map(|(event, action)| (event.as_str(), action.subtype()))
+            .collect();
+        assert_eq!(events, vec![("PC", "Named"), ("PO", "JavaScript")]);
+
+        // /T is not an author outside markup annotations.
+        let rich_media = &annotations[6];
+        assert_eq!(rich_media.contents.as_deref(), Some("Flash"));
+        assert_eq!(rich_media.author, None);
+        assert!(!rich_media.subtype.is_markup());
+    }
+
+    #[test]
+    fn test_annotation_appearances() {
+        let bytes = read_testcase("test_annotations.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let pages = pdf.pages().unwrap();
+        let widget = &pages[0].annotations()[5];
+        assert_eq!(widget.appearance_state.as_deref(), Some("On"));
+        let stream = |kind: &str, state: Option<&str>, id: u32| AppearanceStream {
+            kind: kind.to_string(),
+            state: state.map(str::to_string),
+            reference: Some(IndirectReference { id, generation: 0 }),
+        };
+        assert_eq!(
+            widget.appearances,
+            vec![
+                stream("N", Some("Off"), 21),
+                stream("N", Some("On"), 20),
+                stream("D", None, 21),
+            ]
+        );
+        assert!(pages[1].annotations()[0].appearances.is_empty());
+    }
+
+    #[test]
+    fn test_widget_annotations() {
+        let bytes = read_testcase("test.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let annotations = pdf.annotations().unwrap();
+        let widgets: Vec<(usize, Option<Rect>)> = annotations
+            .iter()
+            .map(|(page, annotation)| {
+                assert_eq!(annotation.subtype, AnnotationType::Widget);
+                (*page, annotation.rect)
+            })
+            .collect();
+        assert_eq!(
+            widgets,
+            vec![
+                (0, Some(Rect::new(0.0, 0.0, 1000.0, 1000.0))),
+                (1, Some(Rect::new(400.0, 400.0, 600.0, 600.0))),
+            ]
+        );
+    }
+}
@@ -0,0 +1,141 @@
+#[cfg(test)]
+mod tests {
+    use pdf_parser::content::{Content, Operation};
+    use pdf_parser::object::{Object, PDF};
+    use std::{fs::File, io::Read, path::PathBuf};
+
+    fn read_testcase(filename: &str) -> Vec<u8> {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("assets");
+        path.push(filename);
+        let mut file = File::open(path).unwrap();
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer).unwrap();
+        buffer
+    }
+
+    #[test]
+    fn test_parse_operation() {
+        let input = b"/F1 12 Tf THIS_STRING_MUST_REMAINED";
+        let result = Operation::parse(input);
+        assert!(result.is_ok());
+        let (input, operation) = result.unwrap();
+        assert_eq!(input, b"THIS_STRING_MUST_REMAINED");
+        assert_eq!(operation.operator, "Tf");
+        assert_eq!(operation.operands.len(), 2);
+        assert_eq!(operation.operands[0].as_name(), Some("F1"));
+        assert_eq!(operation.operands[1].as_i32(), Some(12));
+    }
+
+    #[test]
+    fn test_parse_content() {
+        let input = b"BT /F1 12 Tf (Hello) Tj ET";
+        let result = Content::parse(input);
+        assert!(result.is_ok());
+        let operators: Vec<&str> = result
+            .unwrap()
+            .operations
+            .iter()
+            .map(|operation| operation.operator)
+            .collect();
+        assert_eq!(operators, vec!["BT", "Tf", "Tj", "ET"]);
+    }
+
+    #[test]
+    fn test_parse_content_without_separators() {
+        let input = b"[(Wor)-20(ld)]TJ/F2 9.5 Tf";
+        let result = Content::parse(input);
+        assert!(result.is_ok());
+        let operations = result.unwrap().operations;
+        assert_eq!(operations.len(), 2);
+        assert_eq!(operations[0].operator, "TJ");
+        match &operations[0].operands[0] {
+            Object::Array(a) => {
+                assert_eq!(a.len(), 3);
+                assert_eq!(a[1].as_i32(), Some(-20));
+            }
+            _ => panic!("Expected Object::Array"),
+        }
+        assert_eq!(operations[1].operands[1].as_f32(), Some(9.5));
+    }
+
+    #[test]
+    fn test_parse_content_with_comment() {
+        let input = b"q % save\n1 0 0 1 0 0 cm Q";
+        let result = Content::parse(input);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().operations.len(), 3);
+    }
+
+    #[test]
+    fn test_parse_content_non_ascii_strings() {
+        let input = b"BT (caf\xe9) Tj <> Tj < 00 41 > Tj [(\x80\x99)] TJ ET";
+        let operations = Content::parse(input).unwrap().operations;
+        assert_eq!(operations.len(), 6);
+        let bytes: Vec<Option<Vec<u8>>> = operations[1..4]
+            .iter()
+            .map(|operation| operation.operands[0].string_bytes())
+            .collect();
+        assert_eq!(
+            bytes,
+            vec![
+                Some(b"caf\xe9".to_vec()),
+                Some(Vec::new()),
+                Some(vec![0x00, 0x41])
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_content_invalid() {
+        // Operands without an operator
+        let input = b"BT /F1 12";
+        let result = Content::parse(input);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_inline_image() {
+        let input = b"BI /W 2 /H 1 /BPC 8 /CS /G ID \x00\xff EI Q";
+        let result = Content::parse(input);
+        assert!(result.is_ok());
+        let operations = result.unwrap().operations;
+        assert_eq!(operations.len(), 2);
+        assert_eq!(operations[0].operator, "BI");
+        match &operations[0].operands[0] {
+            Object::Dictionary(d, s) => {
+                assert_eq!(d.len(), 4);
+                assert_eq!(*s, b"\x00\xff");
+            }
+            _ => panic!("Expected Object::Dictionary"),
+        }
+        assert_eq!(operations[1].operator, "Q");
+    }
+
+    #[test]
+    fn test_page_contents() {
+        let bytes = read_testcase("test_content.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let pages = pdf.pages();
+        assert!(pages.is_ok());
+        let pages = pages.unwrap();
+        assert_eq!(pages.len(), 1);
+
+        // The second stream of the /Contents array is Flate-compressed.
+        let data = pages[0].contents();
+        assert!(data.is_ok());
+        let data = data.unwrap();
+        let content = Content::parse(&data);
+        assert!(content.is_ok());
+        let operators: Vec<&str> = content
+            .unwrap()
+            .operations
+            .iter()
+            .map(|operation| operation.operator)
+            .collect();
+        assert_eq!(
+            operators,
+            vec!["q", "cm", "BT", "Tf", "Tj", "ET", "TJ", "BI", "Q"]
+        );
+    }
+}
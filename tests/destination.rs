@@ -0,0 +1,142 @@
+#[cfg(test)]
+mod tests {
+    use pdf_parser::destination::{
+        Destination, DestinationResolver, PageTarget, ResolvedDestination, View,
+    };
+    use pdf_parser::object::{IndirectReference, Object, PDF};
+    use std::{fs::File, io::Read, path::PathBuf};
+
+    fn read_testcase(filename: &str) -> Vec<u8> {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("assets");
+        path.push(filename);
+        let mut file = File::open(path).unwrap();
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer).unwrap();
+        buffer
+    }
+
+    #[test]
+    fn test_named_destinations() {
+        let bytes = read_testcase("test_outlines.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let page = |id| PageTarget::Reference(IndirectReference { id, generation: 0 });
+        assert_eq!(
+            pdf.named_destinations().unwrap(),
+            vec![
+                (
+                    Destination::Name("named12".to_string()),
+                    Destination::Explicit {
+                        page: page(5),
+                        view: View::XYZ {
+                            left: Some(0.0),
+                            top: Some(0.0),
+                            zoom: None
+                        }
+                    }
+                ),
+                (
+                    Destination::String(b"sec11".to_vec()),
+                    Destination::Explicit {
+                        page: page(4),
+                        view: View::FitH { top: Some(700.0) }
+                    }
+                ),
+            ]
+        );
+
+        let resolver = DestinationResolver::new(&pdf).unwrap();
+        assert_eq!(
+            resolver.resolve(&Destination::Name("named12".to_string())),
+            Some(ResolvedDestination {
+                page: 2,
+                view: View::XYZ {
+                    left: Some(0.0),
+                    top: Some(0.0),
+                    zoom: None
+                }
+            })
+        );
+        assert_eq!(
+            resolver.resolve(&Destination::String(b"sec11".to_vec())),
+            Some(ResolvedDestination {
+                page: 1,
+                view: View::FitH { top: Some(700.0) }
+            })
+        );
+        assert_eq!(
+            resolver.resolve(&Destination::Name("missing".to_string())),
+            None
+        );
+        // A name object is looked up in `/Dests`, a string in the name tree.
+        assert_eq!(
+            resolver.resolve(&Destination::String(b"named12".to_vec())),
+            None
+        );
+    }
+
+    #[test]
+    fn test_explicit_destinations() {
+        let bytes = read_testcase("test_trees.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let resolver = DestinationResolver::new(&pdf).unwrap();
+        let tree = pdf.name_tree("Dests").unwrap().unwrap();
+        let views: Vec<(Vec<u8>, Option<ResolvedDestination>)> = tree
+            .iter()
+            .map(|(key, value)| (key, resolver.resolve_object(value)))
+            .collect();
+        let resolved = |view| Some(ResolvedDestination { page: 0, view });
+        assert_eq!(
+            views,
+            vec![
+                (b"alpha".to_vec(), resolved(View::Fit)),
+                (
+                    b"beta".to_vec(),
+                    resolved(View::XYZ {
+                        left: Some(0.0),
+                        top: Some(792.0),
+                        zoom: None
+                    })
+                ),
+                // A dictionary with `/D`.
+                (b"gamma".to_vec(), resolved(View::FitV { left: Some(0.0) })),
+                (b"zeta".to_vec(), resolved(View::FitH { top: Some(100.0) })),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_destination_objects() {
+        let bytes = read_testcase("test_outlines.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let resolver = DestinationResolver::new(&pdf).unwrap();
+        let (_, destination) = Object::parse_array(b"[1 /FitR 10 20 30.5 40]").unwrap();
+        assert_eq!(
+            resolver.resolve_object(&destination),
+            Some(ResolvedDestination {
+                page: 1,
+                view: View::FitR {
+                    left: 10.0,
+                    bottom: 20.0,
+                    right: 30.5,
+                    top: 40.0
+                }
+            })
+        );
+        for invalid in [
+            &b"[3 /Fit]"[..],
+            b"[1 /Unknown]",
+            b"[1 /FitR 0 0]",
+            b"[9 0 R /Fit]",
+        ] {
+            let (_, destination) = Object::parse_array(invalid).unwrap();
+            assert_eq!(resolver.resolve_object(&destination), None);
+        }
+        assert_eq!(
+            pdf.resolve_destination(&Object::Name(pdf_parser::object::NameObject("named12")))
+                .unwrap()
+                .map(|destination| destination.page),
+            Some(2)
+        );
+    }
+}
@@ -0,0 +1,107 @@
+#[cfg(test)]
+mod tests {
+    use pdf_parser::embedded_file::AttachmentSource;
+    use pdf_parser::object::{IndirectReference, PDF};
+    use std::{fs::File, io::Read, path::PathBuf};
+
+    fn read_testcase(filename: &str) -> Vec<u8> {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("assets");
+        path.push(filename);
+        let mut file = File::open(path).unwrap();
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer).unwrap();
+        buffer
+    }
+
+    #[test]
+    fn test_embedded_files() {
+        let bytes = read_testcase("test_embedded_files.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let files = pdf.embedded_files().unwrap();
+        let sources: Vec<(&AttachmentSource, Option<&str>)> = files
+            .iter()
+            .map(|file| (&file.source, file.name()))
+            .collect();
+        // The second annotation refers to a file of the name tree.
+        assert_eq!(
+            sources,
+            vec![
+                (
+                    &AttachmentSource::NameTree("a.txt".to_string()),
+                    Some("a.txt")
+                ),
+                (
+                    &AttachmentSource::NameTree("b.bin".to_string()),
+                    Some("b.bin")
+                ),
+                (
+                    &AttachmentSource::Annotation { page: 0, index: 0 },
+                    Some("facture.pdf")
+                ),
+            ]
+        );
+
+        let text = &files[0];
+        assert_eq!(
+            text.file_spec,
+            Some(IndirectReference {
+                id: 10,
+                generation: 0
+            })
+        );
+        assert_eq!(
+            text.reference,
+            Some(IndirectReference {
+                id: 20,
+                generation: 0
+            })
+        );
+        assert_eq!(text.description.as_deref(), Some("Greeting"));
+        assert_eq!(text.mime_type.as_deref(), Some("text/plain"));
+        assert_eq!(text.size, Some(18));
+        assert_eq!(text.creation_date.as_deref(), Some("D:20230101120000Z"));
+        assert_eq!(text.modification_date.as_deref(), Some("D:20230102120000Z"));
+        assert_eq!(text.content().unwrap(), b"hello, attachment\n");
+
+        let invoice = &files[2];
+        assert_eq!(invoice.file.as_deref(), Some("invoice.pdf"));
+        assert_eq!(invoice.unicode_file.as_deref(), Some("facture.pdf"));
+        assert_eq!(invoice.mime_type.as_deref(), Some("application/pdf"));
+        // The end of line before `endstream` is not part of the file.
+        assert_eq!(invoice.content().unwrap(), b"%PDF-1.4 fake");
+    }
+
+    #[test]
+    fn test_embedded_file_checks() {
+        let bytes = read_testcase("test_embedded_files.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let files = pdf.embedded_files().unwrap();
+        let checks: Vec<(Option<bool>, Option<bool>)> = files
+            .iter()
+            .map(|file| {
+                (
+                    file.checksum_matches().unwrap(),
+                    file.size_matches().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            checks,
+            vec![
+                (Some(true), Some(true)),
+                (Some(false), Some(false)),
+                (None, None)
+            ]
+        );
+        assert_eq!(files[1].checksum.as_ref().map(Vec::len), Some(16));
+        assert_eq!(files[1].content().unwrap(), b"\x00\x01binary");
+    }
+
+    #[test]
+    fn test_no_embedded_files() {
+        let bytes = read_testcase("test.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        assert!(pdf.embedded_files().unwrap().is_empty());
+    }
+}
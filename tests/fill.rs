@@ -0,0 +1,152 @@
+#[cfg(test)]
+mod tests {
+    use pdf_parser::error::ParseError;
+    use pdf_parser::form::FieldValue;
+    use pdf_parser::object::PDF;
+    use std::{fs::File, io::Read, path::PathBuf};
+
+    fn read_testcase(filename: &str) -> Vec<u8> {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("assets");
+        path.push(filename);
+        let mut file = File::open(path).unwrap();
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer).unwrap();
+        buffer
+    }
+
+    #[test]
+    fn test_fill_text_incremental() {
+        let bytes = read_testcase("test_form.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let mut filler = pdf.form_filler().unwrap();
+        assert!(filler.set_text("person.name", "Carol").is_ok());
+        assert!(filler
+            .set_text("person.notes", "line one\nligne deux é")
+            .is_ok());
+        let saved = filler.save_incremental(&bytes);
+        assert!(saved.starts_with(&bytes));
+
+        let pdf = PDF::parse(saved.as_slice()).unwrap();
+        assert_eq!(pdf.cross_reference_tables.len(), 3);
+        let form = pdf.form().unwrap().unwrap();
+        let name = form.field("person.name").unwrap();
+        assert_eq!(name.value, Some(FieldValue::Text("Carol".to_string())));
+        assert_eq!(
+            name.default_value,
+            Some(FieldValue::Text("Bob".to_string()))
+        );
+        assert_eq!(
+            form.field("person.notes").unwrap().value,
+            Some(FieldValue::Text("line one\nligne deux é".to_string()))
+        );
+
+        let widget = pdf.get_object(name.widgets[0].reference.unwrap()).unwrap();
+        let appearance = pdf
+            .get(widget, "AP")
+            .and_then(|appearances| pdf.get(appearances, "N"))
+            .unwrap();
+        let (dictionary, data) = appearance.as_stream().unwrap();
+        assert!(dictionary.contains_key("Resources"));
+        let content = String::from_utf8_lossy(data);
+        assert!(content.contains("/Helv 12 Tf"));
+        assert!(content.contains("<4361726F6C> Tj"));
+    }
+
+    #[test]
+    fn test_fill_buttons_and_choice() {
+        let bytes = read_testcase("test_form.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let mut filler = pdf.form_filler().unwrap();
+        assert!(filler.set_checkbox("agree", false).is_ok());
+        assert!(filler.set_radio("size", "S").is_ok());
+        assert!(filler.set_choice("colors", &["g"]).is_ok());
+        let saved = filler.save();
+        // The binary marker comment is written as raw bytes, not UTF-8.
+        assert_eq!(
+            saved.split(|&c| c == b'\n').nth(1),
+            Some(&b"%\xe2\xe3\xcf\xd3"[..])
+        );
+
+        let pdf = PDF::parse(saved.as_slice()).unwrap();
+        assert_eq!(pdf.cross_reference_tables.len(), 1);
+        let form = pdf.form().unwrap().unwrap();
+        let agree = form.field("agree").unwrap();
+        assert_eq!(agree.value, Some(FieldValue::Name("Off".to_string())));
+        assert_eq!(agree.widgets[0].appearance_state.as_deref(), Some("Off"));
+        let size = form.field("size").unwrap();
+        assert_eq!(size.value, Some(FieldValue::Name("S".to_string())));
+        let states: Vec<Option<&str>> = size
+            .widgets
+            .iter()
+            .map(|widget| widget.appearance_state.as_deref())
+            .collect();
+        assert_eq!(states, vec![Some("S"), Some("Off")]);
+        let colors = form.field("colors").unwrap();
+        assert_eq!(colors.value, Some(FieldValue::Text("g".to_string())));
+        let colors_object = pdf.get_object(colors.reference.unwrap()).unwrap();
+        assert!(colors_object.get("I").is_none());
+        // Untouched fields survive the rewrite.
+        assert_eq!(
+            form.field("person.name").unwrap().value,
+            Some(FieldValue::Text("Alice".to_string()))
+        );
+
+        let pdf = PDF::parse(saved.as_slice()).unwrap();
+        let mut filler = pdf.form_filler().unwrap();
+        assert!(filler.set_checkbox("agree", true).is_ok());
+        let saved = filler.save();
+        let pdf = PDF::parse(saved.as_slice()).unwrap();
+        let form = pdf.form().unwrap().unwrap();
+        let agree = form.field("agree").unwrap();
+        assert_eq!(agree.value, Some(FieldValue::Name("Yes".to_string())));
+    }
+
+    #[test]
+    fn test_fill_errors() {
+        let bytes = read_testcase("test_form.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let mut filler = pdf.form_filler().unwrap();
+        assert_eq!(
+            filler.set_text("person.age", "42"),
+            Err(ParseError::InvalidPDFFormField("person.age".to_string()))
+        );
+        // Wrong field types.
+        assert!(filler.set_text("agree", "yes").is_err());
+        assert!(filler.set_checkbox("size", true).is_err());
+        assert!(filler.set_radio("agree", "Yes").is_err());
+        // Unknown states and options.
+        assert!(filler.set_radio("size", "XL").is_err());
+        assert!(filler.set_choice("colors", &["purple"]).is_err());
+    }
+
+    #[test]
+    fn test_flatten() {
+        let bytes = read_testcase("test_form.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let mut filler = pdf.form_filler().unwrap();
+        assert!(filler.set_text("person.name", "Carol").is_ok());
+        assert!(filler.flatten().is_ok());
+        let saved = filler.save_incremental(&bytes);
+
+        let pdf = PDF::parse(saved.as_slice()).unwrap();
+        assert_eq!(pdf.form(), Ok(None));
+        let pages = pdf.pages().unwrap();
+        for page in &pages {
+            let annotations = page
+                .dictionary
+                .get("Annots")
+                .and_then(|annotations| pdf.resolve(annotations).as_array())
+                .unwrap_or_default();
+            assert!(annotations.is_empty());
+        }
+        let text = pages[0].extract_text().unwrap();
+        assert!(text.contains("Hello"));
+        assert!(text.contains("Carol"));
+        let contents = String::from_utf8(pages[0].contents().unwrap()).unwrap();
+        // The check box and the unselected radio button are drawn with their
+        // appearance streams.
+        assert_eq!(contents.matches(" Do Q").count(), 3);
+        assert!(contents.starts_with("q\n"));
+    }
+}
@@ -0,0 +1,156 @@
+#[cfg(test)]
+mod tests {
+    use pdf_parser::cmap::CMap;
+    use pdf_parser::font::Font;
+    use pdf_parser::object::PDF;
+    use std::{fs::File, io::Read, path::PathBuf};
+
+    fn read_testcase(filename: &str) -> Vec<u8> {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("assets");
+        path.push(filename);
+        let mut file = File::open(path).unwrap();
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer).unwrap();
+        buffer
+    }
+
+    fn font(pdf: &PDF, name: &str) -> Font {
+        let pages = pdf.pages().unwrap();
+        let fonts = pages[0].resources().unwrap().get("Font").unwrap();
+        Font::from_object(pdf, pdf.get(fonts, name).unwrap())
+    }
+
+    #[test]
+    fn test_glyph_list() {
+        assert_eq!(
+            pdf_parser::glyph_list::to_unicode("A"),
+            Some("A".to_string())
+        );
+        assert_eq!(
+            pdf_parser::glyph_list::to_unicode("eacute"),
+            Some("é".to_string())
+        );
+        assert_eq!(
+            pdf_parser::glyph_list::to_unicode("uni20AC"),
+            Some("€".to_string())
+        );
+        assert_eq!(
+            pdf_parser::glyph_list::to_unicode("u1F600"),
+            Some("😀".to_string())
+        );
+        assert_eq!(
+            pdf_parser::glyph_list::to_unicode("f_f_i"),
+            Some("ffi".to_string())
+        );
+        assert_eq!(
+            pdf_parser::glyph_list::to_unicode("a.sc"),
+            Some("a".to_string())
+        );
+        assert_eq!(pdf_parser::glyph_list::to_unicode("g123"), None);
+    }
+
+    #[test]
+    fn test_parse_cmap_bfchar_bfrange() {
+        let input = b"1 begincodespacerange <0000> <FFFF> endcodespacerange
+            2 beginbfchar <0001> <C548> <0005> <D83DDE00> endbfchar
+            2 beginbfrange <0002> <0003> <B155> <0010> <0011> [<65E5> <672C>] endbfrange";
+        let result = CMap::parse(input);
+        assert!(result.is_ok());
+        let cmap = result.unwrap();
+        assert_eq!(cmap.codes(b"\x00\x01\x00\x03"), vec![1, 3]);
+        assert_eq!(cmap.to_unicode(1), Some("안".to_string()));
+        assert_eq!(cmap.to_unicode(3), Some("녖".to_string()));
+        assert_eq!(cmap.to_unicode(5), Some("😀".to_string()));
+        assert_eq!(cmap.to_unicode(0x11), Some("本".to_string()));
+        assert_eq!(cmap.to_unicode(4), None);
+    }
+
+    #[test]
+    fn test_parse_cmap_multi_byte_codespace() {
+        let input = b"2 begincodespacerange <00> <7F> <8140> <9FFC> endcodespacerange
+            2 begincidrange <00> <7F> 1 <8140> <817E> 100 endcidrange";
+        let result = CMap::parse(input);
+        assert!(result.is_ok());
+        let cmap = result.unwrap();
+        assert_eq!(cmap.codes(b"A\x81\x41B"), vec![0x41, 0x8141, 0x42]);
+        assert_eq!(cmap.cid(0x41), 0x42);
+        assert_eq!(cmap.cid(0x8141), 101);
+        assert!(cmap.is_single_byte(0x20));
+    }
+
+    #[test]
+    fn test_parse_cmap_cid_range_overflow() {
+        let input = b"1 begincodespacerange <00000000> <FFFFFFFF> endcodespacerange
+            1 begincidrange <00000000> <FFFFFFFF> 2147483647 endcidrange";
+        let cmap = CMap::parse(input).unwrap();
+        assert_eq!(cmap.cid(1), 2147483648);
+        assert_eq!(cmap.cid(0xFFFFFFFF), 0);
+    }
+
+    #[test]
+    fn test_parse_cmap_invalid() {
+        let input = b"1 begincodespacerange <00> <FFFF> endcodespacerange";
+        let result = CMap::parse(input);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_font_differences() {
+        let bytes = read_testcase("test_fonts.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let font = font(&pdf, "F1");
+        assert_eq!(font.to_unicode(b'a' as u32), "a");
+        // From the base encoding
+        assert_eq!(font.to_unicode(0x93), "\u{201C}");
+        // From /Differences
+        assert_eq!(font.to_unicode(129), "\u{FB01}");
+        assert_eq!(font.to_unicode(200), "é");
+    }
+
+    #[test]
+    fn test_font_differences_out_of_range() {
+        let pdf = PDF::parse(b"%PDF-1.7\ntrailer\n<< >>\nstartxref\n0\n%%EOF\n").unwrap();
+        let (_, object) = pdf_parser::object::Object::parse(
+            b"<< /Type /Font /Subtype /Type1 /Encoding << /Differences \
+              [-1 /a /b 300 /c 65 /B 2147483647 /d /e] >> >>",
+        )
+        .unwrap();
+        let font = Font::from_object(&pdf, &object);
+        assert_eq!(font.to_unicode(0), "\u{FFFD}");
+        assert_eq!(font.to_unicode(65), "B");
+    }
+
+    #[test]
+    fn test_font_standard_encoding() {
+        let bytes = read_testcase("test_fonts.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let font = font(&pdf, "F2");
+        assert_eq!(font.to_unicode(0x8E), "é");
+        assert_eq!(font.to_unicode(0xD5), "\u{2019}");
+    }
+
+    #[test]
+    fn test_font_type0() {
+        let bytes = read_testcase("test_fonts.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let font = font(&pdf, "F3");
+        assert_eq!(font.codes(b"\x00\x01\x00\x20"), vec![1, 0x20]);
+        assert_eq!(font.width(1), 500.0);
+        assert_eq!(font.width(2), 600.0);
+        assert_eq!(font.width(17), 800.0);
+        assert_eq!(font.width(3), 1000.0);
+        assert!(!font.is_space(0x20));
+        assert_eq!(font.to_unicode(0x10), "日");
+    }
+
+    #[test]
+    fn test_extract_text_with_fonts() {
+        let bytes = read_testcase("test_fonts.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let pages = pdf.pages().unwrap();
+        let result = pages[0].extract_text();
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), "café \u{FB01}\nété\n안녕日本\nA、B");
+    }
+}
@@ -0,0 +1,140 @@
+#[cfg(test)]
+mod tests {
+    use pdf_parser::form::{
+        ChoiceOption, FieldType, FieldValue, MULTILINE, MULTI_SELECT, RADIO, REQUIRED,
+    };
+    use pdf_parser::interpreter::Rect;
+    use pdf_parser::object::PDF;
+    use std::{fs::File, io::Read, path::PathBuf};
+
+    fn read_testcase(filename: &str) -> Vec<u8> {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("assets");
+        path.push(filename);
+        let mut file = File::open(path).unwrap();
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer).unwrap();
+        buffer
+    }
+
+    #[test]
+    fn test_form_without_names() {
+        let bytes = read_testcase("test.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let form = pdf.form().unwrap().unwrap();
+        let names: Vec<&str> = form
+            .fields
+            .iter()
+            .map(|field| field.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["MyField", "MyField"]);
+        let fields: Vec<_> = form.terminal_fields().collect();
+        assert_eq!(fields.len(), 1);
+        let field = fields[0];
+        assert_eq!(field.partial_name, None);
+        assert_eq!(field.parent, Some(0));
+        assert_eq!(field.field_type, Some(FieldType::Text));
+        let pages: Vec<Option<usize>> = field.widgets.iter().map(|widget| widget.page).collect();
+        assert_eq!(pages, vec![Some(0), Some(1)]);
+        assert_eq!(
+            field.widgets[1].rect,
+            Some(Rect::new(400.0, 400.0, 600.0, 600.0))
+        );
+    }
+
+    #[test]
+    fn test_form_fields() {
+        let bytes = read_testcase("test_form.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let form = pdf.form().unwrap().unwrap();
+        assert!(form.need_appearances);
+        assert_eq!(form.default_appearance.as_deref(), Some("/Helv 0 Tf 0 g"));
+        let names: Vec<&str> = form
+            .terminal_fields()
+            .map(|field| field.name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec!["person.name", "person.notes", "agree", "size", "colors"]
+        );
+
+        let person = form.field("person").unwrap();
+        assert_eq!(person.children.len(), 2);
+        assert!(person.widgets.is_empty());
+
+        let name = form.field("person.name").unwrap();
+        assert_eq!(name.field_type, Some(FieldType::Text));
+        assert_eq!(name.value, Some(FieldValue::Text("Alice".to_string())));
+        assert_eq!(
+            name.default_value,
+            Some(FieldValue::Text("Bob".to_string()))
+        );
+        assert!(name.has_flag(REQUIRED));
+        assert_eq!(name.default_appearance.as_deref(), Some("/Helv 12 Tf 0 g"));
+        assert_eq!(name.widgets.len(), 1);
+        assert_eq!(name.widgets[0].page, Some(0));
+
+        let notes = form.field("person.notes").unwrap();
+        assert_eq!(notes.value, Some(FieldValue::Text("été".to_string())));
+        assert!(notes.has_flag(MULTILINE));
+        assert!(!notes.has_flag(REQUIRED));
+        assert_eq!(notes.default_appearance.as_deref(), Some("/Helv 0 Tf 0 g"));
+        assert_eq!(
+            notes.widgets[0].rect,
+            Some(Rect::new(100.0, 500.0, 300.0, 600.0))
+        );
+    }
+
+    #[test]
+    fn test_form_buttons() {
+        let bytes = read_testcase("test_form.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let form = pdf.form().unwrap().unwrap();
+
+        let agree = form.field("agree").unwrap();
+        assert_eq!(agree.field_type, Some(FieldType::Button));
+        assert_eq!(agree.value, Some(FieldValue::Name("Yes".to_string())));
+        assert_eq!(agree.widgets.len(), 1);
+        assert_eq!(agree.widgets[0].appearance_state.as_deref(), Some("Yes"));
+        // Not listed in any /Annots, so the page comes from /P.
+        assert_eq!(agree.widgets[0].page, Some(0));
+
+        let size = form.field("size").unwrap();
+        assert!(size.has_flag(RADIO));
+        assert!(size.is_terminal());
+        assert_eq!(size.value, Some(FieldValue::Name("L".to_string())));
+        let states: Vec<(Option<&str>, Option<usize>)> = size
+            .widgets
+            .iter()
+            .map(|widget| (widget.appearance_state.as_deref(), widget.page))
+            .collect();
+        assert_eq!(states, vec![(Some("Off"), Some(0)), (Some("L"), Some(1))]);
+    }
+
+    #[test]
+    fn test_form_choice() {
+        let bytes = read_testcase("test_form.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let form = pdf.form().unwrap().unwrap();
+        let colors = form.field("colors").unwrap();
+        assert_eq!(colors.field_type, Some(FieldType::Choice));
+        assert!(colors.has_flag(MULTI_SELECT));
+        let option = |export: &str, label: &str| ChoiceOption {
+            export: export.to_string(),
+            label: label.to_string(),
+        };
+        assert_eq!(
+            colors.options,
+            vec![
+                option("r", "Red"),
+                option("g", "Green"),
+                option("blue", "blue")
+            ]
+        );
+        assert_eq!(
+            colors.value,
+            Some(FieldValue::List(vec!["r".to_string(), "blue".to_string()]))
+        );
+        assert_eq!(colors.widgets[0].page, Some(1));
+    }
+}
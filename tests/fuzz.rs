@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod tests {
+    use pdf_parser::filter;
+    use pdf_parser::object::{CrossReferenceTable, Object, Trailer, PDF};
+    use pdf_parser::options::ParseOptions;
+    use std::{fs, path::PathBuf};
+
+    /// Reads the regression corpus of a fuzz target, `assets/fuzz/<target>`.
+    fn read_corpus(target: &str) -> Vec<(String, Vec<u8>)> {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("assets");
+        path.push("fuzz");
+        path.push(target);
+        let mut corpus: Vec<(String, Vec<u8>)> = fs::read_dir(path)
+            .unwrap()
+            .map(|entry| {
+                let path = entry.unwrap().path();
+                let name = path.file_name().unwrap().to_string_lossy().into_owned();
+                (name, fs::read(path).unwrap())
+            })
+            .collect();
+        corpus.sort();
+        assert!(!corpus.is_empty());
+        corpus
+    }
+
+    /// Runs `target` on each input of its corpus, naming the input that panics.
+    fn run_corpus(target: &str, run: impl Fn(&[u8]) + std::panic::RefUnwindSafe) {
+        for (name, data) in read_corpus(target) {
+            let result = std::panic::catch_unwind(|| run(&data));
+            assert!(result.is_ok(), "{}/{} panicked", target, name);
+        }
+    }
+
+    // Each test runs the body of the fuzz target of the same name, see `fuzz/`.
+    // A panic on any input is a bug, whether the input is valid or not.
+
+    #[test]
+    fn test_fuzz_parse_pdf() {
+        run_corpus("parse_pdf", |data| {
+            let _ = PDF::parse(data);
+            let _ = PDF::parse_with_warnings(data, &ParseOptions::lenient());
+        });
+    }
+
+    #[test]
+    fn test_fuzz_parse_object() {
+        run_corpus("parse_object", |data| {
+            let _ = Object::parse(data);
+        });
+    }
+
+    #[test]
+    fn test_fuzz_parse_xref() {
+        run_corpus("parse_xref", |data| {
+            let _ = CrossReferenceTable::parse(data);
+            let _ = CrossReferenceTable::parse_section(data);
+        });
+    }
+
+    #[test]
+    fn test_fuzz_parse_trailer() {
+        run_corpus("parse_trailer", |data| {
+            let _ = Trailer::parse(data);
+        });
+    }
+
+    #[test]
+    fn test_fuzz_decode_stream() {
+        run_corpus("decode_stream", |data| {
+            if let Ok((_, object)) = Object::parse(data) {
+                if let Some((dictionary, data)) = object.as_stream() {
+                    let _ = filter::decode(dictionary, data);
+                }
+            }
+        });
+    }
+}
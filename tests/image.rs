@@ -0,0 +1,306 @@
+#[cfg(test)]
+mod tests {
+    use pdf_parser::filter;
+    use pdf_parser::image::{ColorSpace, Image, ImageFormat};
+    use pdf_parser::object::PDF;
+    use std::{fs::File, io::Read, path::PathBuf};
+
+    fn read_testcase(filename: &str) -> Vec<u8> {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("assets");
+        path.push(filename);
+        let mut file = File::open(path).unwrap();
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer).unwrap();
+        buffer
+    }
+
+    fn images() -> Vec<Image> {
+        let bytes = read_testcase("test_images.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let pages = pdf.pages().unwrap();
+        pages[0].images().unwrap()
+    }
+
+    fn decode_png(data: &[u8]) -> (png::ColorType, Vec<u8>) {
+        let decoder = png::Decoder::new(std::io::Cursor::new(data));
+        let mut reader = decoder.read_info().unwrap();
+        let mut buffer = vec![0; reader.output_buffer_size().unwrap()];
+        let info = reader.next_frame(&mut buffer).unwrap();
+        buffer.truncate(info.buffer_size());
+        (info.color_type, buffer)
+    }
+
+    #[test]
+    fn test_list_images() {
+        let images = images();
+        // Im1 is drawn twice but listed once.
+        assert_eq!(images.len(), 6);
+        let names: Vec<Option<&str>> = images.iter().map(|i| i.name.as_deref()).collect();
+        assert_eq!(
+            names,
+            vec![
+                Some("Im1"),
+                Some("Im2"),
+                Some("Im3"),
+                Some("Im4"),
+                Some("Mask"),
+                None
+            ]
+        );
+
+        let image = &images[2];
+        assert_eq!((image.width, image.height), (2, 2));
+        assert_eq!(image.bits_per_component, 8);
+        assert_eq!(image.color_space, Some(ColorSpace::DeviceRGB));
+        assert_eq!(image.filters, vec!["FlateDecode"]);
+        assert_eq!(image.reference.map(|r| r.id), Some(7));
+        assert_eq!(image.matrix.e, 300.0);
+    }
+
+    #[test]
+    fn test_export_jpeg() {
+        let images = images();
+        let result = images[0].export();
+        assert!(result.is_ok());
+        let (format, data) = result.unwrap();
+        assert_eq!(format, ImageFormat::Jpeg);
+        assert_eq!(format.extension(), "jpg");
+        assert_eq!(&data[..2], &[0xFF, 0xD8]);
+        assert_eq!(data, images[0].data);
+    }
+
+    #[test]
+    fn test_export_jpeg2000() {
+        let images = images();
+        assert_eq!(images[1].color_space, None);
+        let (format, data) = images[1].export().unwrap();
+        assert_eq!(format, ImageFormat::Jpeg2000);
+        assert_eq!(&data[4..8], b"jP  ");
+    }
+
+    #[test]
+    fn test_export_flate_with_predictor() {
+        let images = images();
+        let (format, data) = images[2].export().unwrap();
+        assert_eq!(format, ImageFormat::Png);
+        let (color_type, pixels) = decode_png(&data);
+        assert_eq!(color_type, png::ColorType::Rgb);
+        assert_eq!(pixels, vec![255, 0, 0, 0, 255, 0, 0, 0, 255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn test_export_indexed() {
+        let images = images();
+        match &images[3].color_space {
+            Some(ColorSpace::Indexed { base, hival, .. }) => {
+                assert_eq!(**base, ColorSpace::DeviceRGB);
+                assert_eq!(*hival, 1);
+            }
+            other => panic!("unexpected colour space {:?}", other),
+        }
+        let (_, data) = images[3].export().unwrap();
+        let (_, pixels) = decode_png(&data);
+        assert_eq!(pixels, vec![255, 0, 0, 0, 255, 0, 0, 255, 0, 255, 0, 0]);
+    }
+
+    #[test]
+    fn test_export_image_mask() {
+        let images = images();
+        let image = &images[4];
+        assert!(image.image_mask);
+        assert_eq!(image.bits_per_component, 1);
+        let (_, data) = image.export().unwrap();
+        let (color_type, pixels) = decode_png(&data);
+        assert_eq!(color_type, png::ColorType::Grayscale);
+        // `/Decode [1 0]` paints the 1 bits.
+        assert_eq!(pixels, vec![0, 0, 0, 0, 255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn test_export_inline_image() {
+        let images = images();
+        let image = &images[5];
+        assert_eq!(image.reference, None);
+        assert_eq!(image.color_space, Some(ColorSpace::DeviceGray));
+        assert_eq!(image.filters, vec!["ASCIIHexDecode"]);
+        let (_, data) = image.export().unwrap();
+        let (_, pixels) = decode_png(&data);
+        assert_eq!(pixels, vec![0x00, 0xFF, 0x80, 0x40]);
+    }
+
+    #[test]
+    fn test_pdf_images() {
+        let bytes = read_testcase("test_images.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let result = pdf.images();
+        assert!(result.is_ok());
+        let result = result.unwrap();
+        assert_eq!(result.images.len(), 6);
+        assert!(result.images.iter().all(|(page, _)| *page == 0));
+        assert!(result.errors.is_empty());
+    }
+
+    #[test]
+    fn test_resource_images() {
+        // Page 0 draws Im1 but neither Im2 nor the form Fm1 with Im3, listed by name. The content
+        // of page 1 cannot be parsed.
+        let input = b"%PDF-1.7
+1 0 obj << /Type /Catalog /Pages 2 0 R >> endobj
+2 0 obj << /Type /Pages /Count 2 /Kids [3 0 R 4 0 R] >> endobj
+3 0 obj << /Type /Page /Parent 2 0 R /Contents 5 0 R
+  /Resources << /XObject << /Im1 7 0 R /Im2 8 0 R /Fm1 9 0 R >> >> >> endobj
+4 0 obj << /Type /Page /Parent 2 0 R /Contents 6 0 R
+  /Resources << /XObject << /Im1 7 0 R >> >> >> endobj
+5 0 obj << /Length 10 >> stream
+/Im1 Do q
+endstream endobj
+6 0 obj << /Length 10 >> stream
+BT /F1 12
+endstream endobj
+7 0 obj << /Subtype /Image /Width 1 /Height 1 /ColorSpace /DeviceGray /Length 1 >> stream
+\x00
+endstream endobj
+8 0 obj << /Subtype /Image /Width 1 /Height 1 /ColorSpace /DeviceGray /Length 1 >> stream
+\xff
+endstream endobj
+9 0 obj << /Subtype /Form /Resources << /XObject << /Im3 10 0 R /Fm1 9 0 R >> >> /Length 0 >> stream
+endstream endobj
+10 0 obj << /Subtype /Image /Width 1 /Height 1 /ColorSpace /DeviceGray /Length 1 >> stream
+\x80
+endstream endobj
+trailer << /Root 1 0 R >>
+startxref
+0
+%%EOF
+";
+        let pdf = PDF::parse(input).unwrap();
+        let pages = pdf.pages().unwrap();
+        let names = |images: &[Image]| -> Vec<String> {
+            images
+                .iter()
+                .filter_map(|image| image.name.clone())
+                .collect()
+        };
+        assert_eq!(
+            names(&pages[0].images().unwrap()),
+            vec!["Im1", "Im3", "Im2"]
+        );
+        assert_eq!(names(&pages[1].resource_images()), vec!["Im1"]);
+        assert!(pages[1].images().is_err());
+
+        let result = pdf.images().unwrap();
+        let images: Vec<(usize, Option<&str>)> = result
+            .images
+            .iter()
+            .map(|(page, image)| (*page, image.name.as_deref()))
+            .collect();
+        assert_eq!(
+            images,
+            vec![
+                (0, Some("Im1")),
+                (0, Some("Im3")),
+                (0, Some("Im2")),
+                (1, Some("Im1"))
+            ]
+        );
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].0, 1);
+    }
+
+    #[test]
+    fn test_export_invalid_color_spaces() {
+        let input = b"%PDF-1.7
+1 0 obj << /Type /Catalog /Pages 2 0 R >> endobj
+2 0 obj << /Type /Pages /Count 1 /Kids [3 0 R] >> endobj
+3 0 obj << /Type /Page /Parent 2 0 R
+  /Resources << /XObject << /Im1 4 0 R /Im2 5 0 R /Im3 6 0 R /Im4 7 0 R >> >> >> endobj
+4 0 obj << /Subtype /Image /Width 2 /Height 2 /BitsPerComponent 8
+  /ColorSpace [/DeviceN [] /DeviceGray null] /Length 4 >> stream
+\x00\x00\x00\x00
+endstream endobj
+5 0 obj << /Subtype /Image /Width 2 /Height 2 /BitsPerComponent 8
+  /ColorSpace [/ICCBased 8 0 R] /Length 4 >> stream
+\x00\x00\x00\x00
+endstream endobj
+6 0 obj << /Subtype /Image /Width 2 /Height 2 /BitsPerComponent 8
+  /ColorSpace [/ICCBased 9 0 R] /Length 4 >> stream
+\x00\x00\x00\x00
+endstream endobj
+7 0 obj << /Subtype /Image /Width 2147483647 /Height 2147483647 /BitsPerComponent 16
+  /ColorSpace /DeviceRGB /Length 4 >> stream
+\x00\x00\x00\x00
+endstream endobj
+8 0 obj << /N 0 /Length 0 >> stream
+endstream endobj
+9 0 obj << /N 2147483647 /Length 0 >> stream
+endstream endobj
+trailer << /Root 1 0 R >>
+startxref
+0
+%%EOF
+";
+        let pdf = PDF::parse(input).unwrap();
+        let images = pdf.pages().unwrap()[0].resource_images();
+        assert_eq!(images.len(), 4);
+        for image in &images[..3] {
+            assert_eq!(image.color_space, None, "{:?}", image.name);
+        }
+        for image in &images {
+            assert!(image.export().is_err(), "{:?}", image.name);
+        }
+    }
+
+    #[test]
+    fn test_lzw_decode() {
+        // The example from the PDF specification.
+        let input = [0x80, 0x0B, 0x60, 0x50, 0x22, 0x0C, 0x0C, 0x85, 0x01];
+        let result = filter::lzw_decode(&input, true);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), b"-----A---B");
+    }
+
+    #[test]
+    fn test_run_length_decode() {
+        let input = [2, b'a', b'b', b'c', 254, b'x', 128];
+        let result = filter::run_length_decode(&input);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), b"abcxxx");
+    }
+
+    #[test]
+    fn test_decode_limits() {
+        use flate2::{write::ZlibEncoder, Compression};
+        use pdf_parser::error::ParseError;
+        use pdf_parser::object::{DictionaryObject, NameObject, Object};
+        use pdf_parser::options::ParseOptions;
+        use std::io::Write;
+
+        // 16 MB of zeros deflate to about 16 KB.
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::best());
+        encoder.write_all(&vec![0; 16 * 1024 * 1024]).unwrap();
+        let data = encoder.finish().unwrap();
+        let mut dictionary = DictionaryObject::new();
+        dictionary.insert(
+            NameObject("Filter"),
+            Object::Name(NameObject("FlateDecode")),
+        );
+
+        let result = filter::decode(&dictionary, &data);
+        assert_eq!(result.map(|decoded| decoded.len()), Ok(16 * 1024 * 1024));
+
+        let options = ParseOptions {
+            max_decoded_size: 1024 * 1024,
+            ..ParseOptions::default()
+        };
+        let result = filter::decode_with(&dictionary, &data, &options);
+        assert!(matches!(result, Err(ParseError::PDFLimitExceeded(_))));
+
+        let options = ParseOptions {
+            max_decompression_ratio: 100,
+            ..ParseOptions::default()
+        };
+        let result = filter::decode_with(&dictionary, &data, &options);
+        assert!(matches!(result, Err(ParseError::PDFLimitExceeded(_))));
+    }
+}
@@ -0,0 +1,157 @@
+#[cfg(test)]
+mod tests {
+    use pdf_parser::content::Content;
+    use pdf_parser::interpreter::{
+        FillRule, GraphicsState, ImageDraw, Interpreter, Matrix, Paint, PathSegment, TextShow,
+        Visitor,
+    };
+    use pdf_parser::object::PDF;
+    use std::{fs::File, io::Read, path::PathBuf};
+
+    fn read_testcase(filename: &str) -> Vec<u8> {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("assets");
+        path.push(filename);
+        let mut file = File::open(path).unwrap();
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer).unwrap();
+        buffer
+    }
+
+    #[derive(Default)]
+    struct Recorder {
+        paths: Vec<(Vec<PathSegment>, Paint, f32)>,
+        texts: Vec<(Vec<u8>, Vec<Matrix>, f32)>,
+        images: Vec<Matrix>,
+    }
+
+    impl Visitor for Recorder {
+        fn path(&mut self, path: &[PathSegment], paint: Paint, state: &GraphicsState) {
+            self.paths.push((path.to_vec(), paint, state.line_width));
+        }
+
+        fn text(&mut self, text: &TextShow, state: &GraphicsState) {
+            let matrices = text.glyphs.iter().map(|glyph| glyph.matrix).collect();
+            self.texts
+                .push((text.text.to_vec(), matrices, state.text_state.font_size));
+        }
+
+        fn image(&mut self, image: &ImageDraw, _state: &GraphicsState) {
+            self.images.push(image.matrix);
+        }
+    }
+
+    fn run(input: &[u8]) -> Recorder {
+        let bytes = read_testcase("test_content.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let content = Content::parse(input).unwrap();
+        let mut recorder = Recorder::default();
+        let result = Interpreter::new(&pdf).run(&content, None, &mut recorder);
+        assert!(result.is_ok());
+        recorder
+    }
+
+    #[test]
+    fn test_matrix_multiply() {
+        let scale = Matrix::new(2.0, 0.0, 0.0, 2.0, 0.0, 0.0);
+        let translate = Matrix::translate(10.0, 20.0);
+        // Scale first, then translate
+        assert_eq!((scale * translate).transform(1.0, 1.0), (12.0, 22.0));
+        // Translate first, then scale
+        assert_eq!((translate * scale).transform(1.0, 1.0), (22.0, 42.0));
+    }
+
+    #[test]
+    fn test_path_with_ctm() {
+        let recorder = run(b"q 2 0 0 2 10 10 cm 3 w 0 0 5 5 re f Q 0 0 m 1 1 l S");
+        assert_eq!(recorder.paths.len(), 2);
+
+        let (path, paint, line_width) = &recorder.paths[0];
+        assert_eq!(path[0], PathSegment::MoveTo(10.0, 10.0));
+        assert_eq!(path[2], PathSegment::LineTo(20.0, 20.0));
+        assert_eq!(paint.fill, Some(FillRule::NonZero));
+        assert!(!paint.stroke);
+        assert_eq!(*line_width, 3.0);
+
+        // The state is restored by Q
+        let (path, paint, line_width) = &recorder.paths[1];
+        assert_eq!(path[1], PathSegment::LineTo(1.0, 1.0));
+        assert!(paint.stroke);
+        assert_eq!(*line_width, 1.0);
+    }
+
+    #[test]
+    fn test_clip() {
+        let recorder = run(b"0 0 10 10 re W* n");
+        assert_eq!(recorder.paths.len(), 1);
+        let (_, paint, _) = &recorder.paths[0];
+        assert_eq!(paint.clip, Some(FillRule::EvenOdd));
+        assert_eq!(paint.fill, None);
+        assert!(!paint.stroke);
+    }
+
+    #[test]
+    fn test_text_matrices() {
+        let recorder =
+            run(b"1 0 0 1 0 100 cm BT /F1 10 Tf 14 TL 72 700 Td (Hi) Tj T* [(A)-500(B)] TJ ET");
+        assert_eq!(recorder.texts.len(), 3);
+
+        let (text, matrices, font_size) = &recorder.texts[0];
+        assert_eq!(text, b"Hi");
+        assert_eq!(*font_size, 10.0);
+        assert_eq!(matrices[0], Matrix::new(10.0, 0.0, 0.0, 10.0, 72.0, 800.0));
+
+        // T* moves to the next line
+        let (text, matrices, _) = &recorder.texts[1];
+        assert_eq!(text, b"A");
+        assert_eq!(matrices[0].f, 786.0);
+
+        // A TJ adjustment of -500 moves half the font size to the right
+        let (text, matrices, _) = &recorder.texts[2];
+        assert_eq!(text, b"B");
+        assert_eq!(matrices[0].e, 77.0);
+    }
+
+    #[test]
+    fn test_text_state_spacing() {
+        let recorder = run(b"BT /F1 10 Tf 2 Tc 3 Tw 50 Tz ( a) Tj ET");
+        let (_, matrices, _) = &recorder.texts[0];
+        // The space advances by (Tc + Tw) * Th
+        assert_eq!(matrices[0].e, 0.0);
+        assert_eq!(matrices[1].e, 2.5);
+        assert_eq!(matrices[1].a, 5.0);
+    }
+
+    #[test]
+    fn test_inline_image() {
+        let recorder = run(b"q 20 0 0 10 5 5 cm BI /W 1 /H 1 /BPC 8 /CS /G ID \x80 EI Q");
+        assert_eq!(recorder.images.len(), 1);
+        assert_eq!(
+            recorder.images[0],
+            Matrix::new(20.0, 0.0, 0.0, 10.0, 5.0, 5.0)
+        );
+    }
+
+    #[test]
+    fn test_invalid_operands() {
+        let bytes = read_testcase("test_content.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let content = Content::parse(b"1 0 0 cm").unwrap();
+        let result = Interpreter::new(&pdf).run(&content, None, &mut Recorder::default());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_interpret_page() {
+        let bytes = read_testcase("test_content.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let pages = pdf.pages().unwrap();
+        let mut recorder = Recorder::default();
+        let result = pages[0].interpret(&mut recorder);
+        assert!(result.is_ok());
+        assert_eq!(recorder.texts.len(), 3);
+        assert_eq!(recorder.images.len(), 1);
+        assert_eq!(recorder.texts[0].1[0].e, 72.0);
+        assert_eq!(recorder.texts[0].1[0].f, 700.0);
+    }
+}
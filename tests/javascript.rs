@@ -0,0 +1,109 @@
+#[cfg(test)]
+mod tests {
+    use pdf_parser::object::PDF;
+    use std::{fs::File, io::Read, path::PathBuf};
+
+    fn read_testcase(filename: &str) -> Vec<u8> {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("assets");
+        path.push(filename);
+        let mut file = File::open(path).unwrap();
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer).unwrap();
+        buffer
+    }
+
+    #[test]
+    fn test_javascript_open_action_and_field() {
+        let bytes = read_testcase("test.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let result = pdf.javascript();
+        assert!(result.is_ok());
+        let scripts = result.unwrap();
+        let paths: Vec<&str> = scripts.iter().map(|s| s.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "/Root/OpenAction",
+                "/Root/Pages[0]/AA/C",
+                "/Root/AcroForm/Fields[0]/AA/F"
+            ]
+        );
+        assert_eq!(scripts[0].reference.map(|r| r.id), Some(10));
+        assert_eq!(scripts[0].source.trim(), "");
+        assert_eq!(scripts[2].reference.map(|r| r.id), Some(12));
+        assert!(scripts[2].source.starts_with("function spray() {"));
+        assert!(scripts[2].source.contains("this.removeField(\"MyField\");"));
+    }
+
+    #[test]
+    fn test_javascript_all_locations() {
+        let bytes = read_testcase("test_javascript.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let scripts = pdf.javascript().unwrap();
+        let found: Vec<(&str, &str)> = scripts
+            .iter()
+            .map(|s| (s.path.as_str(), s.source.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("/Root/AA/WC", "will close"),
+                ("/Root/Names/JavaScript(a)", "var compressed = 1;"),
+                ("/Root/Names/JavaScript(b)", "app.alert('été');"),
+                ("/Root/Names/JavaScript(c)", "third"),
+                ("/Root/Pages[0]/AA/O", "page open"),
+                ("/Root/Pages[0]/Annots[0]/A/Next[0]", "first next"),
+                ("/Root/Pages[0]/Annots[0]/A/Next[1]", "second next"),
+                ("/Root/Outlines/First/A", "outline"),
+            ]
+        );
+        assert_eq!(scripts[1].reference.map(|r| r.id), Some(13));
+        assert_eq!(scripts[2].reference, None);
+    }
+
+    #[test]
+    fn test_javascript_with_broken_page_tree() {
+        // Object 9 does not exist, so the pages cannot be listed.
+        let input = b"%PDF-1.7
+1 0 obj << /Type /Catalog /Pages 2 0 R /OpenAction << /S /JavaScript /JS (app.alert(1)) >> >> endobj
+2 0 obj << /Type /Pages /Count 1 /Kids [9 0 R] >> endobj
+trailer << /Root 1 0 R >>
+startxref
+0
+%%EOF
+";
+        let pdf = PDF::parse(input).unwrap();
+        assert!(pdf.pages().is_err());
+        let graph = pdf.action_graph().unwrap();
+        assert_eq!(graph.errors.len(), 1);
+        let scripts = pdf.javascript().unwrap();
+        assert_eq!(scripts.len(), 1);
+        assert_eq!(scripts[0].path, "/Root/OpenAction");
+        assert_eq!(scripts[0].source, "app.alert(1)");
+    }
+
+    #[test]
+    fn test_javascript_with_escaped_names() {
+        let input = b"%PDF-1.7
+1 0 obj << /Type /Catalog /Pages 2 0 R /OpenAction << /S /Java#53cript /J#53 (app.alert(1)) >> >> endobj
+2 0 obj << /Type /Pages /Count 0 /Kids [] >> endobj
+trailer << /Root 1 0 R >>
+startxref
+0
+%%EOF
+";
+        let pdf = PDF::parse(input).unwrap();
+        let scripts = pdf.javascript().unwrap();
+        assert_eq!(scripts.len(), 1);
+        assert_eq!(scripts[0].source, "app.alert(1)");
+    }
+
+    #[test]
+    fn test_decode_text_string() {
+        use pdf_parser::encoding::decode_text_string;
+        assert_eq!(decode_text_string(b"caf\xE9 \x80 \x92"), "café • ™");
+        assert_eq!(decode_text_string(b"\xFE\xFF\x00A\xD8\x3D\xDE\x00"), "A😀");
+        assert_eq!(decode_text_string(b"\xEF\xBB\xBFh\xC3\xA9"), "hé");
+    }
+}
@@ -0,0 +1,146 @@
+#[cfg(test)]
+mod tests {
+    use pdf_parser::link::{find_urls, LinkKind};
+    use pdf_parser::object::{IndirectReference, PDF};
+    use std::{fs::File, io::Read, path::PathBuf};
+
+    fn read_testcase(filename: &str) -> Vec<u8> {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("assets");
+        path.push(filename);
+        let mut file = File::open(path).unwrap();
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer).unwrap();
+        buffer
+    }
+
+    fn reference(id: u32) -> Option<IndirectReference> {
+        Some(IndirectReference { id, generation: 0 })
+    }
+
+    #[test]
+    fn test_links() {
+        let bytes = read_testcase("test_links.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let links = pdf.links().unwrap();
+        let found: Vec<(&str, LinkKind, Option<usize>, Option<IndirectReference>)> = links
+            .iter()
+            .map(|link| (link.url.as_str(), link.kind, link.page, link.reference))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("http://open.example/", LinkKind::Uri, None, reference(1)),
+                (
+                    "https://example.com/a?b=c",
+                    LinkKind::Uri,
+                    Some(0),
+                    reference(10)
+                ),
+                ("cmd.exe", LinkKind::Launch, Some(0), reference(20)),
+                (
+                    "http://js.example/x",
+                    LinkKind::JavaScript,
+                    Some(0),
+                    reference(21)
+                ),
+                (
+                    "HTTPS://Upper.example/",
+                    LinkKind::JavaScript,
+                    Some(0),
+                    reference(21)
+                ),
+                (
+                    "https://collect.example/post",
+                    LinkKind::SubmitForm,
+                    Some(1),
+                    reference(12)
+                ),
+                (
+                    "\\\\server\\share\\doc.pdf",
+                    LinkKind::RemoteGoTo,
+                    Some(1),
+                    reference(13)
+                ),
+                (
+                    "https://evil.example/login",
+                    LinkKind::Text,
+                    Some(0),
+                    reference(3)
+                ),
+                ("www.example.org", LinkKind::Text, Some(0), reference(3)),
+            ]
+        );
+        assert_eq!(links[3].path, "/Root/Pages[0]/Annots[1]/A/Next");
+        assert_eq!(links[7].path, "/Root/Pages[0]/Contents");
+    }
+
+    #[test]
+    fn test_links_with_invalid_page_text() {
+        // The content of the page cannot be parsed.
+        let input = b"%PDF-1.7
+1 0 obj << /Type /Catalog /Pages 2 0 R /OpenAction << /S /URI /URI (https://example.com/open) >> >> endobj
+2 0 obj << /Type /Pages /Count 1 /Kids [3 0 R] >> endobj
+3 0 obj << /Type /Page /Parent 2 0 R /Contents 4 0 R >> endobj
+4 0 obj << /Length 25 >> stream
+BT (https://example.org) 
+endstream endobj
+trailer << /Root 1 0 R >>
+startxref
+0
+%%EOF
+";
+        let pdf = PDF::parse(input).unwrap();
+        assert!(pdf.pages().unwrap()[0].extract_text().is_err());
+        let links = pdf.links().unwrap();
+        let urls: Vec<(&str, LinkKind)> = links
+            .iter()
+            .map(|link| (link.url.as_str(), link.kind))
+            .collect();
+        assert_eq!(urls, vec![("https://example.com/open", LinkKind::Uri)]);
+    }
+
+    #[test]
+    fn test_links_with_broken_page_tree() {
+        // Object 9 does not exist, so the pages cannot be listed.
+        let input = b"%PDF-1.7
+1 0 obj << /Type /Catalog /Pages 2 0 R /OpenAction << /S /URI /URI (https://example.com/open) >> >> endobj
+2 0 obj << /Type /Pages /Count 1 /Kids [9 0 R] >> endobj
+trailer << /Root 1 0 R >>
+startxref
+0
+%%EOF
+";
+        let pdf = PDF::parse(input).unwrap();
+        assert!(pdf.pages().is_err());
+        let links = pdf.links().unwrap();
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].url, "https://example.com/open");
+        assert_eq!(links[0].kind, LinkKind::Uri);
+        assert_eq!(links[0].page, None);
+    }
+
+    #[test]
+    fn test_find_urls() {
+        assert_eq!(
+            find_urls("see <a href=\"http://a.example/x\">x</a> and (mailto:someone@example.com)."),
+            vec!["http://a.example/x", "mailto:someone@example.com"]
+        );
+        assert_eq!(
+            find_urls("ftp://files.example/pub; file:///etc/passwd! WWW.Example.COM?"),
+            vec![
+                "ftp://files.example/pub",
+                "file:///etc/passwd",
+                "WWW.Example.COM"
+            ]
+        );
+        assert!(find_urls("no links here, just http:// and www.").is_empty());
+        assert_eq!(
+            find_urls("é https://ü.example/ é"),
+            vec!["https://ü.example/"]
+        );
+        // Schemes that never appear do not make the scan quadratic.
+        let text = "http://a.example ".repeat(100_000);
+        assert_eq!(find_urls(&text).len(), 100_000);
+    }
+}
@@ -0,0 +1,213 @@
+#[cfg(test)]
+mod tests {
+    use pdf_parser::error::ParseError;
+    use pdf_parser::loader::{Loader, MAX_SECTION_SIZE};
+    use pdf_parser::object::{IndirectReference, Object, PDF};
+    use pdf_parser::writer::{write_object, Writer};
+    use std::io::{Cursor, Read, Seek, SeekFrom};
+    use std::{fs::File, path::PathBuf};
+
+    fn read_testcase(filename: &str) -> Vec<u8> {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("assets");
+        path.push(filename);
+        let mut file = File::open(path).unwrap();
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer).unwrap();
+        buffer
+    }
+
+    /// Counts the bytes read from the inner source.
+    struct Counter<R> {
+        inner: R,
+        read: usize,
+    }
+
+    impl<R: Read> Read for Counter<R> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let read = self.inner.read(buf)?;
+            self.read += read;
+            Ok(read)
+        }
+    }
+
+    impl<R: Seek> Seek for Counter<R> {
+        fn seek(&mut self, position: SeekFrom) -> std::io::Result<u64> {
+            self.inner.seek(position)
+        }
+    }
+
+    fn serialize(object: &Object) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        write_object(object, &mut bytes);
+        bytes
+    }
+
+    #[test]
+    fn test_loader_matches_parse() {
+        for name in [
+            "test_form.pdf",
+            "test_outlines.pdf",
+            "test_embedded_files.pdf",
+        ] {
+            let bytes = read_testcase(name);
+            let pdf = PDF::parse(bytes.as_slice()).unwrap();
+            let mut loader = Loader::new(Cursor::new(bytes.as_slice())).unwrap();
+            assert_eq!(loader.header(), pdf.header);
+            assert_eq!(
+                serialize(&loader.trailer().unwrap().dictionary),
+                serialize(&pdf.trailer.dictionary)
+            );
+            let references: Vec<IndirectReference> = loader.references().collect();
+            assert!(!references.is_empty());
+            for reference in references {
+                let buffer = loader.load(reference).unwrap();
+                assert_eq!(
+                    serialize(&buffer.object().unwrap()),
+                    serialize(pdf.get_object(reference).unwrap()),
+                    "{} {:?}",
+                    name,
+                    reference
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_loader_reads_only_requested_objects() {
+        // A document with large streams.
+        let bytes = read_testcase("test_embedded_files.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let mut writer = Writer::new(&pdf);
+        let data = vec![b'x'; 100_000];
+        for _ in 0..20 {
+            writer.add(&Object::Dictionary(Default::default(), &data));
+        }
+        let large = writer.rewrite();
+
+        let mut source = Counter {
+            inner: Cursor::new(large.as_slice()),
+            read: 0,
+        };
+        let mut loader = Loader::new(&mut source).unwrap();
+        let catalog = IndirectReference {
+            id: 1,
+            generation: 0,
+        };
+        let buffer = loader.load(catalog).unwrap();
+        assert!(buffer.bytes.starts_with(b"1 0 obj"));
+        assert!(buffer.object().unwrap().get("Pages").is_some());
+        drop(loader);
+        assert!(source.read < 10_000, "{}", source.read);
+        assert!(large.len() > 2_000_000);
+    }
+
+    #[test]
+    fn test_loader_incremental_update() {
+        let bytes = read_testcase("test_outlines.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let catalog = IndirectReference {
+            id: 1,
+            generation: 0,
+        };
+        let mut writer = Writer::new(&pdf);
+        writer.set(
+            catalog,
+            "PageMode",
+            &Object::Name(pdf_parser::object::NameObject("UseOutlines")),
+        );
+        let added = writer.add(&Object::Dictionary(Default::default(), &[]));
+        let saved = writer.incremental(&bytes);
+
+        let mut loader = Loader::new(Cursor::new(saved.as_slice())).unwrap();
+        assert!(loader.references().any(|reference| reference == added));
+        let trailer = loader.trailer().unwrap();
+        assert!(trailer.dictionary.get("Prev").is_some());
+        let buffer = loader.load(catalog).unwrap();
+        let object = buffer.object().unwrap();
+        assert_eq!(
+            object.get("PageMode").and_then(Object::as_name),
+            Some("UseOutlines")
+        );
+
+        // Objects untouched by the update come from the original section.
+        let pages: Vec<_> = [1, 2, 3, 4, 5]
+            .iter()
+            .map(|&id| {
+                loader
+                    .load(IndirectReference { id, generation: 0 })
+                    .unwrap()
+            })
+            .collect();
+        let document = loader.document(&pages).unwrap();
+        assert_eq!(document.pages().unwrap().len(), 3);
+        assert!(loader
+            .load(IndirectReference {
+                id: 99,
+                generation: 0
+            })
+            .is_err());
+    }
+
+    #[test]
+    fn test_loader_bounds_section_reads() {
+        let bytes = read_testcase("test_embedded_files.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let mut writer = Writer::new(&pdf);
+        let data = vec![b'x'; 100_000];
+        for _ in 0..20 {
+            writer.add(&Object::Dictionary(Default::default(), &data));
+        }
+        let mut large = writer.rewrite();
+        // Point `startxref` at the first object instead of the section.
+        let position = large
+            .windows(b"startxref".len())
+            .rposition(|window| window == b"startxref")
+            .unwrap();
+        large.truncate(position);
+        large.extend_from_slice(b"startxref\n9\n%%EOF\n");
+
+        let mut source = Counter {
+            inner: Cursor::new(large.as_slice()),
+            read: 0,
+        };
+        let result = Loader::with_max_section_size(&mut source, 256 * 1024);
+        assert_eq!(result.err(), Some(ParseError::InvalidPDFXrefTable));
+        assert!(source.read < 512 * 1024, "{}", source.read);
+    }
+
+    #[test]
+    fn test_loader_bounds_object_reads() {
+        let bytes = read_testcase("test_embedded_files.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let mut writer = Writer::new(&pdf);
+        let data = vec![b'x'; 100_000];
+        let stream = writer.add(&Object::Dictionary(Default::default(), &data));
+        let large = writer.rewrite();
+
+        let mut source = Counter {
+            inner: Cursor::new(large.as_slice()),
+            read: 0,
+        };
+        let mut loader = Loader::with_limits(&mut source, MAX_SECTION_SIZE, 50_000).unwrap();
+        assert!(matches!(
+            loader.load(stream),
+            Err(ParseError::PDFLimitExceeded(_))
+        ));
+        let catalog = IndirectReference {
+            id: 1,
+            generation: 0,
+        };
+        assert!(loader.load(catalog).is_ok());
+        drop(loader);
+        assert!(source.read < 10_000, "{}", source.read);
+    }
+
+    #[test]
+    fn test_loader_invalid() {
+        assert!(Loader::new(Cursor::new(&b"not a pdf"[..])).is_err());
+        let bytes = read_testcase("test_outlines.pdf");
+        let truncated = &bytes[..bytes.len() - 40];
+        assert!(Loader::new(Cursor::new(truncated)).is_err());
+    }
+}
@@ -0,0 +1,148 @@
+#[cfg(test)]
+mod tests {
+    use pdf_parser::metadata::{Date, Metadata, Mismatch};
+    use pdf_parser::object::PDF;
+    use std::{fs::File, io::Read, path::PathBuf};
+
+    fn read_testcase(filename: &str) -> Vec<u8> {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("assets");
+        path.push(filename);
+        let mut file = File::open(path).unwrap();
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer).unwrap();
+        buffer
+    }
+
+    fn date(year: u16, month: u8, day: u8, hour: u8, minute: u8, offset: Option<i16>) -> Date {
+        Date {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second: 0,
+            offset,
+        }
+    }
+
+    #[test]
+    fn test_parse_date() {
+        assert_eq!(
+            Date::parse("D:20230101120000+09'00'"),
+            Some(date(2023, 1, 1, 12, 0, Some(540)))
+        );
+        assert_eq!(
+            Date::parse("D:19991231235930-05'30"),
+            Some(Date {
+                second: 30,
+                ..date(1999, 12, 31, 23, 59, Some(-330))
+            })
+        );
+        assert_eq!(
+            Date::parse("D:20230102120000Z"),
+            Some(date(2023, 1, 2, 12, 0, Some(0)))
+        );
+        assert_eq!(Date::parse("2023"), Some(date(2023, 1, 1, 0, 0, None)));
+        assert_eq!(Date::parse("D:202302"), Some(date(2023, 2, 1, 0, 0, None)));
+        for invalid in [
+            "",
+            "D:",
+            "D:202",
+            "D:20231301",
+            "D:20230101250000",
+            "D:2023x",
+        ] {
+            assert_eq!(Date::parse(invalid), None, "{}", invalid);
+        }
+
+        assert_eq!(
+            Date::parse_xmp("2023-02-01T10:30:00.25+01:00"),
+            Some(date(2023, 2, 1, 10, 30, Some(60)))
+        );
+        assert_eq!(
+            Date::parse_xmp("2023-02"),
+            Some(date(2023, 2, 1, 0, 0, None))
+        );
+        assert_eq!(Date::parse_xmp("2023-2-1"), None);
+    }
+
+    #[test]
+    fn test_timestamp() {
+        assert_eq!(date(1970, 1, 1, 0, 0, None).timestamp(), 0);
+        assert_eq!(
+            date(2023, 1, 1, 12, 0, Some(540)).timestamp(),
+            date(2023, 1, 1, 3, 0, Some(0)).timestamp()
+        );
+        assert_eq!(date(2000, 3, 1, 0, 0, Some(0)).timestamp(), 951868800);
+        assert_eq!(date(1969, 12, 31, 23, 0, None).timestamp(), -3600);
+    }
+
+    #[test]
+    fn test_info() {
+        let bytes = read_testcase("test_metadata.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        assert_eq!(
+            pdf.info(),
+            Some(Metadata {
+                title: Some("The report & more".to_string()),
+                author: Some("Zoë Writer".to_string()),
+                subject: Some("Quarterly".to_string()),
+                keywords: Some("alpha, beta".to_string()),
+                creator: Some("Writer 1.0".to_string()),
+                producer: Some("Producer 1.0".to_string()),
+                creation_date: Some(date(2023, 1, 1, 12, 0, Some(540))),
+                modification_date: Some(date(2023, 1, 2, 0, 0, None)),
+            })
+        );
+    }
+
+    #[test]
+    fn test_xmp() {
+        let bytes = read_testcase("test_metadata.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let xmp = pdf.xmp().unwrap().unwrap();
+        assert!(xmp.starts_with(b"<?xpacket begin="));
+        assert!(xmp.ends_with(b"<?xpacket end=\"w\"?>"));
+        assert_eq!(
+            pdf.xmp_metadata().unwrap(),
+            Some(Metadata {
+                title: Some("The report & more".to_string()),
+                author: Some("Zoë Writer; Sam Editor".to_string()),
+                subject: None,
+                keywords: Some("alpha, beta".to_string()),
+                creator: Some("Writer 1.0".to_string()),
+                producer: Some("Other Producer 2.0".to_string()),
+                creation_date: Some(date(2023, 1, 1, 3, 0, Some(0))),
+                modification_date: Some(date(2023, 2, 1, 10, 30, Some(60))),
+            })
+        );
+    }
+
+    #[test]
+    fn test_mismatches() {
+        let bytes = read_testcase("test_metadata.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let fields: Vec<&str> = pdf
+            .metadata_mismatches()
+            .unwrap()
+            .iter()
+            .map(|mismatch| mismatch.field)
+            .collect();
+        // The creation dates are the same instant in different time zones.
+        assert_eq!(fields, vec!["Author", "Producer", "ModDate"]);
+        assert_eq!(
+            pdf.metadata_mismatches().unwrap()[1],
+            Mismatch {
+                field: "Producer",
+                info: "Producer 1.0".to_string(),
+                xmp: "Other Producer 2.0".to_string(),
+            }
+        );
+
+        let bytes = read_testcase("test.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        assert_eq!(pdf.xmp().unwrap(), None);
+        assert!(pdf.metadata_mismatches().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,122 @@
+#[cfg(test)]
+mod tests {
+    use pdf_parser::error::ParseError;
+    use pdf_parser::name_tree::{NameTree, NumberTree};
+    use pdf_parser::object::{Object, PDF};
+    use std::{fs::File, io::Read, path::PathBuf};
+
+    fn read_testcase(filename: &str) -> Vec<u8> {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("assets");
+        path.push(filename);
+        let mut file = File::open(path).unwrap();
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer).unwrap();
+        buffer
+    }
+
+    fn reference(id: u32) -> Object<'static> {
+        Object::IndirectReference { id, generation: 0 }
+    }
+
+    fn id(object: Option<&Object>) -> Option<u32> {
+        object
+            .and_then(Object::as_reference)
+            .map(|reference| reference.id)
+    }
+
+    #[test]
+    fn test_name_tree() {
+        let bytes = read_testcase("test_trees.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let tree = pdf.name_tree("Dests").unwrap().unwrap();
+        let keys: Vec<Vec<u8>> = tree.iter().map(|(key, _)| key).collect();
+        assert_eq!(
+            keys,
+            vec![
+                b"alpha".to_vec(),
+                b"beta".to_vec(),
+                b"gamma".to_vec(),
+                b"zeta".to_vec()
+            ]
+        );
+        assert_eq!(tree.entries().len(), 4);
+        assert_eq!(id(tree.get(&b"gamma".to_vec())), Some(9));
+        let zeta = tree.get(&b"zeta".to_vec()).unwrap();
+        assert_eq!(
+            pdf.resolve(zeta).as_array().unwrap()[1].as_name(),
+            Some("FitH")
+        );
+        assert!(tree.get(&b"delta".to_vec()).is_none());
+        assert_eq!(tree.validate(), Ok(()));
+        assert!(pdf.name_tree("Pages").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_number_tree() {
+        let bytes = read_testcase("test_trees.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let root = pdf.catalog().unwrap().get("PageLabels").unwrap();
+        let tree = NumberTree::new(&pdf, root);
+        let keys: Vec<i32> = tree.iter().map(|(key, _)| key).collect();
+        assert_eq!(keys, vec![0, 2, 4]);
+        let label = tree.get(&4).unwrap();
+        assert_eq!(pdf.get(label, "St").and_then(Object::as_i32), Some(7));
+        assert!(tree.get(&3).is_none());
+        assert_eq!(tree.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_invalid_trees() {
+        let bytes = read_testcase("test_trees.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+
+        let tree = pdf.name_tree("EmbeddedFiles").unwrap().unwrap();
+        assert_eq!(
+            tree.validate(),
+            Err(ParseError::InvalidPDFNameTree(
+                "/Kids[0]: keys (a) to (c) are outside /Limits [(a) (b)]".to_string()
+            ))
+        );
+        // Lookup trusts the limits.
+        assert!(tree.get(&b"c".to_vec()).is_none());
+        assert_eq!(tree.entries().len(), 2);
+
+        let unsorted = reference(50);
+        assert_eq!(
+            NumberTree::new(&pdf, &unsorted).validate(),
+            Err(ParseError::InvalidPDFNumberTree(
+                "key 1 is not after 3".to_string()
+            ))
+        );
+
+        let reversed = reference(60);
+        assert_eq!(
+            NumberTree::new(&pdf, &reversed).validate(),
+            Err(ParseError::InvalidPDFNumberTree(
+                "/Kids[0]: /Limits [5 1] are reversed".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_cyclic_tree() {
+        let bytes = read_testcase("test_trees.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let root = reference(40);
+        let tree = NameTree::new(&pdf, &root);
+        let entries: Vec<(Vec<u8>, Option<u32>)> = tree
+            .iter()
+            .map(|(key, value)| (key, id(Some(value))))
+            .collect();
+        assert_eq!(entries, vec![(b"init".to_vec(), Some(42))]);
+        assert_eq!(id(tree.get(&b"init".to_vec())), Some(42));
+        assert!(tree.get(&b"other".to_vec()).is_none());
+        assert_eq!(
+            tree.validate(),
+            Err(ParseError::InvalidPDFNameTree(
+                "/Kids[0]/Kids[0]: 40 0 R is reached twice".to_string()
+            ))
+        );
+    }
+}
@@ -0,0 +1,124 @@
+#[cfg(test)]
+mod tests {
+    use pdf_parser::action::Action;
+    use pdf_parser::object::PDF;
+    use pdf_parser::outline::{Bookmark, OutlineItem};
+    use pdf_parser::writer::Writer;
+    use std::{fs::File, io::Read, path::PathBuf};
+
+    fn read_testcase(filename: &str) -> Vec<u8> {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("assets");
+        path.push(filename);
+        let mut file = File::open(path).unwrap();
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer).unwrap();
+        buffer
+    }
+
+    /// Flattens the outline as (depth, title, page, count).
+    fn summary(items: &[OutlineItem], depth: usize) -> Vec<(usize, String, Option<usize>, i32)> {
+        items
+            .iter()
+            .flat_map(|item| {
+                let mut lines = vec![(depth, item.title.clone(), item.page, item.count)];
+                lines.extend(summary(&item.children, depth + 1));
+                lines
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_outline() {
+        let bytes = read_testcase("test_outlines.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let outline = pdf.outline().unwrap();
+        // The last item points back to the first one with `/Next`.
+        assert_eq!(
+            summary(&outline, 0),
+            vec![
+                (0, "Chapter 1".to_string(), Some(0), -2),
+                (1, "Section 1.1".to_string(), Some(1), 0),
+                (1, "Section 1.2 \u{2014} named".to_string(), Some(2), 0),
+                (0, "Résumé".to_string(), None, 0),
+            ]
+        );
+        assert!(!outline[0].is_open());
+        assert_eq!(outline[0].reference.map(|reference| reference.id), Some(11));
+        assert_eq!(outline[0].children[0].action, Some(Action::GoTo));
+        assert_eq!(
+            outline[1].action,
+            Some(Action::URI {
+                uri: "https://example.com".to_string(),
+                is_map: false
+            })
+        );
+    }
+
+    #[test]
+    fn test_outline_with_destination_cycle() {
+        // The `/D` of the destination dictionary is the dictionary itself.
+        let input = b"%PDF-1.7
+1 0 obj << /Type /Catalog /Pages 2 0 R /Outlines 4 0 R >> endobj
+2 0 obj << /Type /Pages /Count 1 /Kids [3 0 R] >> endobj
+3 0 obj << /Type /Page /Parent 2 0 R >> endobj
+4 0 obj << /Type /Outlines /First 5 0 R /Last 5 0 R /Count 1 >> endobj
+5 0 obj << /Title (Loop) /Parent 4 0 R /Dest 6 0 R >> endobj
+6 0 obj << /D 6 0 R >> endobj
+trailer << /Root 1 0 R >>
+startxref
+0
+%%EOF
+";
+        let pdf = PDF::parse(input).unwrap();
+        let outline = pdf.outline().unwrap();
+        assert_eq!(summary(&outline, 0), vec![(0, "Loop".to_string(), None, 0)]);
+    }
+
+    #[test]
+    fn test_no_outline() {
+        let bytes = read_testcase("test.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        assert!(pdf.outline().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_set_outline() {
+        let bytes = read_testcase("test_outlines.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let mut chapter = Bookmark::new("Chapter", 0);
+        chapter.children = vec![Bookmark::new("Part A", 1), Bookmark::new("Part B", 2)];
+        let mut closed = Bookmark::new("Annexe é", 2);
+        closed.open = false;
+        closed.children = vec![Bookmark::new("Table", 2)];
+        let mut writer = Writer::new(&pdf);
+        writer.set_outline(&[chapter, closed]).unwrap();
+
+        for saved in [writer.incremental(&bytes), writer.rewrite()] {
+            let pdf = PDF::parse(saved.as_slice()).unwrap();
+            assert_eq!(
+                summary(&pdf.outline().unwrap(), 0),
+                vec![
+                    (0, "Chapter".to_string(), Some(0), 2),
+                    (1, "Part A".to_string(), Some(1), 0),
+                    (1, "Part B".to_string(), Some(2), 0),
+                    (0, "Annexe é".to_string(), Some(2), -1),
+                    (1, "Table".to_string(), Some(2), 0),
+                ]
+            );
+            let catalog = pdf.catalog().unwrap();
+            let outlines = pdf.get(catalog, "Outlines").unwrap();
+            assert_eq!(
+                pdf.get(outlines, "Count").and_then(|count| count.as_i32()),
+                Some(4)
+            );
+        }
+
+        let mut writer = Writer::new(&pdf);
+        assert!(writer.set_outline(&[Bookmark::new("Far", 3)]).is_err());
+        writer.set_outline(&[]).unwrap();
+        let saved = writer.incremental(&bytes);
+        let pdf = PDF::parse(saved.as_slice()).unwrap();
+        assert!(pdf.outline().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,95 @@
+#[cfg(test)]
+mod tests {
+    use pdf_parser::object::PDF;
+    use pdf_parser::page_label::LabelStyle;
+    use std::{fs::File, io::Read, path::PathBuf};
+
+    fn read_testcase(filename: &str) -> Vec<u8> {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("assets");
+        path.push(filename);
+        let mut file = File::open(path).unwrap();
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer).unwrap();
+        buffer
+    }
+
+    #[test]
+    fn test_label_styles() {
+        let format = |style: LabelStyle, numbers: &[u32]| -> Vec<String> {
+            numbers.iter().map(|number| style.format(*number)).collect()
+        };
+        assert_eq!(
+            format(
+                LabelStyle::UpperRoman,
+                &[1, 4, 9, 14, 40, 90, 400, 1994, 4001]
+            ),
+            vec!["I", "IV", "IX", "XIV", "XL", "XC", "CD", "MCMXCIV", "MMMMI"]
+        );
+        assert_eq!(
+            format(LabelStyle::LowerRoman, &[3, 49]),
+            vec!["iii", "xlix"]
+        );
+        assert_eq!(
+            format(LabelStyle::UpperAlpha, &[1, 26, 27, 52, 53]),
+            vec!["A", "Z", "AA", "ZZ", "AAA"]
+        );
+        assert_eq!(format(LabelStyle::LowerAlpha, &[2, 28]), vec!["b", "bb"]);
+        assert_eq!(format(LabelStyle::Decimal, &[7]), vec!["7"]);
+    }
+
+    #[test]
+    fn test_label_styles_large_numbers() {
+        use pdf_parser::page_label::PageLabelRange;
+
+        assert_eq!(LabelStyle::UpperRoman.format(100_000), "M".repeat(100));
+        assert_eq!(LabelStyle::UpperRoman.format(101_000), "101000");
+        assert_eq!(LabelStyle::UpperAlpha.format(2600), "Z".repeat(100));
+        assert_eq!(LabelStyle::LowerAlpha.format(2601), "2601");
+        // 0 has no roman numeral or letter.
+        assert_eq!(LabelStyle::UpperAlpha.format(0), "0");
+        assert_eq!(LabelStyle::LowerRoman.format(0), "0");
+
+        // A hostile `/St`.
+        let range = PageLabelRange {
+            start: 0,
+            style: Some(LabelStyle::UpperAlpha),
+            prefix: "A-".to_string(),
+            first: i32::MAX as u32,
+        };
+        assert_eq!(range.label(1), "A-2147483648");
+    }
+
+    #[test]
+    fn test_page_labels() {
+        let bytes = read_testcase("test_page_labels.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        assert_eq!(
+            pdf.page_labels().unwrap(),
+            ["i", "ii", "iii", "1", "2", "A-Z", "A-AA", "Cover"]
+                .iter()
+                .map(|label| Some(label.to_string()))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(pdf.page_label(1).unwrap(), Some("ii".to_string()));
+        assert_eq!(pdf.page_label(8).unwrap(), None);
+        assert_eq!(pdf.page_index("1").unwrap(), Some(3));
+        assert_eq!(pdf.page_index("A-AA").unwrap(), Some(6));
+        assert_eq!(pdf.page_index("iv").unwrap(), None);
+
+        let ranges = pdf.page_label_ranges().unwrap();
+        assert_eq!(ranges.len(), 4);
+        assert_eq!(ranges[2].prefix, "A-");
+        assert_eq!(ranges[2].first, 26);
+        assert_eq!(ranges[3].style, None);
+    }
+
+    #[test]
+    fn test_no_page_labels() {
+        let bytes = read_testcase("test.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        assert!(pdf.page_label_ranges().unwrap().is_empty());
+        assert_eq!(pdf.page_label(0).unwrap(), None);
+        assert_eq!(pdf.page_index("1").unwrap(), None);
+    }
+}
@@ -0,0 +1,195 @@
+#[cfg(test)]
+mod tests {
+    use pdf_parser::object::{IndirectReference, Object, PDF};
+    use pdf_parser::writer::{write_object, Writer};
+    use std::io::Read;
+    use std::{fs::File, path::PathBuf};
+
+    fn read_testcase(filename: &str) -> Vec<u8> {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("assets");
+        path.push(filename);
+        let mut file = File::open(path).unwrap();
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer).unwrap();
+        buffer
+    }
+
+    fn serialize(object: &Object) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        write_object(object, &mut bytes);
+        bytes
+    }
+
+    fn assert_same(parallel: &PDF, sequential: &PDF) {
+        assert_eq!(parallel.header, sequential.header);
+        assert_eq!(parallel.body.len(), sequential.body.len());
+        for (parallel, sequential) in parallel.body.iter().zip(&sequential.body) {
+            assert_eq!(serialize(parallel), serialize(sequential));
+        }
+        assert_eq!(
+            parallel.cross_reference_tables,
+            sequential.cross_reference_tables
+        );
+        assert_eq!(
+            serialize(&parallel.trailer.dictionary),
+            serialize(&sequential.trailer.dictionary)
+        );
+        assert_eq!(parallel.trailer.startxref, sequential.trailer.startxref);
+    }
+
+    #[test]
+    fn test_parse_parallel_matches_parse() {
+        // The `startxref` of test.pdf is wrong, so it is parsed sequentially.
+        for name in [
+            "test.pdf",
+            "test_form.pdf",
+            "test_fonts.pdf",
+            "test_images.pdf",
+            "test_outlines.pdf",
+            "test_embedded_files.pdf",
+        ] {
+            let bytes = read_testcase(name);
+            let sequential = PDF::parse(&bytes).unwrap();
+            let parallel = PDF::parse_parallel(&bytes).unwrap();
+            assert_same(&parallel, &sequential);
+        }
+    }
+
+    #[test]
+    fn test_parse_parallel_incremental_update() {
+        let bytes = read_testcase("test_form.pdf");
+        let pdf = PDF::parse(&bytes).unwrap();
+        let mut writer = Writer::new(&pdf);
+        let root = pdf.trailer.dictionary.get("Root").unwrap().as_reference();
+        writer.set(
+            root.unwrap(),
+            "Lang",
+            &Object::Name(pdf_parser::object::NameObject("en")),
+        );
+        writer.add(&Object::Dictionary(Default::default(), &[]));
+        let updated = writer.incremental(&bytes);
+
+        let sequential = PDF::parse(&updated).unwrap();
+        let parallel = PDF::parse_parallel(&updated).unwrap();
+        assert_same(&parallel, &sequential);
+        assert!(sequential.body.len() > pdf.body.len());
+    }
+
+    #[test]
+    fn test_parse_parallel_falls_back() {
+        // Shift the offset of the first object in use, so that the regions no
+        // longer start at objects.
+        let mut bytes = read_testcase("test_form.pdf");
+        let xref = bytes
+            .windows(6)
+            .rposition(|window| window == b"\nxref\n")
+            .unwrap();
+        let entry = bytes[xref..]
+            .windows(3)
+            .position(|window| window == b" n ")
+            .unwrap()
+            + xref
+            - 16;
+        let offset: usize = std::str::from_utf8(&bytes[entry..entry + 10])
+            .unwrap()
+            .parse()
+            .unwrap();
+        bytes[entry..entry + 10].copy_from_slice(format!("{:010}", offset + 3).as_bytes());
+
+        let sequential = PDF::parse(&bytes).unwrap();
+        let parallel = PDF::parse_parallel(&bytes).unwrap();
+        assert_same(&parallel, &sequential);
+
+        let invalid = b"%PDF-1.7\n1 0 obj\n<< >>\nxref\n";
+        assert_eq!(
+            PDF::parse_parallel(invalid).err(),
+            PDF::parse(invalid).err()
+        );
+    }
+
+    #[test]
+    fn test_parse_parallel_with_limits() {
+        use pdf_parser::options::ParseOptions;
+
+        let bytes = read_testcase("test_form.pdf");
+        let count = PDF::parse(&bytes).unwrap().body.len();
+        for options in [
+            ParseOptions {
+                max_objects: count,
+                ..ParseOptions::default()
+            },
+            ParseOptions {
+                max_objects: count - 1,
+                ..ParseOptions::default()
+            },
+            ParseOptions {
+                max_depth: 1,
+                ..ParseOptions::default()
+            },
+        ] {
+            let sequential = PDF::parse_with(&bytes, &options);
+            let parallel = PDF::parse_parallel_with(&bytes, &options);
+            match (parallel, sequential) {
+                (Ok(parallel), Ok(sequential)) => assert_same(&parallel, &sequential),
+                (parallel, sequential) => {
+                    assert!(sequential.is_err(), "{:?}", options);
+                    assert_eq!(parallel.err(), sequential.err());
+                }
+            }
+        }
+        let limited = ParseOptions {
+            max_objects: count - 1,
+            ..ParseOptions::default()
+        };
+        assert!(PDF::parse_parallel_with(&bytes, &limited).is_err());
+    }
+
+    #[test]
+    fn test_parse_parallel_with_limits_in_trailer() {
+        use pdf_parser::options::ParseOptions;
+
+        let mut bytes = b"%PDF-1.7\n".to_vec();
+        let object = bytes.len();
+        bytes.extend_from_slice(b"1 0 obj\n<< /Type /Catalog >>\nendobj\n");
+        let xref = bytes.len();
+        bytes.extend_from_slice(
+            format!(
+                "xref\n0 2\n0000000000 65535 f \n{:010} 00000 n \n\
+                 trailer\n<< /Size 2 /Root 1 0 R /X [[[[[[1]]]]]] >>\n\
+                 startxref\n{}\n%%EOF\n",
+                object, xref
+            )
+            .as_bytes(),
+        );
+
+        let sequential = PDF::parse(&bytes).unwrap();
+        let parallel = PDF::parse_parallel(&bytes).unwrap();
+        assert_same(&parallel, &sequential);
+        let options = ParseOptions {
+            max_depth: 3,
+            ..ParseOptions::default()
+        };
+        let sequential = PDF::parse_with(&bytes, &options);
+        assert!(sequential.is_err());
+        assert_eq!(
+            PDF::parse_parallel_with(&bytes, &options).err(),
+            sequential.err()
+        );
+    }
+
+    #[test]
+    fn test_decode_streams_parallel() {
+        let bytes = read_testcase("test_images.pdf");
+        let pdf = PDF::parse_parallel(&bytes).unwrap();
+        let sequential = pdf.decode_streams();
+        let parallel = pdf.decode_streams_parallel();
+        assert!(!sequential.is_empty());
+        assert_eq!(parallel, sequential);
+        let references: Vec<IndirectReference> =
+            parallel.iter().map(|(reference, _)| *reference).collect();
+        assert!(references
+            .iter()
+            .all(|reference| pdf.get_object(*reference).is_some()));
+    }
+}
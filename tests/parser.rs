@@ -294,7 +294,7 @@ mod tests {
         assert_eq!(input, b""); // should consume input
         match obj {
             pdf_parser::object::Object::LiteralString(s) => {
-                assert_eq!(s, "This is a literal string")
+                assert_eq!(s, b"This is a literal string")
             }
             _ => panic!("Expected Object::LiteralString"),
         }
@@ -310,7 +310,7 @@ mod tests {
         assert_eq!(input, b""); // should consume input
         match obj {
             pdf_parser::object::Object::LiteralString(s) => {
-                assert_eq!(s, "This is a literal string with (parentheses)")
+                assert_eq!(s, b"This is a literal string with (parentheses)")
             }
             _ => panic!("Expected Object::LiteralString"),
         }
@@ -327,7 +327,7 @@ mod tests {
         assert_eq!(input, b""); // should consume input
         match obj {
             pdf_parser::object::Object::LiteralString(s) => {
-                assert_eq!(s, "This is a literal string with \nnewline")
+                assert_eq!(s, b"This is a literal string with \nnewline")
             }
             _ => panic!("Expected Object::LiteralString"),
         }
@@ -352,7 +352,7 @@ mod tests {
         assert_eq!(input, b"THIS_STRING_MUST_REMAINED"); // should consume input
         match obj {
             pdf_parser::object::Object::LiteralString(s) => {
-                assert_eq!(s, "This is a literal string")
+                assert_eq!(s, b"This is a literal string")
             }
             _ => panic!("Expected Object::LiteralString"),
         }
@@ -370,7 +370,7 @@ mod tests {
         assert_eq!(input, b""); // should consume input
         match obj {
             pdf_parser::object::Object::LiteralString(s) => {
-                assert_eq!(s, "This is a literal string with (escape)")
+                assert_eq!(s, b"This is a literal string with (escape)")
             }
             _ => panic!("Expected Object::LiteralString"),
         }
@@ -385,7 +385,7 @@ mod tests {
         let (input, obj) = result.unwrap();
         assert_eq!(input, b""); // should consume input
         match obj {
-            pdf_parser::object::Object::HexadecimalString(s) => assert_eq!(s, "48656C6C6F"),
+            pdf_parser::object::Object::HexadecimalString(s) => assert_eq!(s, b"48656C6C6F"),
             _ => panic!("Expected Object::HexadecimalString"),
         }
     }
@@ -393,10 +393,34 @@ mod tests {
     #[test]
     fn test_parse_hexadecimal_string_with_space() {
         // Test parsing hexadecimal string with space
-        let input = b"<48 65 6C 6C 6F>";
+        let input = b"< 48 65 6C\n6C 6F >";
         let result = pdf_parser::object::Object::parse_hexadecimal_string(input);
-        assert!(result.is_err());
-        assert_eq!(input, b"<48 65 6C 6C 6F>"); // should not consume input
+        assert!(result.is_ok());
+        let (input, obj) = result.unwrap();
+        assert_eq!(input, b""); // should consume input
+        assert_eq!(obj.string_bytes(), Some(b"Hello".to_vec()));
+    }
+
+    #[test]
+    fn test_parse_hexadecimal_string_empty() {
+        let (input, obj) = pdf_parser::object::Object::parse_hexadecimal_string(b"<>").unwrap();
+        assert_eq!(input, b"");
+        assert_eq!(obj.string_bytes(), Some(Vec::new()));
+        // A dictionary is not a hexadecimal string.
+        assert!(pdf_parser::object::Object::parse_hexadecimal_string(b"<< >>").is_err());
+    }
+
+    #[test]
+    fn test_parse_literal_string_non_utf8() {
+        // WinAnsiEncoding "café"
+        let input = b"(caf\xe9)";
+        let (input, obj) = pdf_parser::object::Object::parse_literal_string(input).unwrap();
+        assert_eq!(input, b"");
+        match obj {
+            pdf_parser::object::Object::LiteralString(s) => assert_eq!(s, b"caf\xe9"),
+            _ => panic!("Expected Object::LiteralString"),
+        }
+        assert_eq!(obj.text_string(), Some("café".to_string()));
     }
 
     #[test]
@@ -408,7 +432,7 @@ mod tests {
         let (input, obj) = result.unwrap();
         assert_eq!(input, b"THIS_STRING_MUST_REMAINED"); // should consume input
         match obj {
-            pdf_parser::object::Object::HexadecimalString(s) => assert_eq!(s, "48656C6C6F"),
+            pdf_parser::object::Object::HexadecimalString(s) => assert_eq!(s, b"48656C6C6F"),
             _ => panic!("Expected Object::HexadecimalString"),
         }
     }
@@ -531,6 +555,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_empty_array() {
+        // Test parsing empty array
+        let input = b"[ ]";
+        let result = pdf_parser::object::Object::parse_array(input);
+        assert!(result.is_ok());
+        let (input, obj) = result.unwrap();
+        assert_eq!(input, b""); // should consume input
+        match obj {
+            pdf_parser::object::Object::Array(a) => assert!(a.is_empty()),
+            _ => panic!("Expected Object::Array"),
+        }
+    }
+
     #[test]
     fn test_parse_array_with_multiple_types() {
         // Test parsing array
@@ -561,7 +599,7 @@ mod tests {
                 }
 
                 if let pdf_parser::object::Object::LiteralString(s) = a[3] {
-                    assert_eq!(s, "Ralph");
+                    assert_eq!(s, b"Ralph");
                 } else {
                     panic!("Expected Object::LiteralString");
                 }
@@ -746,12 +784,24 @@ mod tests {
         assert_eq!(input, b""); // should consume input
         match obj {
             pdf_parser::object::Object::Comment(s) => {
-                assert_eq!(s, " this is a comment");
+                assert_eq!(s, b" this is a comment");
             }
             _ => panic!("Expected Object::Comment"),
         }
     }
 
+    #[test]
+    fn test_parse_binary_comment() {
+        // The marker written after the header of files with binary data.
+        let input = b"%\xe2\xe3\xcf\xd3\n1 0 obj";
+        let (input, obj) = pdf_parser::object::Object::parse_comment(input).unwrap();
+        assert_eq!(input, b"1 0 obj");
+        assert!(matches!(
+            obj,
+            pdf_parser::object::Object::Comment(b"\xe2\xe3\xcf\xd3")
+        ));
+    }
+
     #[ignore]
     #[test]
     fn test_parse_one() {}
@@ -865,9 +915,7 @@ mod tests {
         let (input, stream) = result.unwrap();
         assert_eq!(input, b""); // should consume input
         let expected = read_testcase("test_stream.expected");
-        // expected to &str
-        let expected = std::str::from_utf8(expected.as_slice()).unwrap();
-        assert_eq!(stream, expected);
+        assert_eq!(stream, expected.as_slice());
     }
 
     #[test]
@@ -880,7 +928,6 @@ mod tests {
         let (input, obj) = result.unwrap();
         assert_eq!(input, b""); // should consume input
         let expected = read_testcase("test_stream.expected");
-        let expected = std::str::from_utf8(expected.as_slice()).unwrap();
         match obj {
             pdf_parser::object::Object::IndirectObject {
                 id,
@@ -892,7 +939,7 @@ mod tests {
                 let unboxed = *dictionary;
                 if let pdf_parser::object::Object::Dictionary(d, s) = unboxed {
                     assert_eq!(d.len(), 0);
-                    assert_eq!(s, expected);
+                    assert_eq!(s, expected.as_slice());
                 } else {
                     panic!("Expected Object::Dictionary");
                 }
@@ -943,4 +990,292 @@ mod tests {
             _ => panic!("Expected Object::Dictionary"),
         }
     }
+
+    #[test]
+    fn test_get_object() {
+        use pdf_parser::object::{IndirectReference, Object};
+
+        let input = b"%PDF-1.7\n1 0 obj\n<< /A 1 >>\nendobj\n2 0 obj\n<< >>\nendobj\n\
+            1 0 obj\n<< /A 2 >>\nendobj\ntrailer\n<< >>\nstartxref\n0\n%%EOF\n";
+        let mut pdf = PDF::parse(input).unwrap();
+        let reference = |id| IndirectReference { id, generation: 0 };
+        // The last definition wins.
+        let a = |pdf: &PDF, id| {
+            pdf.get_object(reference(id))
+                .and_then(|o| o.get("A")?.as_i32())
+        };
+        assert_eq!(a(&pdf, 1), Some(2));
+        assert!(pdf.get_object(reference(2)).is_some());
+        assert!(pdf.get_object(reference(3)).is_none());
+
+        // Objects added after a lookup are found.
+        let (_, object) = Object::parse_indirect_object(b"1 0 obj\n<< /A 3 >>\nendobj\n").unwrap();
+        pdf.body.push(object);
+        assert_eq!(a(&pdf, 1), Some(3));
+    }
+
+    #[test]
+    fn test_parse_nesting_limit() {
+        use pdf_parser::error::ParseError;
+        use pdf_parser::object::Object;
+        use pdf_parser::options::ParseOptions;
+
+        let nested = |depth: usize| format!("{}1{}", "[".repeat(depth), "]".repeat(depth));
+        assert!(Object::parse(nested(100).as_bytes()).is_ok());
+        let deep = nested(100_000);
+        let result = Object::parse(deep.as_bytes());
+        assert!(matches!(
+            result,
+            Err(nom::Err::Failure(ParseError::PDFLimitExceeded(_)))
+        ));
+
+        let options = ParseOptions {
+            max_depth: 3,
+            ..ParseOptions::default()
+        };
+        assert!(Object::parse_with(b"<< /A [<< /B 1 >>] >>", &options).is_ok());
+        let result = Object::parse_with(b"<< /A [<< /B [1] >>] >>", &options);
+        assert!(matches!(
+            result,
+            Err(nom::Err::Failure(ParseError::PDFLimitExceeded(_)))
+        ));
+    }
+
+    #[test]
+    fn test_parse_object_limit() {
+        use pdf_parser::error::ParseError;
+        use pdf_parser::options::ParseOptions;
+
+        let bytes = read_testcase("test.pdf");
+        let options = ParseOptions {
+            max_objects: 20,
+            ..ParseOptions::default()
+        };
+        assert!(PDF::parse_with(&bytes, &options).is_ok());
+        let options = ParseOptions {
+            max_objects: 19,
+            ..ParseOptions::default()
+        };
+        assert!(matches!(
+            PDF::parse_with(&bytes, &options),
+            Err(ParseError::PDFLimitExceeded(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_invalid_input_does_not_panic() {
+        // A dictionary followed by two streams.
+        let input = b"1 0 obj\n<< >>\nstream\nab\nendstream\nstream\ncd\nendstream\nendobj\n";
+        assert!(pdf_parser::object::Object::parse_indirect_object(input).is_err());
+        // A cross-reference entry with an unknown in-use flag.
+        let input = b"0000000000 65535 x \n";
+        assert!(pdf_parser::object::CrossReferenceEntry::parse(input).is_err());
+        // A dictionary key which is not a name.
+        assert!(pdf_parser::object::Object::parse_dictionary(b"<< 1 2 >>").is_err());
+    }
+
+    /// A small document, with the deviation accepted in lenient mode named
+    /// `deviation`, if any.
+    fn deviating_document(deviation: &str) -> String {
+        let endobj = if deviation == "endobj" {
+            ""
+        } else {
+            "endobj\n"
+        };
+        let offset = if deviation == "width" {
+            "9"
+        } else {
+            "0000000009"
+        };
+        let eof = if deviation == "eof" { "" } else { "%%EOF\n" };
+        format!(
+            "%PDF-1.7\n1 0 obj\n<< /Type /Catalog >>\n{}xref\n0 2\n\
+             0000000000 65535 f \n{} 00000 n \ntrailer\n<< /Size 2 /Root 1 0 R >>\n\
+             startxref\n40\n{}",
+            endobj, offset, eof
+        )
+    }
+
+    #[test]
+    fn test_parse_lenient() {
+        use pdf_parser::error::{ParseWarning, ParseWarningKind};
+        use pdf_parser::options::ParseOptions;
+
+        let document = deviating_document("");
+        assert!(PDF::parse(document.as_bytes()).is_ok());
+        let (_, warnings) =
+            PDF::parse_with_warnings(document.as_bytes(), &ParseOptions::lenient()).unwrap();
+        assert!(warnings.is_empty());
+
+        for (deviation, kind) in [
+            (
+                "endobj",
+                ParseWarningKind::MissingEndobj {
+                    id: 1,
+                    generation: 0,
+                },
+            ),
+            ("width", ParseWarningKind::XrefEntryWidth),
+            ("eof", ParseWarningKind::MissingEof),
+        ] {
+            let document = deviating_document(deviation);
+            let offset = match deviation {
+                "endobj" => document.find("xref").unwrap(),
+                "width" => document.find("\n9 ").unwrap() + 1,
+                _ => document.len(),
+            };
+            // Strict mode, the default, rejects the deviation.
+            assert!(PDF::parse(document.as_bytes()).is_err(), "{}", deviation);
+            let (pdf, warnings) =
+                PDF::parse_with_warnings(document.as_bytes(), &ParseOptions::lenient()).unwrap();
+            assert_eq!(
+                warnings,
+                vec![ParseWarning { offset, kind }],
+                "{}",
+                deviation
+            );
+            assert_eq!(pdf.body.len(), 1);
+            assert_eq!(pdf.cross_reference_tables[0].entries[1].offset, 9);
+        }
+    }
+
+    /// Rewrites every end-of-line marker of `bytes`, CR LF, LF or CR, as
+    /// `end_of_line`.
+    fn convert_end_of_lines(bytes: &[u8], end_of_line: &[u8]) -> Vec<u8> {
+        let mut result = Vec::with_capacity(bytes.len() * 2);
+        let mut iter = bytes.iter().peekable();
+        while let Some(&byte) = iter.next() {
+            match byte {
+                b'\r' if iter.peek() == Some(&&b'\n') => {}
+                b'\r' | b'\n' => result.extend_from_slice(end_of_line),
+                _ => result.push(byte),
+            }
+        }
+        result
+    }
+
+    fn serialize(object: &pdf_parser::object::Object) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        pdf_parser::writer::write_object(object, &mut bytes);
+        bytes
+    }
+
+    /// The object without its stream data, which is converted with the file.
+    fn without_stream<'a>(
+        object: &pdf_parser::object::Object<'a>,
+    ) -> (u32, u32, Vec<u8>, &'a [u8]) {
+        use pdf_parser::object::Object;
+        match object {
+            Object::IndirectObject {
+                id,
+                generation,
+                dictionary,
+            } => match dictionary.as_ref() {
+                Object::Dictionary(d, s) => (
+                    *id,
+                    *generation,
+                    serialize(&Object::Dictionary(d.clone(), &[])),
+                    *s,
+                ),
+                other => (*id, *generation, serialize(other), &[]),
+            },
+            other => (0, 0, serialize(other), &[]),
+        }
+    }
+
+    #[test]
+    fn test_parse_end_of_lines() {
+        // The fixtures written with LF only, so that converting back gives the
+        // original bytes.
+        for name in [
+            "test.pdf",
+            "test_actions.pdf",
+            "test_annotations.pdf",
+            "test_embedded_files.pdf",
+            "test_form.pdf",
+            "test_javascript.pdf",
+            "test_links.pdf",
+            "test_outlines.pdf",
+            "test_page_labels.pdf",
+            "test_spans.pdf",
+            "test_trees.pdf",
+            "test_xfa.pdf",
+        ] {
+            let bytes = read_testcase(name);
+            assert!(!bytes.contains(&b'\r'), "{}", name);
+            let original = PDF::parse(&bytes).unwrap();
+            for end_of_line in [&b"\r\n"[..], b"\r"] {
+                let converted = convert_end_of_lines(&bytes, end_of_line);
+                let pdf = PDF::parse(&converted)
+                    .unwrap_or_else(|error| panic!("{} {:?}: {:?}", name, end_of_line, error));
+                assert_eq!(pdf.header, original.header);
+                assert_eq!(pdf.body.len(), original.body.len(), "{}", name);
+                for (object, expected) in pdf.body.iter().zip(&original.body) {
+                    let (id, generation, dictionary, stream) = without_stream(object);
+                    let expected = without_stream(expected);
+                    assert_eq!((id, generation), (expected.0, expected.1), "{}", name);
+                    assert_eq!(dictionary, expected.2, "{} {} {}", name, id, generation);
+                    assert_eq!(convert_end_of_lines(stream, b"\n"), expected.3);
+                }
+                assert_eq!(
+                    pdf.cross_reference_tables, original.cross_reference_tables,
+                    "{}",
+                    name
+                );
+                assert_eq!(
+                    serialize(&pdf.trailer.dictionary),
+                    serialize(&original.trailer.dictionary)
+                );
+                assert_eq!(pdf.trailer.startxref, original.trailer.startxref);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_end_of_lines_fixtures() {
+        use pdf_parser::object::{CrossReferenceTable, Object, Trailer};
+
+        let xref = read_testcase("test_xref");
+        let (_, expected_xref) = CrossReferenceTable::parse(&xref).unwrap();
+        let trailer = read_testcase("test_trailer");
+        let (_, expected_trailer) = Trailer::parse(&trailer).unwrap();
+        let stream = read_testcase("test_stream");
+        let object = read_testcase("test_object_with_stream");
+        let expected_stream = read_testcase("test_stream.expected");
+
+        for end_of_line in [&b"\r\n"[..], b"\r"] {
+            let converted = convert_end_of_lines(&xref, end_of_line);
+            let (input, xref_table) = CrossReferenceTable::parse(&converted).unwrap();
+            assert_eq!(input, b"");
+            assert_eq!(xref_table, expected_xref);
+
+            let converted = convert_end_of_lines(&trailer, end_of_line);
+            let (input, trailer) = Trailer::parse(&converted).unwrap();
+            assert_eq!(input, b"");
+            assert_eq!(trailer.startxref, expected_trailer.startxref);
+            assert_eq!(
+                serialize(&trailer.dictionary),
+                serialize(&expected_trailer.dictionary)
+            );
+
+            let converted = convert_end_of_lines(&stream, end_of_line);
+            let (input, data) = Object::parse_stream(&converted).unwrap();
+            assert_eq!(input, b"");
+            assert_eq!(convert_end_of_lines(data, b"\n"), expected_stream);
+
+            let converted = convert_end_of_lines(&object, end_of_line);
+            let (input, object) = Object::parse_indirect_object(&converted).unwrap();
+            assert_eq!(input, b"");
+            let (id, generation, _, data) = without_stream(&object);
+            assert_eq!((id, generation), (13, 0));
+            assert_eq!(convert_end_of_lines(data, b"\n"), expected_stream);
+        }
+
+        // A cross-reference entry may end with CR LF instead of a space.
+        let (input, entry) =
+            pdf_parser::object::CrossReferenceEntry::parse(b"0000000015 00000 n\r\n").unwrap();
+        assert_eq!(input, b"");
+        assert_eq!(entry.offset, 15);
+        assert!(!entry.free);
+    }
 }
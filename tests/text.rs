@@ -0,0 +1,122 @@
+#[cfg(test)]
+mod tests {
+    use pdf_parser::interpreter::Rect;
+    use pdf_parser::object::PDF;
+    use pdf_parser::text::TextSpan;
+    use std::{fs::File, io::Read, path::PathBuf};
+
+    fn read_testcase(filename: &str) -> Vec<u8> {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("assets");
+        path.push(filename);
+        let mut file = File::open(path).unwrap();
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer).unwrap();
+        buffer
+    }
+
+    #[test]
+    fn test_extract_text() {
+        let bytes = read_testcase("test_text.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let pages = pdf.pages().unwrap();
+        assert_eq!(pages.len(), 2);
+        let result = pages[0].extract_text();
+        assert!(result.is_ok());
+        assert_eq!(
+            result.unwrap(),
+            "Hello World\nQuoted\nSpaced\n(c) 2023\nLeft Right\nLast"
+        );
+    }
+
+    #[test]
+    fn test_extract_text_empty_page() {
+        let bytes = read_testcase("test_text.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let pages = pdf.pages().unwrap();
+        let result = pages[1].extract_text();
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), "");
+    }
+
+    #[test]
+    fn test_extract_text_win_ansi() {
+        // Literal strings with WinAnsiEncoding bytes, not valid UTF-8.
+        let bytes = read_testcase("test_text_encodings.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let pages = pdf.pages().unwrap();
+        assert_eq!(
+            pages[0].extract_text().unwrap(),
+            "café crème\n\u{201C}Naïve\u{201D}"
+        );
+    }
+
+    #[test]
+    fn test_extract_text_identity_h() {
+        // A literal string of raw 2-byte codes, mapped by the ToUnicode CMap.
+        let bytes = read_testcase("test_text_encodings.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let pages = pdf.pages().unwrap();
+        assert_eq!(pages[1].extract_text().unwrap(), "日本語");
+        let spans: Vec<TextSpan> = pages[1].text_spans().unwrap().collect();
+        let codes: Vec<u32> = spans[0].glyphs.iter().map(|glyph| glyph.code).collect();
+        assert_eq!(codes, vec![0x0001, 0x80FF, 0xE9C8]);
+    }
+
+    #[test]
+    fn test_parse_literal_string_with_escaped_parenthesis() {
+        let input = b"(\\(c\\) 2023) Tj";
+        let result = pdf_parser::object::Object::parse_literal_string(input);
+        assert!(result.is_ok());
+        let (input, obj) = result.unwrap();
+        assert_eq!(input, b"Tj");
+        assert_eq!(obj.string_bytes().unwrap(), b"(c) 2023");
+    }
+
+    fn assert_rect(rect: Rect, expected: [f32; 4]) {
+        let actual = [rect.x0, rect.y0, rect.x1, rect.y1];
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-3, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_text_spans() {
+        let bytes = read_testcase("test_spans.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let pages = pdf.pages().unwrap();
+        let result = pages[0].text_spans();
+        assert!(result.is_ok());
+        let spans: Vec<TextSpan> = result.unwrap().collect();
+        assert_eq!(spans.len(), 2);
+
+        let span = &spans[0];
+        assert_eq!(span.text, "Hi");
+        assert_eq!(span.font_name.as_deref(), Some("Helvetica"));
+        assert_eq!(span.font_size, 10.0);
+        assert_eq!(span.fill_color.space, "DeviceRGB");
+        assert_eq!(span.fill_color.components, vec![1.0, 0.0, 0.0]);
+        assert_eq!(span.glyphs.len(), 2);
+        assert_eq!(span.glyphs[0].origin, (100.0, 700.0));
+        assert_rect(span.glyphs[0].bbox, [100.0, 697.93, 107.22, 707.18]);
+        assert_rect(span.glyphs[1].bbox, [107.22, 697.93, 109.44, 707.18]);
+        assert_rect(span.bbox, [100.0, 697.93, 109.44, 707.18]);
+    }
+
+    #[test]
+    fn test_text_spans_font_matrix() {
+        let bytes = read_testcase("test_spans.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let pages = pdf.pages().unwrap();
+        let spans: Vec<TextSpan> = pages[0].text_spans().unwrap().collect();
+
+        let span = &spans[1];
+        assert_eq!(span.text, "AA");
+        assert_eq!(span.font_name, None);
+        assert_eq!(span.font_size, 40.0);
+        assert_eq!(span.fill_color.components, vec![0.0, 0.0, 1.0]);
+        assert_eq!(span.glyphs[1].origin, (70.0, 500.0));
+        assert_eq!(span.glyphs[1].end, (90.0, 500.0));
+        assert_rect(span.bbox, [50.0, 492.0, 90.0, 532.0]);
+    }
+}
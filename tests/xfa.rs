@@ -0,0 +1,109 @@
+#[cfg(test)]
+mod tests {
+    use pdf_parser::object::PDF;
+    use std::{fs::File, io::Read, path::PathBuf};
+
+    fn read_testcase(filename: &str) -> Vec<u8> {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("assets");
+        path.push(filename);
+        let mut file = File::open(path).unwrap();
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer).unwrap();
+        buffer
+    }
+
+    #[test]
+    fn test_xfa_parts() {
+        let bytes = read_testcase("test.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let xfa = pdf.xfa().unwrap().unwrap();
+        let names: Vec<Option<&str>> = xfa.parts.iter().map(|part| part.name.as_deref()).collect();
+        assert_eq!(
+            names,
+            vec![Some("xdp:xdp"), Some("form"), Some("</xdp:xdp>")]
+        );
+        let xml = String::from_utf8(xfa.xml()).unwrap();
+        assert!(xml.starts_with("<?xml"));
+        assert!(xml.contains("<config></config>\n<template></template>"));
+        assert!(xml.trim_end().ends_with("</xdp:xdp>"));
+
+        // The "form" stream holds two packets.
+        let packets: Vec<(String, String)> = xfa
+            .packets()
+            .into_iter()
+            .map(|packet| (packet.name, packet.xml))
+            .collect();
+        assert_eq!(
+            packets,
+            vec![
+                ("config".to_string(), "<config></config>".to_string()),
+                ("template".to_string(), "<template></template>".to_string()),
+            ]
+        );
+        assert!(xfa.scripts().is_empty());
+    }
+
+    #[test]
+    fn test_xfa_packets() {
+        let bytes = read_testcase("test_xfa.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let xfa = pdf.xfa().unwrap().unwrap();
+        assert_eq!(xfa.parts.len(), 3);
+        let names: Vec<String> = xfa
+            .packets()
+            .into_iter()
+            .map(|packet| packet.name)
+            .collect();
+        assert_eq!(names, vec!["config", "template", "datasets"]);
+
+        // The template spans the three streams, one of them compressed.
+        let template = xfa.packet("template").unwrap();
+        assert!(template.xml.starts_with("<template xmlns="));
+        assert!(template.xml.contains("app.alert"));
+        assert!(template.xml.ends_with("</template>"));
+        let datasets = xfa.packet("datasets").unwrap();
+        assert!(datasets.xml.starts_with("<xfa:datasets"));
+        assert!(datasets.xml.contains("<total>3</total>"));
+        assert!(xfa.packet("localeSet").is_none());
+    }
+
+    #[test]
+    fn test_xfa_scripts() {
+        let bytes = read_testcase("test_xfa.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        let xfa = pdf.xfa().unwrap().unwrap();
+        let scripts = xfa.scripts();
+        assert_eq!(scripts.len(), 3);
+
+        assert_eq!(
+            scripts[0].path,
+            "template/subform[form1]/field[total]/event/script"
+        );
+        assert_eq!(
+            scripts[0].content_type.as_deref(),
+            Some("application/x-javascript")
+        );
+        assert_eq!(scripts[0].run_at, None);
+        assert_eq!(scripts[0].source, "if (a < b) { app.alert(\"hi\"); }");
+        let xml = xfa.xml();
+        assert!(xml[scripts[0].offset..].starts_with(b"<script "));
+
+        assert_eq!(
+            scripts[1].path,
+            "template/subform[form1]/field[total]/calculate/script"
+        );
+        assert_eq!(scripts[1].run_at.as_deref(), Some("server"));
+        assert_eq!(scripts[1].source, "Sum(a, b) > 0");
+
+        assert_eq!(scripts[2].path, "template/script");
+        assert_eq!(scripts[2].source, "");
+    }
+
+    #[test]
+    fn test_no_xfa() {
+        let bytes = read_testcase("test_form.pdf");
+        let pdf = PDF::parse(bytes.as_slice()).unwrap();
+        assert_eq!(pdf.xfa(), Ok(None));
+    }
+}